@@ -1,5 +1,5 @@
-use crate::secgov::models::FilingTransaction;
-use bigdecimal::{BigDecimal, FromPrimitive};
+use crate::secgov::models::{DerivativeTransaction, Filing, FilingTransaction, Holding, ReportingOwner};
+use bigdecimal::BigDecimal;
 use chrono::NaiveDate;
 
 pub struct NewIssuer<'a> {
@@ -9,7 +9,7 @@ pub struct NewIssuer<'a> {
 }
 
 impl NewIssuer<'_> {
-    pub fn map(filing: &FilingTransaction) -> NewIssuer {
+    pub fn map(filing: &Filing) -> NewIssuer<'_> {
         NewIssuer {
             issuer_name: &filing.company,
             issuer_symbol: &filing.symbol,
@@ -26,8 +26,8 @@ pub struct NewIndividual<'a> {
 }
 
 impl NewIndividual<'_> {
-    pub fn map(filing: &FilingTransaction) -> NewIndividual {
-        let split: Vec<_> = filing.owner.split(" ")
+    pub fn map(owner: &ReportingOwner) -> NewIndividual<'_> {
+        let split: Vec<_> = owner.name.split(" ")
             .map(|c| c.to_string())
             .collect();
 
@@ -36,15 +36,15 @@ impl NewIndividual<'_> {
             let first_name = Some(split[1..split.len()].join(" "));
 
             NewIndividual {
-                full_name: filing.owner.to_string(),
-                cik: &filing.owner_cik,
+                full_name: owner.name.to_string(),
+                cik: &owner.cik,
                 first_name,
                 last_name,
             }
         } else {
             NewIndividual {
-                full_name: filing.owner.to_string(),
-                cik: &filing.owner_cik,
+                full_name: owner.name.to_string(),
+                cik: &owner.cik,
                 first_name: None,
                 last_name: None,
             }
@@ -52,6 +52,38 @@ impl NewIndividual<'_> {
     }
 }
 
+pub struct NewFormOwner<'a> {
+    pub form_id: i64,
+    pub individual_id: i32,
+    pub relationships: Vec<i32>,
+    pub officer_title: &'a str,
+    pub other_text: &'a str,
+    pub roles: Vec<i32>,
+    pub street1: &'a str,
+    pub street2: &'a str,
+    pub city: &'a str,
+    pub state: &'a str,
+    pub zip_code: &'a str,
+}
+
+impl NewFormOwner<'_> {
+    pub fn map(owner: &ReportingOwner, form_id: i64, individual_id: i32) -> NewFormOwner<'_> {
+        NewFormOwner {
+            form_id,
+            individual_id,
+            relationships: owner.relationship.iter().map(|r| *r as i32).collect(),
+            officer_title: &owner.officer_title,
+            other_text: &owner.other_text,
+            roles: owner.roles.iter().map(|r| *r as i32).collect(),
+            street1: &owner.street1,
+            street2: &owner.street2,
+            city: &owner.city,
+            state: &owner.state,
+            zip_code: &owner.zip_code,
+        }
+    }
+}
+
 pub struct NewForm {
     pub issuer_id: i32,
     pub date_reported: NaiveDate,
@@ -59,10 +91,12 @@ pub struct NewForm {
     pub txt_url: String,
     pub web_url: String,
     pub access_no: String,
+    pub filed_date: Option<NaiveDate>,
+    pub original_submission: Option<NaiveDate>,
 }
 
 impl NewForm {
-    pub fn map(filing: &FilingTransaction, issuer_id: i32) -> NewForm {
+    pub fn map(filing: &Filing, issuer_id: i32) -> NewForm {
         NewForm {
             issuer_id,
             date_reported: filing.form_date,
@@ -70,23 +104,28 @@ impl NewForm {
             txt_url: filing.form_url.to_string(),
             web_url: filing.web_url.to_string(),
             access_no: filing.access_no.to_string(),
+            filed_date: filing.filed_date,
+            original_submission: filing.original_submission,
         }
     }
 }
 
 pub struct NewNonDerivTransaction {
-    pub date_reported: NaiveDate,
+    pub date_reported: Option<NaiveDate>,
     pub form_id: i64,
     pub issuer_id: i32,
     pub individual_id: i32,
     pub action_code: Option<String>,
     pub ownership_code: Option<String>,
     pub transaction_code: Option<String>,
-    pub shares_balance: BigDecimal,
-    pub shares_traded: BigDecimal,
-    pub avg_price: BigDecimal,
-    pub amount: BigDecimal,
+    pub shares_balance: Option<BigDecimal>,
+    pub shares_traded: Option<BigDecimal>,
+    pub avg_price: Option<BigDecimal>,
+    pub amount: Option<BigDecimal>,
     pub relationships: Vec<i32>,
+    pub officer_title: Option<String>,
+    pub other_text: Option<String>,
+    pub roles: Vec<i32>,
 }
 
 impl NewNonDerivTransaction {
@@ -103,11 +142,102 @@ impl NewNonDerivTransaction {
             action_code: Some(filing.action_code.clone()),
             ownership_code: Some(filing.ownership_code.clone()),
             transaction_code: Some(filing.trans_code.clone()),
-            shares_balance: BigDecimal::from_f32(filing.shares_owned).unwrap(),
-            shares_traded: BigDecimal::from_f32(filing.shares_traded).unwrap(),
-            avg_price: BigDecimal::from_f32(filing.avg_price).unwrap(),
-            amount: BigDecimal::from_f32(filing.amount).unwrap(),
+            shares_balance: filing.shares_owned.clone(),
+            shares_traded: filing.shares_traded.clone(),
+            avg_price: filing.avg_price.clone(),
+            amount: filing.amount.clone(),
+            relationships,
+            officer_title: Some(filing.officer_title.clone()).filter(|t| !t.is_empty()),
+            other_text: Some(filing.other_text.clone()).filter(|t| !t.is_empty()),
+            roles: filing.roles.iter().map(|r| *r as i32).collect(),
+        }
+    }
+}
+
+pub struct NewDerivTransaction {
+    pub date_reported: Option<NaiveDate>,
+    pub form_id: i64,
+    pub issuer_id: i32,
+    pub individual_id: i32,
+    pub security_title: String,
+    pub conversion_price: Option<BigDecimal>,
+    pub exercise_date: Option<NaiveDate>,
+    pub expiration_date: Option<NaiveDate>,
+    pub underlying_title: String,
+    pub underlying_shares: Option<BigDecimal>,
+    pub action_code: Option<String>,
+    pub ownership_code: Option<String>,
+    pub transaction_code: Option<String>,
+    pub shares_balance: Option<BigDecimal>,
+    pub shares_traded: Option<BigDecimal>,
+    pub avg_price: Option<BigDecimal>,
+    pub amount: Option<BigDecimal>,
+    pub relationships: Vec<i32>,
+}
+
+impl NewDerivTransaction {
+    pub fn map(filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, individual_id: i32) -> NewDerivTransaction {
+        let relationships = filing.primary_owner()
+            .map(|o| o.relationship.iter().map(|r| *r as i32).collect())
+            .unwrap_or_default();
+
+        NewDerivTransaction {
+            date_reported: trans.trans_date,
+            form_id,
+            issuer_id,
+            individual_id,
+            security_title: trans.security_title.clone(),
+            conversion_price: trans.conversion_price.clone(),
+            exercise_date: trans.exercise_date,
+            expiration_date: trans.expiration_date,
+            underlying_title: trans.underlying_title.clone(),
+            underlying_shares: trans.underlying_shares.clone(),
+            action_code: Some(trans.action_code.clone()),
+            ownership_code: Some(trans.ownership_code.clone()),
+            transaction_code: Some(trans.trans_code.clone()),
+            shares_balance: trans.shares_owned.clone(),
+            shares_traded: trans.shares_traded.clone(),
+            avg_price: trans.avg_price.clone(),
+            amount: trans.amount.clone(),
             relationships,
         }
     }
-}
\ No newline at end of file
+}
+
+pub struct NewHolding {
+    pub date_reported: NaiveDate,
+    pub form_id: i64,
+    pub issuer_id: i32,
+    pub individual_id: i32,
+    pub security_title: String,
+    pub shares_owned: Option<BigDecimal>,
+    pub ownership_code: Option<String>,
+    pub ownership_nature: Option<String>,
+    pub derivative: bool,
+    pub conversion_price: Option<BigDecimal>,
+    pub exercise_date: Option<NaiveDate>,
+    pub expiration_date: Option<NaiveDate>,
+    pub underlying_title: String,
+    pub underlying_shares: Option<BigDecimal>,
+}
+
+impl NewHolding {
+    pub fn map(filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, individual_id: i32) -> NewHolding {
+        NewHolding {
+            date_reported: filing.form_date,
+            form_id,
+            issuer_id,
+            individual_id,
+            security_title: holding.security_title.clone(),
+            shares_owned: holding.shares_owned.clone(),
+            ownership_code: Some(holding.ownership_code.clone()),
+            ownership_nature: Some(holding.ownership_nature.clone()),
+            derivative: holding.derivative,
+            conversion_price: holding.conversion_price.clone(),
+            exercise_date: holding.exercise_date,
+            expiration_date: holding.expiration_date,
+            underlying_title: holding.underlying_title.clone(),
+            underlying_shares: holding.underlying_shares.clone(),
+        }
+    }
+}
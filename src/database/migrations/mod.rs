@@ -0,0 +1,79 @@
+/// A schema change shipped with the crate. Versions are applied in ascending order and
+/// never edited once released, a change to the schema always gets a new version.
+///
+/// Every backend keeps its own scripts under a directory named after its dialect, with the
+/// same version numbers, and records what it applied in a `schema_migrations` table.
+pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+pub const MSSQL: &[Migration] = &[
+    Migration { version: 1, name: "initial_schema", sql: include_str!("mssql/0001_initial_schema.sql") },
+    Migration { version: 2, name: "lookup_indexes", sql: include_str!("mssql/0002_lookup_indexes.sql") },
+    Migration { version: 3, name: "mining_checkpoint", sql: include_str!("mssql/0003_mining_checkpoint.sql") },
+    Migration { version: 4, name: "form_amendments", sql: include_str!("mssql/0004_form_amendments.sql") },
+    Migration { version: 5, name: "proposed_sale", sql: include_str!("mssql/0005_proposed_sale.sql") },
+    Migration { version: 6, name: "beneficial_ownership", sql: include_str!("mssql/0006_beneficial_ownership.sql") },
+    Migration { version: 7, name: "institutional_holding", sql: include_str!("mssql/0007_institutional_holding.sql") },
+    Migration { version: 8, name: "xbrl_fact", sql: include_str!("mssql/0008_xbrl_fact.sql") },
+    Migration { version: 9, name: "material_event", sql: include_str!("mssql/0009_material_event.sql") },
+    Migration { version: 10, name: "form_document", sql: include_str!("mssql/0010_form_document.sql") },
+    Migration { version: 11, name: "holding_derivative_terms", sql: include_str!("mssql/0011_holding_derivative_terms.sql") },
+    Migration { version: 12, name: "row_footnotes", sql: include_str!("mssql/0012_row_footnotes.sql") },
+];
+
+pub const POSTGRES: &[Migration] = &[
+    Migration { version: 1, name: "initial_schema", sql: include_str!("postgres/0001_initial_schema.sql") },
+    Migration { version: 2, name: "lookup_indexes", sql: include_str!("postgres/0002_lookup_indexes.sql") },
+    Migration { version: 3, name: "mining_checkpoint", sql: include_str!("postgres/0003_mining_checkpoint.sql") },
+    Migration { version: 4, name: "form_amendments", sql: include_str!("postgres/0004_form_amendments.sql") },
+    Migration { version: 5, name: "proposed_sale", sql: include_str!("postgres/0005_proposed_sale.sql") },
+    Migration { version: 6, name: "beneficial_ownership", sql: include_str!("postgres/0006_beneficial_ownership.sql") },
+    Migration { version: 7, name: "institutional_holding", sql: include_str!("postgres/0007_institutional_holding.sql") },
+    Migration { version: 8, name: "xbrl_fact", sql: include_str!("postgres/0008_xbrl_fact.sql") },
+    Migration { version: 9, name: "material_event", sql: include_str!("postgres/0009_material_event.sql") },
+    Migration { version: 10, name: "form_document", sql: include_str!("postgres/0010_form_document.sql") },
+    Migration { version: 11, name: "holding_derivative_terms", sql: include_str!("postgres/0011_holding_derivative_terms.sql") },
+    Migration { version: 12, name: "row_footnotes", sql: include_str!("postgres/0012_row_footnotes.sql") },
+];
+
+pub const SQLITE: &[Migration] = &[
+    Migration { version: 1, name: "initial_schema", sql: include_str!("sqlite/0001_initial_schema.sql") },
+    Migration { version: 2, name: "lookup_indexes", sql: include_str!("sqlite/0002_lookup_indexes.sql") },
+    Migration { version: 3, name: "mining_checkpoint", sql: include_str!("sqlite/0003_mining_checkpoint.sql") },
+    Migration { version: 4, name: "form_amendments", sql: include_str!("sqlite/0004_form_amendments.sql") },
+    Migration { version: 5, name: "proposed_sale", sql: include_str!("sqlite/0005_proposed_sale.sql") },
+    Migration { version: 6, name: "beneficial_ownership", sql: include_str!("sqlite/0006_beneficial_ownership.sql") },
+    Migration { version: 7, name: "institutional_holding", sql: include_str!("sqlite/0007_institutional_holding.sql") },
+    Migration { version: 8, name: "xbrl_fact", sql: include_str!("sqlite/0008_xbrl_fact.sql") },
+    Migration { version: 9, name: "material_event", sql: include_str!("sqlite/0009_material_event.sql") },
+    Migration { version: 10, name: "form_document", sql: include_str!("sqlite/0010_form_document.sql") },
+    Migration { version: 11, name: "holding_derivative_terms", sql: include_str!("sqlite/0011_holding_derivative_terms.sql") },
+    Migration { version: 12, name: "row_footnotes", sql: include_str!("sqlite/0012_row_footnotes.sql") },
+];
+
+/// Migrations of `all` that are not in `applied`, in the order they have to run.
+pub fn pending<'a>(all: &'a [Migration], applied: &'a [i32]) -> impl Iterator<Item = &'a Migration> {
+    all.iter().filter(move |m| !applied.contains(&m.version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_are_strictly_increasing() {
+        for dialect in [MSSQL, POSTGRES, SQLITE] {
+            assert!(dialect.windows(2).all(|pair| pair[0].version < pair[1].version));
+        }
+    }
+
+    #[test]
+    fn dialects_share_version_numbers() {
+        let versions = |d: &[Migration]| d.iter().map(|m| (m.version, m.name)).collect::<Vec<_>>();
+        assert_eq!(versions(MSSQL), versions(POSTGRES));
+        assert_eq!(versions(MSSQL), versions(SQLITE));
+    }
+}
@@ -0,0 +1,15 @@
+CREATE INDEX ix_form_issuer_date ON form (issuer_id, date_reported);
+CREATE INDEX ix_form_date_reported ON form (date_reported);
+
+CREATE INDEX ix_form_owner_individual ON form_owner (individual_id);
+
+CREATE INDEX ix_non_deriv_transaction_form ON non_deriv_transaction (form_id, date_reported);
+CREATE INDEX ix_non_deriv_transaction_issuer_date ON non_deriv_transaction (issuer_id, date_reported);
+CREATE INDEX ix_non_deriv_transaction_individual ON non_deriv_transaction (individual_id);
+
+CREATE INDEX ix_deriv_transaction_form ON deriv_transaction (form_id, date_reported);
+CREATE INDEX ix_deriv_transaction_issuer_date ON deriv_transaction (issuer_id, date_reported);
+CREATE INDEX ix_deriv_transaction_individual ON deriv_transaction (individual_id);
+
+CREATE INDEX ix_holding_form ON holding (form_id);
+CREATE INDEX ix_holding_issuer ON holding (issuer_id);
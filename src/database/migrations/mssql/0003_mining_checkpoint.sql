@@ -0,0 +1,8 @@
+CREATE TABLE mining_checkpoint (
+    mine_date DATE NOT NULL,
+    entries INT NOT NULL,
+    transactions INT NOT NULL,
+    status NVARCHAR(16) NOT NULL,
+    updated_at DATETIME2 NOT NULL CONSTRAINT df_mining_checkpoint_updated_at DEFAULT SYSUTCDATETIME(),
+    CONSTRAINT pk_mining_checkpoint PRIMARY KEY (mine_date)
+);
@@ -0,0 +1,17 @@
+ALTER TABLE form ADD
+    filed_date DATE NULL,
+    original_submission DATE NULL,
+    amends_form_id BIGINT NULL CONSTRAINT fk_form_amends FOREIGN KEY REFERENCES form (form_id),
+    superseded_by BIGINT NULL CONSTRAINT fk_form_superseded_by FOREIGN KEY REFERENCES form (form_id);
+
+ALTER TABLE non_deriv_transaction ADD
+    superseded_by BIGINT NULL CONSTRAINT fk_non_deriv_transaction_superseded_by FOREIGN KEY REFERENCES form (form_id);
+
+ALTER TABLE deriv_transaction ADD
+    superseded_by BIGINT NULL CONSTRAINT fk_deriv_transaction_superseded_by FOREIGN KEY REFERENCES form (form_id);
+
+ALTER TABLE holding ADD
+    superseded_by BIGINT NULL CONSTRAINT fk_holding_superseded_by FOREIGN KEY REFERENCES form (form_id);
+
+CREATE INDEX ix_form_amends ON form (amends_form_id);
+CREATE INDEX ix_form_superseded_by ON form (superseded_by);
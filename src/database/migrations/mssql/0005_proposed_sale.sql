@@ -0,0 +1,22 @@
+CREATE TABLE proposed_sale (
+    proposed_sale_id BIGINT IDENTITY(1, 1) NOT NULL,
+    access_no NVARCHAR(25) NOT NULL,
+    form_type NVARCHAR(16) NOT NULL,
+    form_url NVARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    issuer_cik NVARCHAR(16) NOT NULL,
+    issuer_name NVARCHAR(256) NOT NULL,
+    seller_cik NVARCHAR(16) NOT NULL,
+    seller_name NVARCHAR(256) NOT NULL,
+    relationship NVARCHAR(256) NOT NULL,
+    security_class NVARCHAR(256) NOT NULL,
+    broker NVARCHAR(256) NOT NULL,
+    shares DECIMAL(38, 10) NULL,
+    market_value DECIMAL(38, 10) NULL,
+    approx_sale_date DATE NULL,
+    plan_adoption_date DATE NULL,
+    CONSTRAINT pk_proposed_sale PRIMARY KEY (proposed_sale_id)
+);
+
+CREATE INDEX ix_proposed_sale_access_no ON proposed_sale (access_no);
+CREATE INDEX ix_proposed_sale_seller ON proposed_sale (seller_cik, issuer_cik);
@@ -0,0 +1,24 @@
+CREATE TABLE beneficial_ownership (
+    ownership_id BIGINT IDENTITY(1, 1) NOT NULL,
+    issuer_id INT NOT NULL,
+    access_no NVARCHAR(25) NOT NULL,
+    form_type NVARCHAR(16) NOT NULL,
+    form_url NVARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    event_date DATE NULL,
+    issuer_cusip NVARCHAR(16) NOT NULL,
+    filer_cik NVARCHAR(16) NOT NULL,
+    filer_name NVARCHAR(256) NOT NULL,
+    amendment_no INT NULL,
+    percent_of_class DECIMAL(38, 10) NULL,
+    aggregate_shares DECIMAL(38, 10) NULL,
+    sole_voting DECIMAL(38, 10) NULL,
+    shared_voting DECIMAL(38, 10) NULL,
+    sole_dispositive DECIMAL(38, 10) NULL,
+    shared_dispositive DECIMAL(38, 10) NULL,
+    CONSTRAINT pk_beneficial_ownership PRIMARY KEY (ownership_id),
+    CONSTRAINT fk_beneficial_ownership_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_beneficial_ownership_access_no ON beneficial_ownership (access_no);
+CREATE INDEX ix_beneficial_ownership_issuer ON beneficial_ownership (issuer_id, filed_date);
@@ -0,0 +1,37 @@
+CREATE TABLE institutional_holding (
+    holding_id BIGINT IDENTITY(1, 1) NOT NULL,
+    issuer_id INT NULL,
+    access_no NVARCHAR(25) NOT NULL,
+    form_type NVARCHAR(16) NOT NULL,
+    amendment_type NVARCHAR(16) NULL,
+    form_url NVARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    period_of_report DATE NOT NULL,
+    manager_cik NVARCHAR(16) NOT NULL,
+    manager_name NVARCHAR(256) NOT NULL,
+    issuer_name NVARCHAR(256) NOT NULL,
+    title_of_class NVARCHAR(256) NOT NULL,
+    cusip NVARCHAR(16) NOT NULL,
+    market_value DECIMAL(38, 10) NULL,
+    shares DECIMAL(38, 10) NULL,
+    share_type NVARCHAR(8) NOT NULL,
+    put_call NVARCHAR(8) NULL,
+    investment_discretion NVARCHAR(8) NOT NULL,
+    voting_sole DECIMAL(38, 10) NULL,
+    voting_shared DECIMAL(38, 10) NULL,
+    voting_none DECIMAL(38, 10) NULL,
+    CONSTRAINT pk_institutional_holding PRIMARY KEY (holding_id),
+    CONSTRAINT fk_institutional_holding_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_institutional_holding_access_no ON institutional_holding (access_no);
+CREATE INDEX ix_institutional_holding_manager ON institutional_holding (manager_cik, period_of_report);
+CREATE INDEX ix_institutional_holding_cusip ON institutional_holding (cusip);
+
+-- issuers are identified by the first six characters of the cusip of any of their classes
+CREATE TABLE issuer_cusip (
+    cusip6 NVARCHAR(6) NOT NULL,
+    issuer_id INT NOT NULL,
+    CONSTRAINT pk_issuer_cusip PRIMARY KEY (cusip6),
+    CONSTRAINT fk_issuer_cusip_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
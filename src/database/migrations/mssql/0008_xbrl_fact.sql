@@ -0,0 +1,24 @@
+CREATE TABLE xbrl_fact (
+    fact_id BIGINT IDENTITY(1, 1) NOT NULL,
+    issuer_id INT NOT NULL,
+    access_no NVARCHAR(25) NOT NULL,
+    form_type NVARCHAR(16) NOT NULL,
+    fiscal_year INT NULL,
+    fiscal_period NVARCHAR(8) NOT NULL,
+    taxonomy NVARCHAR(64) NOT NULL,
+    concept NVARCHAR(256) NOT NULL,
+    label NVARCHAR(512) NOT NULL,
+    report NVARCHAR(512) NOT NULL,
+    report_group NVARCHAR(64) NOT NULL,
+    fact_value DECIMAL(38, 10) NOT NULL,
+    unit NVARCHAR(64) NOT NULL,
+    decimals INT NULL,
+    period_start DATE NULL,
+    period_end DATE NOT NULL,
+    dimensions NVARCHAR(2048) NOT NULL,
+    CONSTRAINT pk_xbrl_fact PRIMARY KEY (fact_id),
+    CONSTRAINT fk_xbrl_fact_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_xbrl_fact_access_no ON xbrl_fact (access_no);
+CREATE INDEX ix_xbrl_fact_issuer_period ON xbrl_fact (issuer_id, period_end, concept);
@@ -0,0 +1,17 @@
+CREATE TABLE material_event (
+    event_id BIGINT IDENTITY(1, 1) NOT NULL,
+    issuer_id INT NOT NULL,
+    access_no NVARCHAR(25) NOT NULL,
+    form_type NVARCHAR(16) NOT NULL,
+    form_url NVARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    event_date DATE NULL,
+    item NVARCHAR(8) NOT NULL,
+    item_title NVARCHAR(256) NOT NULL,
+    section NVARCHAR(MAX) NOT NULL,
+    CONSTRAINT pk_material_event PRIMARY KEY (event_id),
+    CONSTRAINT fk_material_event_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_material_event_access_no ON material_event (access_no);
+CREATE INDEX ix_material_event_issuer ON material_event (issuer_id, event_date, item);
@@ -0,0 +1,6 @@
+ALTER TABLE holding ADD
+    conversion_price DECIMAL(38, 10) NULL,
+    exercise_date DATE NULL,
+    expiration_date DATE NULL,
+    underlying_title NVARCHAR(256) NOT NULL CONSTRAINT df_holding_underlying_title DEFAULT '',
+    underlying_shares DECIMAL(38, 10) NULL;
@@ -0,0 +1,15 @@
+CREATE TABLE deriv_transaction_footnote (
+    transaction_id BIGINT NOT NULL,
+    field NVARCHAR(64) NOT NULL,
+    footnote_key NVARCHAR(16) NOT NULL,
+    CONSTRAINT pk_deriv_transaction_footnote PRIMARY KEY (transaction_id, field, footnote_key),
+    CONSTRAINT fk_deriv_transaction_footnote_transaction FOREIGN KEY (transaction_id) REFERENCES deriv_transaction (transaction_id)
+);
+
+CREATE TABLE holding_footnote (
+    holding_id BIGINT NOT NULL,
+    field NVARCHAR(64) NOT NULL,
+    footnote_key NVARCHAR(16) NOT NULL,
+    CONSTRAINT pk_holding_footnote PRIMARY KEY (holding_id, field, footnote_key),
+    CONSTRAINT fk_holding_footnote_holding FOREIGN KEY (holding_id) REFERENCES holding (holding_id)
+);
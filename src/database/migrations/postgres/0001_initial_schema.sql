@@ -0,0 +1,130 @@
+CREATE TABLE issuer (
+    issuer_id INTEGER GENERATED ALWAYS AS IDENTITY,
+    cik VARCHAR(16) NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    symbol VARCHAR(16) NOT NULL,
+    CONSTRAINT pk_issuer PRIMARY KEY (issuer_id),
+    CONSTRAINT uq_issuer_cik UNIQUE (cik)
+);
+
+CREATE TABLE individual (
+    individual_id INTEGER GENERATED ALWAYS AS IDENTITY,
+    cik VARCHAR(16) NOT NULL,
+    full_name VARCHAR(256) NOT NULL,
+    first_name VARCHAR(256) NULL,
+    last_name VARCHAR(256) NULL,
+    CONSTRAINT pk_individual PRIMARY KEY (individual_id),
+    CONSTRAINT uq_individual_cik UNIQUE (cik)
+);
+
+CREATE TABLE form (
+    form_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    issuer_id INTEGER NOT NULL,
+    date_reported DATE NOT NULL,
+    form_type VARCHAR(16) NOT NULL,
+    txt_url VARCHAR(512) NOT NULL,
+    web_url VARCHAR(512) NOT NULL,
+    access_no VARCHAR(25) NOT NULL,
+    CONSTRAINT pk_form PRIMARY KEY (form_id),
+    CONSTRAINT uq_form_access_no UNIQUE (access_no),
+    CONSTRAINT fk_form_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE TABLE form_owner (
+    form_id BIGINT NOT NULL,
+    individual_id INTEGER NOT NULL,
+    relationships VARCHAR(64) NOT NULL,
+    officer_title VARCHAR(256) NULL,
+    other_text VARCHAR(256) NULL,
+    roles VARCHAR(64) NOT NULL,
+    street1 VARCHAR(256) NULL,
+    street2 VARCHAR(256) NULL,
+    city VARCHAR(128) NULL,
+    state VARCHAR(32) NULL,
+    zip_code VARCHAR(16) NULL,
+    CONSTRAINT pk_form_owner PRIMARY KEY (form_id, individual_id),
+    CONSTRAINT fk_form_owner_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_form_owner_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE non_deriv_transaction (
+    transaction_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    date_reported DATE NULL,
+    form_id BIGINT NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    action_code VARCHAR(8) NULL,
+    ownership_code VARCHAR(8) NULL,
+    transaction_code VARCHAR(8) NULL,
+    shares_balance NUMERIC(38, 10) NULL,
+    shares_traded NUMERIC(38, 10) NULL,
+    avg_price NUMERIC(38, 10) NULL,
+    amount NUMERIC(38, 10) NULL,
+    relationships VARCHAR(64) NOT NULL,
+    officer_title VARCHAR(256) NULL,
+    other_text VARCHAR(256) NULL,
+    roles VARCHAR(64) NOT NULL,
+    CONSTRAINT pk_non_deriv_transaction PRIMARY KEY (transaction_id),
+    CONSTRAINT fk_non_deriv_transaction_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_non_deriv_transaction_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id),
+    CONSTRAINT fk_non_deriv_transaction_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE deriv_transaction (
+    transaction_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    date_reported DATE NULL,
+    form_id BIGINT NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    security_title VARCHAR(256) NOT NULL,
+    conversion_price NUMERIC(38, 10) NULL,
+    exercise_date DATE NULL,
+    expiration_date DATE NULL,
+    underlying_title VARCHAR(256) NOT NULL,
+    underlying_shares NUMERIC(38, 10) NULL,
+    action_code VARCHAR(8) NULL,
+    ownership_code VARCHAR(8) NULL,
+    transaction_code VARCHAR(8) NULL,
+    shares_balance NUMERIC(38, 10) NULL,
+    shares_traded NUMERIC(38, 10) NULL,
+    avg_price NUMERIC(38, 10) NULL,
+    amount NUMERIC(38, 10) NULL,
+    relationships VARCHAR(64) NOT NULL,
+    CONSTRAINT pk_deriv_transaction PRIMARY KEY (transaction_id),
+    CONSTRAINT fk_deriv_transaction_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_deriv_transaction_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id),
+    CONSTRAINT fk_deriv_transaction_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE holding (
+    holding_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    date_reported DATE NOT NULL,
+    form_id BIGINT NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    security_title VARCHAR(256) NOT NULL,
+    shares_owned NUMERIC(38, 10) NULL,
+    ownership_code VARCHAR(8) NULL,
+    ownership_nature VARCHAR(256) NULL,
+    derivative BOOLEAN NOT NULL,
+    CONSTRAINT pk_holding PRIMARY KEY (holding_id),
+    CONSTRAINT fk_holding_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_holding_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id),
+    CONSTRAINT fk_holding_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE footnote (
+    form_id BIGINT NOT NULL,
+    footnote_key VARCHAR(16) NOT NULL,
+    text TEXT NOT NULL,
+    CONSTRAINT pk_footnote PRIMARY KEY (form_id, footnote_key),
+    CONSTRAINT fk_footnote_form FOREIGN KEY (form_id) REFERENCES form (form_id)
+);
+
+CREATE TABLE transaction_footnote (
+    transaction_id BIGINT NOT NULL,
+    field VARCHAR(64) NOT NULL,
+    footnote_key VARCHAR(16) NOT NULL,
+    CONSTRAINT pk_transaction_footnote PRIMARY KEY (transaction_id, field, footnote_key),
+    CONSTRAINT fk_transaction_footnote_transaction FOREIGN KEY (transaction_id) REFERENCES non_deriv_transaction (transaction_id)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE mining_checkpoint (
+    mine_date DATE NOT NULL,
+    entries INTEGER NOT NULL,
+    transactions INTEGER NOT NULL,
+    status VARCHAR(16) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT pk_mining_checkpoint PRIMARY KEY (mine_date)
+);
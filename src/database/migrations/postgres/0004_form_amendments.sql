@@ -0,0 +1,17 @@
+ALTER TABLE form
+    ADD COLUMN filed_date DATE NULL,
+    ADD COLUMN original_submission DATE NULL,
+    ADD COLUMN amends_form_id BIGINT NULL CONSTRAINT fk_form_amends REFERENCES form (form_id),
+    ADD COLUMN superseded_by BIGINT NULL CONSTRAINT fk_form_superseded_by REFERENCES form (form_id);
+
+ALTER TABLE non_deriv_transaction
+    ADD COLUMN superseded_by BIGINT NULL CONSTRAINT fk_non_deriv_transaction_superseded_by REFERENCES form (form_id);
+
+ALTER TABLE deriv_transaction
+    ADD COLUMN superseded_by BIGINT NULL CONSTRAINT fk_deriv_transaction_superseded_by REFERENCES form (form_id);
+
+ALTER TABLE holding
+    ADD COLUMN superseded_by BIGINT NULL CONSTRAINT fk_holding_superseded_by REFERENCES form (form_id);
+
+CREATE INDEX ix_form_amends ON form (amends_form_id);
+CREATE INDEX ix_form_superseded_by ON form (superseded_by);
@@ -0,0 +1,22 @@
+CREATE TABLE proposed_sale (
+    proposed_sale_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    access_no VARCHAR(25) NOT NULL,
+    form_type VARCHAR(16) NOT NULL,
+    form_url VARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    issuer_cik VARCHAR(16) NOT NULL,
+    issuer_name VARCHAR(256) NOT NULL,
+    seller_cik VARCHAR(16) NOT NULL,
+    seller_name VARCHAR(256) NOT NULL,
+    relationship VARCHAR(256) NOT NULL,
+    security_class VARCHAR(256) NOT NULL,
+    broker VARCHAR(256) NOT NULL,
+    shares NUMERIC(38, 10) NULL,
+    market_value NUMERIC(38, 10) NULL,
+    approx_sale_date DATE NULL,
+    plan_adoption_date DATE NULL,
+    CONSTRAINT pk_proposed_sale PRIMARY KEY (proposed_sale_id)
+);
+
+CREATE INDEX ix_proposed_sale_access_no ON proposed_sale (access_no);
+CREATE INDEX ix_proposed_sale_seller ON proposed_sale (seller_cik, issuer_cik);
@@ -0,0 +1,24 @@
+CREATE TABLE beneficial_ownership (
+    ownership_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    issuer_id INTEGER NOT NULL,
+    access_no VARCHAR(25) NOT NULL,
+    form_type VARCHAR(16) NOT NULL,
+    form_url VARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    event_date DATE NULL,
+    issuer_cusip VARCHAR(16) NOT NULL,
+    filer_cik VARCHAR(16) NOT NULL,
+    filer_name VARCHAR(256) NOT NULL,
+    amendment_no INTEGER NULL,
+    percent_of_class NUMERIC(38, 10) NULL,
+    aggregate_shares NUMERIC(38, 10) NULL,
+    sole_voting NUMERIC(38, 10) NULL,
+    shared_voting NUMERIC(38, 10) NULL,
+    sole_dispositive NUMERIC(38, 10) NULL,
+    shared_dispositive NUMERIC(38, 10) NULL,
+    CONSTRAINT pk_beneficial_ownership PRIMARY KEY (ownership_id),
+    CONSTRAINT fk_beneficial_ownership_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_beneficial_ownership_access_no ON beneficial_ownership (access_no);
+CREATE INDEX ix_beneficial_ownership_issuer ON beneficial_ownership (issuer_id, filed_date);
@@ -0,0 +1,37 @@
+CREATE TABLE institutional_holding (
+    holding_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    issuer_id INTEGER NULL,
+    access_no VARCHAR(25) NOT NULL,
+    form_type VARCHAR(16) NOT NULL,
+    amendment_type VARCHAR(16) NULL,
+    form_url VARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    period_of_report DATE NOT NULL,
+    manager_cik VARCHAR(16) NOT NULL,
+    manager_name VARCHAR(256) NOT NULL,
+    issuer_name VARCHAR(256) NOT NULL,
+    title_of_class VARCHAR(256) NOT NULL,
+    cusip VARCHAR(16) NOT NULL,
+    market_value NUMERIC(38, 10) NULL,
+    shares NUMERIC(38, 10) NULL,
+    share_type VARCHAR(8) NOT NULL,
+    put_call VARCHAR(8) NULL,
+    investment_discretion VARCHAR(8) NOT NULL,
+    voting_sole NUMERIC(38, 10) NULL,
+    voting_shared NUMERIC(38, 10) NULL,
+    voting_none NUMERIC(38, 10) NULL,
+    CONSTRAINT pk_institutional_holding PRIMARY KEY (holding_id),
+    CONSTRAINT fk_institutional_holding_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_institutional_holding_access_no ON institutional_holding (access_no);
+CREATE INDEX ix_institutional_holding_manager ON institutional_holding (manager_cik, period_of_report);
+CREATE INDEX ix_institutional_holding_cusip ON institutional_holding (cusip);
+
+-- issuers are identified by the first six characters of the cusip of any of their classes
+CREATE TABLE issuer_cusip (
+    cusip6 VARCHAR(6) NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    CONSTRAINT pk_issuer_cusip PRIMARY KEY (cusip6),
+    CONSTRAINT fk_issuer_cusip_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
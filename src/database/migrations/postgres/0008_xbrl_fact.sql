@@ -0,0 +1,24 @@
+CREATE TABLE xbrl_fact (
+    fact_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    issuer_id INTEGER NOT NULL,
+    access_no VARCHAR(25) NOT NULL,
+    form_type VARCHAR(16) NOT NULL,
+    fiscal_year INTEGER NULL,
+    fiscal_period VARCHAR(8) NOT NULL,
+    taxonomy VARCHAR(64) NOT NULL,
+    concept VARCHAR(256) NOT NULL,
+    label VARCHAR(512) NOT NULL,
+    report VARCHAR(512) NOT NULL,
+    report_group VARCHAR(64) NOT NULL,
+    fact_value NUMERIC(38, 10) NOT NULL,
+    unit VARCHAR(64) NOT NULL,
+    decimals INTEGER NULL,
+    period_start DATE NULL,
+    period_end DATE NOT NULL,
+    dimensions VARCHAR(2048) NOT NULL,
+    CONSTRAINT pk_xbrl_fact PRIMARY KEY (fact_id),
+    CONSTRAINT fk_xbrl_fact_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_xbrl_fact_access_no ON xbrl_fact (access_no);
+CREATE INDEX ix_xbrl_fact_issuer_period ON xbrl_fact (issuer_id, period_end, concept);
@@ -0,0 +1,17 @@
+CREATE TABLE material_event (
+    event_id BIGINT GENERATED ALWAYS AS IDENTITY,
+    issuer_id INTEGER NOT NULL,
+    access_no VARCHAR(25) NOT NULL,
+    form_type VARCHAR(16) NOT NULL,
+    form_url VARCHAR(512) NOT NULL,
+    filed_date DATE NULL,
+    event_date DATE NULL,
+    item VARCHAR(8) NOT NULL,
+    item_title VARCHAR(256) NOT NULL,
+    section TEXT NOT NULL,
+    CONSTRAINT pk_material_event PRIMARY KEY (event_id),
+    CONSTRAINT fk_material_event_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_material_event_access_no ON material_event (access_no);
+CREATE INDEX ix_material_event_issuer ON material_event (issuer_id, event_date, item);
@@ -0,0 +1,10 @@
+CREATE TABLE form_document (
+    form_id BIGINT NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    seq INTEGER NULL,
+    doc_type VARCHAR(32) NOT NULL,
+    description VARCHAR(256) NOT NULL,
+    size BIGINT NULL,
+    CONSTRAINT pk_form_document PRIMARY KEY (form_id, name),
+    CONSTRAINT fk_form_document_form FOREIGN KEY (form_id) REFERENCES form (form_id)
+);
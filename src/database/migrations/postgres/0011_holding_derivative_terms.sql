@@ -0,0 +1,6 @@
+ALTER TABLE holding
+    ADD COLUMN conversion_price NUMERIC(38, 10) NULL,
+    ADD COLUMN exercise_date DATE NULL,
+    ADD COLUMN expiration_date DATE NULL,
+    ADD COLUMN underlying_title VARCHAR(256) NOT NULL DEFAULT '',
+    ADD COLUMN underlying_shares NUMERIC(38, 10) NULL;
@@ -0,0 +1,127 @@
+-- Dates are ISO 8601 text and decimals are text as well, so values round-trip exactly
+-- instead of going through a REAL.
+
+CREATE TABLE issuer (
+    issuer_id INTEGER PRIMARY KEY,
+    cik TEXT NOT NULL,
+    name TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    CONSTRAINT uq_issuer_cik UNIQUE (cik)
+);
+
+CREATE TABLE individual (
+    individual_id INTEGER PRIMARY KEY,
+    cik TEXT NOT NULL,
+    full_name TEXT NOT NULL,
+    first_name TEXT NULL,
+    last_name TEXT NULL,
+    CONSTRAINT uq_individual_cik UNIQUE (cik)
+);
+
+CREATE TABLE form (
+    form_id INTEGER PRIMARY KEY,
+    issuer_id INTEGER NOT NULL,
+    date_reported TEXT NOT NULL,
+    form_type TEXT NOT NULL,
+    txt_url TEXT NOT NULL,
+    web_url TEXT NOT NULL,
+    access_no TEXT NOT NULL,
+    CONSTRAINT uq_form_access_no UNIQUE (access_no),
+    CONSTRAINT fk_form_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE TABLE form_owner (
+    form_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    relationships TEXT NOT NULL,
+    officer_title TEXT NULL,
+    other_text TEXT NULL,
+    roles TEXT NOT NULL,
+    street1 TEXT NULL,
+    street2 TEXT NULL,
+    city TEXT NULL,
+    state TEXT NULL,
+    zip_code TEXT NULL,
+    CONSTRAINT pk_form_owner PRIMARY KEY (form_id, individual_id),
+    CONSTRAINT fk_form_owner_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_form_owner_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE non_deriv_transaction (
+    transaction_id INTEGER PRIMARY KEY,
+    date_reported TEXT NULL,
+    form_id INTEGER NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    action_code TEXT NULL,
+    ownership_code TEXT NULL,
+    transaction_code TEXT NULL,
+    shares_balance TEXT NULL,
+    shares_traded TEXT NULL,
+    avg_price TEXT NULL,
+    amount TEXT NULL,
+    relationships TEXT NOT NULL,
+    officer_title TEXT NULL,
+    other_text TEXT NULL,
+    roles TEXT NOT NULL,
+    CONSTRAINT fk_non_deriv_transaction_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_non_deriv_transaction_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id),
+    CONSTRAINT fk_non_deriv_transaction_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE deriv_transaction (
+    transaction_id INTEGER PRIMARY KEY,
+    date_reported TEXT NULL,
+    form_id INTEGER NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    security_title TEXT NOT NULL,
+    conversion_price TEXT NULL,
+    exercise_date TEXT NULL,
+    expiration_date TEXT NULL,
+    underlying_title TEXT NOT NULL,
+    underlying_shares TEXT NULL,
+    action_code TEXT NULL,
+    ownership_code TEXT NULL,
+    transaction_code TEXT NULL,
+    shares_balance TEXT NULL,
+    shares_traded TEXT NULL,
+    avg_price TEXT NULL,
+    amount TEXT NULL,
+    relationships TEXT NOT NULL,
+    CONSTRAINT fk_deriv_transaction_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_deriv_transaction_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id),
+    CONSTRAINT fk_deriv_transaction_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE holding (
+    holding_id INTEGER PRIMARY KEY,
+    date_reported TEXT NOT NULL,
+    form_id INTEGER NOT NULL,
+    issuer_id INTEGER NOT NULL,
+    individual_id INTEGER NOT NULL,
+    security_title TEXT NOT NULL,
+    shares_owned TEXT NULL,
+    ownership_code TEXT NULL,
+    ownership_nature TEXT NULL,
+    derivative INTEGER NOT NULL,
+    CONSTRAINT fk_holding_form FOREIGN KEY (form_id) REFERENCES form (form_id),
+    CONSTRAINT fk_holding_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id),
+    CONSTRAINT fk_holding_individual FOREIGN KEY (individual_id) REFERENCES individual (individual_id)
+);
+
+CREATE TABLE footnote (
+    form_id INTEGER NOT NULL,
+    footnote_key TEXT NOT NULL,
+    text TEXT NOT NULL,
+    CONSTRAINT pk_footnote PRIMARY KEY (form_id, footnote_key),
+    CONSTRAINT fk_footnote_form FOREIGN KEY (form_id) REFERENCES form (form_id)
+);
+
+CREATE TABLE transaction_footnote (
+    transaction_id INTEGER NOT NULL,
+    field TEXT NOT NULL,
+    footnote_key TEXT NOT NULL,
+    CONSTRAINT pk_transaction_footnote PRIMARY KEY (transaction_id, field, footnote_key),
+    CONSTRAINT fk_transaction_footnote_transaction FOREIGN KEY (transaction_id) REFERENCES non_deriv_transaction (transaction_id)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE mining_checkpoint (
+    mine_date TEXT NOT NULL,
+    entries INTEGER NOT NULL,
+    transactions INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT pk_mining_checkpoint PRIMARY KEY (mine_date)
+);
@@ -0,0 +1,13 @@
+-- SQLite adds one column per statement.
+
+ALTER TABLE form ADD COLUMN filed_date TEXT NULL;
+ALTER TABLE form ADD COLUMN original_submission TEXT NULL;
+ALTER TABLE form ADD COLUMN amends_form_id INTEGER NULL CONSTRAINT fk_form_amends REFERENCES form (form_id);
+ALTER TABLE form ADD COLUMN superseded_by INTEGER NULL CONSTRAINT fk_form_superseded_by REFERENCES form (form_id);
+
+ALTER TABLE non_deriv_transaction ADD COLUMN superseded_by INTEGER NULL CONSTRAINT fk_non_deriv_transaction_superseded_by REFERENCES form (form_id);
+ALTER TABLE deriv_transaction ADD COLUMN superseded_by INTEGER NULL CONSTRAINT fk_deriv_transaction_superseded_by REFERENCES form (form_id);
+ALTER TABLE holding ADD COLUMN superseded_by INTEGER NULL CONSTRAINT fk_holding_superseded_by REFERENCES form (form_id);
+
+CREATE INDEX ix_form_amends ON form (amends_form_id);
+CREATE INDEX ix_form_superseded_by ON form (superseded_by);
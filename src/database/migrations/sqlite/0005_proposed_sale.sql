@@ -0,0 +1,21 @@
+CREATE TABLE proposed_sale (
+    proposed_sale_id INTEGER PRIMARY KEY,
+    access_no TEXT NOT NULL,
+    form_type TEXT NOT NULL,
+    form_url TEXT NOT NULL,
+    filed_date TEXT NULL,
+    issuer_cik TEXT NOT NULL,
+    issuer_name TEXT NOT NULL,
+    seller_cik TEXT NOT NULL,
+    seller_name TEXT NOT NULL,
+    relationship TEXT NOT NULL,
+    security_class TEXT NOT NULL,
+    broker TEXT NOT NULL,
+    shares TEXT NULL,
+    market_value TEXT NULL,
+    approx_sale_date TEXT NULL,
+    plan_adoption_date TEXT NULL
+);
+
+CREATE INDEX ix_proposed_sale_access_no ON proposed_sale (access_no);
+CREATE INDEX ix_proposed_sale_seller ON proposed_sale (seller_cik, issuer_cik);
@@ -0,0 +1,23 @@
+CREATE TABLE beneficial_ownership (
+    ownership_id INTEGER PRIMARY KEY,
+    issuer_id INTEGER NOT NULL,
+    access_no TEXT NOT NULL,
+    form_type TEXT NOT NULL,
+    form_url TEXT NOT NULL,
+    filed_date TEXT NULL,
+    event_date TEXT NULL,
+    issuer_cusip TEXT NOT NULL,
+    filer_cik TEXT NOT NULL,
+    filer_name TEXT NOT NULL,
+    amendment_no INTEGER NULL,
+    percent_of_class TEXT NULL,
+    aggregate_shares TEXT NULL,
+    sole_voting TEXT NULL,
+    shared_voting TEXT NULL,
+    sole_dispositive TEXT NULL,
+    shared_dispositive TEXT NULL,
+    CONSTRAINT fk_beneficial_ownership_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_beneficial_ownership_access_no ON beneficial_ownership (access_no);
+CREATE INDEX ix_beneficial_ownership_issuer ON beneficial_ownership (issuer_id, filed_date);
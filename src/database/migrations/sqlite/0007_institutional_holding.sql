@@ -0,0 +1,35 @@
+CREATE TABLE institutional_holding (
+    holding_id INTEGER PRIMARY KEY,
+    issuer_id INTEGER NULL,
+    access_no TEXT NOT NULL,
+    form_type TEXT NOT NULL,
+    amendment_type TEXT NULL,
+    form_url TEXT NOT NULL,
+    filed_date TEXT NULL,
+    period_of_report TEXT NOT NULL,
+    manager_cik TEXT NOT NULL,
+    manager_name TEXT NOT NULL,
+    issuer_name TEXT NOT NULL,
+    title_of_class TEXT NOT NULL,
+    cusip TEXT NOT NULL,
+    market_value TEXT NULL,
+    shares TEXT NULL,
+    share_type TEXT NOT NULL,
+    put_call TEXT NULL,
+    investment_discretion TEXT NOT NULL,
+    voting_sole TEXT NULL,
+    voting_shared TEXT NULL,
+    voting_none TEXT NULL,
+    CONSTRAINT fk_institutional_holding_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_institutional_holding_access_no ON institutional_holding (access_no);
+CREATE INDEX ix_institutional_holding_manager ON institutional_holding (manager_cik, period_of_report);
+CREATE INDEX ix_institutional_holding_cusip ON institutional_holding (cusip);
+
+-- issuers are identified by the first six characters of the cusip of any of their classes
+CREATE TABLE issuer_cusip (
+    cusip6 TEXT NOT NULL PRIMARY KEY,
+    issuer_id INTEGER NOT NULL,
+    CONSTRAINT fk_issuer_cusip_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
@@ -0,0 +1,23 @@
+CREATE TABLE xbrl_fact (
+    fact_id INTEGER PRIMARY KEY,
+    issuer_id INTEGER NOT NULL,
+    access_no TEXT NOT NULL,
+    form_type TEXT NOT NULL,
+    fiscal_year INTEGER NULL,
+    fiscal_period TEXT NOT NULL,
+    taxonomy TEXT NOT NULL,
+    concept TEXT NOT NULL,
+    label TEXT NOT NULL,
+    report TEXT NOT NULL,
+    report_group TEXT NOT NULL,
+    fact_value TEXT NOT NULL,
+    unit TEXT NOT NULL,
+    decimals INTEGER NULL,
+    period_start TEXT NULL,
+    period_end TEXT NOT NULL,
+    dimensions TEXT NOT NULL,
+    CONSTRAINT fk_xbrl_fact_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_xbrl_fact_access_no ON xbrl_fact (access_no);
+CREATE INDEX ix_xbrl_fact_issuer_period ON xbrl_fact (issuer_id, period_end, concept);
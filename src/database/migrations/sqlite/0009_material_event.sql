@@ -0,0 +1,16 @@
+CREATE TABLE material_event (
+    event_id INTEGER PRIMARY KEY,
+    issuer_id INTEGER NOT NULL,
+    access_no TEXT NOT NULL,
+    form_type TEXT NOT NULL,
+    form_url TEXT NOT NULL,
+    filed_date TEXT NULL,
+    event_date TEXT NULL,
+    item TEXT NOT NULL,
+    item_title TEXT NOT NULL,
+    section TEXT NOT NULL,
+    CONSTRAINT fk_material_event_issuer FOREIGN KEY (issuer_id) REFERENCES issuer (issuer_id)
+);
+
+CREATE INDEX ix_material_event_access_no ON material_event (access_no);
+CREATE INDEX ix_material_event_issuer ON material_event (issuer_id, event_date, item);
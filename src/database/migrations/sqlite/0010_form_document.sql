@@ -0,0 +1,10 @@
+CREATE TABLE form_document (
+    form_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    seq INTEGER NULL,
+    doc_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    size INTEGER NULL,
+    CONSTRAINT pk_form_document PRIMARY KEY (form_id, name),
+    CONSTRAINT fk_form_document_form FOREIGN KEY (form_id) REFERENCES form (form_id)
+);
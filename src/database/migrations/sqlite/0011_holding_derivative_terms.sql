@@ -0,0 +1,7 @@
+-- SQLite adds one column per statement.
+
+ALTER TABLE holding ADD COLUMN conversion_price TEXT NULL;
+ALTER TABLE holding ADD COLUMN exercise_date TEXT NULL;
+ALTER TABLE holding ADD COLUMN expiration_date TEXT NULL;
+ALTER TABLE holding ADD COLUMN underlying_title TEXT NOT NULL DEFAULT '';
+ALTER TABLE holding ADD COLUMN underlying_shares TEXT NULL;
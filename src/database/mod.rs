@@ -1,171 +1,501 @@
-use std::collections::HashMap;
 use std::env;
-use std::sync::{Arc, Mutex};
-use once_cell::sync::Lazy;
-use tiberius::{AuthMethod, Client, Config};
-use tokio::net::TcpStream;
-use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::future::Future;
 
-use crate::database::insert_models::{NewForm, NewIndividual, NewIssuer, NewNonDerivTransaction};
-use crate::database::query_models::NonDerivTransaction;
-use crate::secgov::models::FilingTransaction;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::database::query_models::{Checkpoint, CheckpointStatus, DerivTransaction, Footnote, Form, FormDocument, NonDerivTransaction, SaleMatch};
+use crate::secgov::models::{BeneficialOwnership, DerivativeTransaction, Filing, FilingTransaction, Holding, InstitutionalHolding, MaterialEvent, ProposedSale, ReportingOwner, XbrlFact};
 
 pub mod insert_models;
+pub mod migrations;
+pub mod mssql;
+pub mod postgres;
 pub mod query_models;
+pub mod sqlite;
+
+pub use self::mssql::{get_connection, MsSqlStore, SqlHelper};
+pub use self::postgres::PostgresStore;
+pub use self::sqlite::SqliteStore;
+
+/// Connections kept per pooled store, matching the number of filings inserted concurrently.
+pub const POOL_SIZE: u32 = 10;
+
+/// What a call to [`FilingStore::save_filings`] wrote.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SaveStats {
+    /// Filings stored by this call, including ones that were already stored.
+    pub filings: usize,
+    /// Transaction and holding rows written by this call.
+    pub rows: usize,
+    /// Filings left out because they name no reporting owner.
+    pub skipped: usize,
+}
 
-static CONN_STR_PORT: Lazy<String> = Lazy::new(|| {
-    env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or_else(|_| {
-        "server=tcp:localhost\\SQLEXPRESS,1433;database=sec;user id=sa;password=tuputamadre02;".to_owned()
-    })
-});
-
-pub async fn get_connection() -> Result<Client<Compat<TcpStream>>, Box<dyn std::error::Error>> {
-    let mut config = Config::from_ado_string(&CONN_STR_PORT)?;
-    // let mut config = Config::new();
-    // config.authentication(AuthMethod::sql_server("sa", "tuputamadre02"));
-    // config.host("127.0.0.1");
-    // // config.instance_name("SQLEXPRESS");
-    // config.port(1433);
-    // config.database("sec");
+impl SaveStats {
+    /// The checkpoint that closes `date` once these stats are committed.
+    pub fn checkpoint(&self, date: NaiveDate, entries: usize) -> Checkpoint {
+        Checkpoint {
+            mine_date: date,
+            entries: entries as i32,
+            transactions: self.rows as i32,
+            status: CheckpointStatus::COMMITTED,
+        }
+    }
+}
 
-    config.trust_cert();
+/// Documents of a day that are not ownership filings, stored by [`FilingStore::save_day`]
+/// together with its filings.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SideDocuments {
+    pub proposed_sales: Vec<ProposedSale>,
+    pub ownership_events: Vec<BeneficialOwnership>,
+    pub institutional_holdings: Vec<InstitutionalHolding>,
+    pub xbrl_facts: Vec<XbrlFact>,
+    pub material_events: Vec<MaterialEvent>,
+}
 
-    let tcp = TcpStream::connect(config.get_addr()).await?;
-    tcp.set_nodelay(true)?;
+/// Groups `rows` by the document they were read from, in the order the documents first appear,
+/// and keeps the documents `stored` says are not stored yet. A document is stored whole, so
+/// finding any of its rows is enough to skip it. `document` gives the accession number and url
+/// of a row, only the rows of the first url of an accession are kept as the index lists a
+/// submission under every cik it involves and each copy is fetched.
+async fn unstored<'a, T, F, Fut>(rows: &'a [T], document: impl Fn(&'a T) -> (&'a str, &'a str), stored: F) -> Result<Vec<Vec<&'a T>>, Box<dyn Error>>
+where
+    F: Fn(&'a str) -> Fut,
+    Fut: Future<Output = Result<bool, Box<dyn Error>>>,
+{
+    let mut documents = Vec::<(&str, Vec<&T>)>::new();
+    let mut positions = HashMap::<&str, (usize, &str)>::new();
+
+    for row in rows {
+        let (access_no, url) = document(row);
+        match positions.get(access_no) {
+            Some((position, first_url)) if *first_url == url => documents[*position].1.push(row),
+            Some(_) => {}
+            None => {
+                positions.insert(access_no, (documents.len(), url));
+                documents.push((access_no, vec![row]));
+            }
+        }
+    }
 
-    let client = Client::connect(config, tcp.compat_write()).await?;
+    let mut pending = Vec::new();
+    for (access_no, rows) in documents {
+        if !stored(access_no).await? {
+            pending.push(rows);
+        }
+    }
 
-    Ok(client)
+    Ok(pending)
 }
 
-pub struct SqlHelper {
-    issuers_cache: Arc<Mutex<HashMap<String, i32>>>,
-    form_cache: Arc<Mutex<HashMap<String, i64>>>,
-    ind_cache: Arc<Mutex<HashMap<String, i32>>>,
-}
+/// Storage the miner writes filings into. Issuers, individuals and forms are upserted on their
+/// natural keys and a filing whose form is already stored is skipped whole, so storing the
+/// same filing twice writes nothing the second time.
+///
+/// Implementations manage their own connections, pooled or behind a lock, so a store can be
+/// shared by the concurrent inserts of a day.
+#[async_trait(?Send)]
+pub trait FilingStore {
+    /// Brings the schema up to date, returning the migration versions applied by this call.
+    async fn migrate(&self) -> Result<Vec<i32>, Box<dyn Error>>;
+
+    async fn create_issuer(&self, filing: &Filing) -> Result<i32, Box<dyn Error>>;
+
+    async fn create_individual(&self, owner: &ReportingOwner) -> Result<i32, Box<dyn Error>>;
+
+    async fn create_form(&self, filing: &Filing, issuer_id: i32) -> Result<i64, Box<dyn Error>>;
+
+    async fn link_form_owner(&self, owner: &ReportingOwner, form_id: i64, ind_id: i32) -> Result<(), Box<dyn Error>>;
+
+    async fn insert_footnotes(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>>;
+
+    /// Stores the document manifest of a filing, nothing for filings read from the `.txt`
+    /// submission.
+    async fn insert_documents(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>>;
+
+    async fn insert_nonderiv(&self, trans: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn Error>>;
+
+    async fn insert_trans_footnotes(&self, trans: &FilingTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>>;
+
+    async fn insert_deriv(&self, filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<DerivTransaction, Box<dyn Error>>;
 
-impl SqlHelper {
-    pub fn new() -> SqlHelper {
-        SqlHelper {
-            issuers_cache: Arc::new(Mutex::new(HashMap::new())),
-            form_cache: Arc::new(Mutex::new(HashMap::new())),
-            ind_cache: Arc::new(Mutex::new(HashMap::new())),
+    async fn insert_deriv_footnotes(&self, trans: &DerivativeTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>>;
+
+    async fn insert_holding(&self, filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<query_models::Holding, Box<dyn Error>>;
+
+    async fn insert_holding_footnotes(&self, holding: &Holding, holding_id: i64) -> Result<(), Box<dyn Error>>;
+
+    /// Links an amendment to the form it restates and marks that form, any earlier amendment
+    /// of it and their rows as superseded by `form_id`. The original is the form of the same
+    /// issuer, owner and base type filed on `dateOfOriginalSubmission`, or reported on the same
+    /// date when either filing date is unknown. Returns the original's id, `None` when it is
+    /// not stored.
+    async fn link_amendment(&self, filing: &Filing, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<Option<i64>, Box<dyn Error>>;
+
+    /// The form stored under exactly this accession number, superseded or not.
+    async fn get_form_version(&self, access_no: &str) -> Result<Option<Form>, Box<dyn Error>>;
+
+    async fn get_form_by_id(&self, form_id: i64) -> Result<Option<Form>, Box<dyn Error>>;
+
+    /// The effective version of a filing, the latest amendment when it was amended.
+    async fn get_form(&self, access_no: &str) -> Result<Option<Form>, Box<dyn Error>> {
+        match self.get_form_version(access_no).await? {
+            Some(Form { superseded_by: Some(amendment), .. }) => self.get_form_by_id(amendment).await,
+            form => Ok(form),
         }
     }
 
-    pub async fn create_issuer(&mut self, client: &mut Client<Compat<TcpStream>>, filing: &FilingTransaction) -> Result<i32, Box<dyn std::error::Error>> {
-        let new_issuer = NewIssuer::map(&filing);
+    /// Non derivative rows of a form that no amendment superseded.
+    async fn get_nonderiv(&self, form_id: i64) -> Result<Vec<NonDerivTransaction>, Box<dyn Error>>;
+
+    async fn get_footnotes(&self, form_id: i64) -> Result<Vec<Footnote>, Box<dyn Error>>;
+
+    /// Documents of the submission of a form, by sequence with the unnumbered files last.
+    async fn get_documents(&self, form_id: i64) -> Result<Vec<FormDocument>, Box<dyn Error>>;
+
+    /// Holdings reported on the latest effective Form 3 of an owner at an issuer, the balance
+    /// position tracking starts from. Empty when the owner reported no securities or never
+    /// filed a Form 3.
+    async fn get_baseline(&self, issuer_id: i32, ind_id: i32) -> Result<Vec<query_models::Holding>, Box<dyn Error>>;
+
+    async fn insert_proposed_sale(&self, sale: &ProposedSale) -> Result<query_models::ProposedSale, Box<dyn Error>>;
 
-        let mut cache = self.issuers_cache.lock().unwrap();
-        if let Some(&issuer_id) = cache.get(&new_issuer.cik.to_string()) {
-            return Ok(issuer_id);
+    /// Rows stored for a Form 144 notice, one per class of securities.
+    async fn get_proposed_sales(&self, access_no: &str) -> Result<Vec<query_models::ProposedSale>, Box<dyn Error>>;
+
+    /// Form 4 sales (code `S`, disposed) of the seller of a Form 144 notice at the same issuer,
+    /// reported on or after the day the notice was filed, or its approximate sale date when
+    /// the filing date is unknown, and at most 90 days after its approximate sale date, the
+    /// filing date when it has none. Rows superseded by an amendment are left out.
+    async fn get_sale_matches(&self, access_no: &str) -> Result<Vec<SaleMatch>, Box<dyn Error>>;
+
+    /// Stores Form 144 notices, skipping the ones already stored, and returns the rows written.
+    async fn save_proposed_sales(&self, sales: &[ProposedSale]) -> Result<usize, Box<dyn Error>> {
+        let notices = unstored(sales, |s| (&s.access_no, &s.form_url), |access_no| async move {
+            Ok(!self.get_proposed_sales(access_no).await?.is_empty())
+        }).await?;
+
+        let mut rows = 0;
+        for sale in notices.into_iter().flatten() {
+            self.insert_proposed_sale(sale).await?;
+            rows += 1;
         }
 
-        let query = format!("SELECT issuer_id FROM issuer WHERE cik = '{}'", new_issuer.cik);
-        let stream = client.query(query, &[]).await?;
+        Ok(rows)
+    }
+
+    async fn insert_ownership_event(&self, event: &BeneficialOwnership, issuer_id: i32) -> Result<query_models::BeneficialOwnership, Box<dyn Error>>;
 
-        let insert_query = format!(
-            "INSERT INTO issuer (Name, Symbol, cik) VALUES ('{}', '{}', '{}')",
-            new_issuer.issuer_name, new_issuer.issuer_symbol, new_issuer.cik
-        );
+    /// Events stored for a Schedule 13D or 13G, one per reporting person.
+    async fn get_ownership_events(&self, access_no: &str) -> Result<Vec<query_models::BeneficialOwnership>, Box<dyn Error>>;
 
-        if let Some(row) = stream.into_row().await? {
-            let issuer_id: i32 = row.get("issuer_id").unwrap();
-            cache.insert(new_issuer.cik.to_string(), issuer_id);
-            return Ok(issuer_id);
+    /// Stores Schedule 13D and 13G events under their issuer, skipping statements already
+    /// stored, and returns the rows written.
+    async fn save_ownership_events(&self, events: &[BeneficialOwnership]) -> Result<usize, Box<dyn Error>> {
+        let schedules = unstored(events, |e| (&e.access_no, &e.form_url), |access_no| async move {
+            Ok(!self.get_ownership_events(access_no).await?.is_empty())
+        }).await?;
+
+        let mut rows = 0;
+        for event in schedules.into_iter().flatten() {
+            // issuers are upserted on their cik, a schedule carries no trading symbol
+            let issuer = Filing { company_cik: event.issuer_cik.clone(), company: event.issuer_name.clone(), ..Default::default() };
+            let issuer_id = self.create_issuer(&issuer).await?;
+
+            self.insert_ownership_event(event, issuer_id).await?;
+            rows += 1;
         }
 
-        client.execute(insert_query, &[]).await?;
+        Ok(rows)
+    }
+
+    async fn insert_institutional_holding(&self, holding: &InstitutionalHolding) -> Result<query_models::InstitutionalHolding, Box<dyn Error>>;
+
+    /// Rows stored for a 13F-HR, one per line of its information table.
+    async fn get_institutional_holdings(&self, access_no: &str) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>>;
 
-        Err("Failed to create issuer".into())
+    /// Every row a manager reported for a quarter, original and amendments, oldest filing first.
+    async fn get_manager_reports(&self, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>>;
+
+    /// Positions of a manager at the end of a quarter. The latest original report or
+    /// restatement replaces the ones filed before it, `NEW HOLDINGS` amendments filed
+    /// after it add to it.
+    async fn get_manager_holdings(&self, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let mut rows = self.get_manager_reports(manager_cik, period_of_report).await?;
+
+        let base = rows.iter()
+            .rposition(|r| r.amendment_type.as_deref() != Some("NEW HOLDINGS"))
+            .map(|last| rows[last].access_no.clone());
+
+        if let Some(base) = base {
+            let start = rows.iter().position(|r| r.access_no == base).unwrap_or_default();
+            rows.drain(..start);
+        }
+
+        Ok(rows)
     }
 
-    pub async fn create_individual(&mut self, client: &mut Client<Compat<TcpStream>>, filing: &FilingTransaction) -> Result<i32, Box<dyn std::error::Error>> {
-        let new_ind = NewIndividual::map(&filing);
+    /// Stores 13F-HR positions, skipping reports already stored, and returns the rows written.
+    async fn save_institutional_holdings(&self, holdings: &[InstitutionalHolding]) -> Result<usize, Box<dyn Error>> {
+        let reports = unstored(holdings, |h| (&h.access_no, &h.form_url), |access_no| async move {
+            Ok(!self.get_institutional_holdings(access_no).await?.is_empty())
+        }).await?;
 
-        let mut cache = self.ind_cache.lock().unwrap();
-        if let Some(&individual_id) = cache.get(&new_ind.cik.to_string()) {
-            return Ok(individual_id);
+        let mut rows = 0;
+        for holding in reports.into_iter().flatten() {
+            self.insert_institutional_holding(holding).await?;
+            rows += 1;
         }
 
-        let query = format!("SELECT individual_id FROM individual WHERE cik = '{}'", new_ind.cik);
-        let stream = client.query(query, &[]).await?;
+        Ok(rows)
+    }
+
+    /// Links 13F positions without an issuer to the issuer of their cusip, see
+    /// [`RESOLVE_CUSIPS`], and returns how many were linked.
+    async fn resolve_cusips(&self) -> Result<usize, Box<dyn Error>>;
+
+    async fn insert_xbrl_fact(&self, fact: &XbrlFact, issuer_id: i32) -> Result<query_models::XbrlFact, Box<dyn Error>>;
+
+    /// Facts stored for a 10-K or 10-Q.
+    async fn get_report_facts(&self, access_no: &str) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>>;
+
+    /// Facts of an issuer for periods ending on `period_end`, from every report that has them,
+    /// by concept.
+    async fn get_period_facts(&self, issuer_id: i32, period_end: NaiveDate) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>>;
 
-        if let Some(row) = stream.into_row().await? {
-            let individual_id: i32 = row.get("individual_id").unwrap();
-            cache.insert(new_ind.cik.to_string(), individual_id);
-            return Ok(individual_id);
+    /// Stores XBRL facts under their issuer, skipping reports already stored, and returns the
+    /// rows written.
+    async fn save_xbrl_facts(&self, facts: &[XbrlFact]) -> Result<usize, Box<dyn Error>> {
+        let reports = unstored(facts, |f| (&f.access_no, &f.form_url), |access_no| async move {
+            Ok(!self.get_report_facts(access_no).await?.is_empty())
+        }).await?;
+
+        let mut rows = 0;
+        for facts in reports {
+            // every fact of a report belongs to the issuer that filed it
+            let issuer = Filing { company_cik: facts[0].issuer_cik.clone(), company: facts[0].issuer_name.clone(), ..Default::default() };
+            let issuer_id = self.create_issuer(&issuer).await?;
+
+            for fact in facts {
+                self.insert_xbrl_fact(fact, issuer_id).await?;
+                rows += 1;
+            }
         }
 
-        let insert_query = format!(
-            "INSERT INTO individual (FullName, cik, FirstName, LastName) VALUES ('{}', '{}', '{}', '{}')",
-            new_ind.full_name, new_ind.cik, new_ind.first_name.unwrap_or_default(), new_ind.last_name.unwrap_or_default()
-        );
+        Ok(rows)
+    }
+
+    async fn insert_material_event(&self, event: &MaterialEvent, issuer_id: i32) -> Result<query_models::MaterialEvent, Box<dyn Error>>;
+
+    /// Events stored for an 8-K, one per item.
+    async fn get_material_events(&self, access_no: &str) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>>;
 
-        client.execute(insert_query, &[]).await?;
+    /// 8-K events of an issuer that happened between `from` and `to`, both included, by date.
+    /// Events without a date count on the day they were filed.
+    async fn get_issuer_events(&self, issuer_id: i32, from: NaiveDate, to: NaiveDate) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>>;
 
-        Err("Failed to create individual".into())
+    /// Stores 8-K events under their issuer, skipping reports already stored, and returns the
+    /// rows written.
+    async fn save_material_events(&self, events: &[MaterialEvent]) -> Result<usize, Box<dyn Error>> {
+        let reports = unstored(events, |e| (&e.access_no, &e.form_url), |access_no| async move {
+            Ok(!self.get_material_events(access_no).await?.is_empty())
+        }).await?;
+
+        let mut rows = 0;
+        for event in reports.into_iter().flatten() {
+            let issuer = Filing { company_cik: event.issuer_cik.clone(), company: event.issuer_name.clone(), ..Default::default() };
+            let issuer_id = self.create_issuer(&issuer).await?;
+
+            self.insert_material_event(event, issuer_id).await?;
+            rows += 1;
+        }
+
+        Ok(rows)
     }
 
-    pub async fn create_form(&self, client: &mut Client<Compat<TcpStream>>, filing: &FilingTransaction, issuer_id: i32) -> Result<i64, Box<dyn std::error::Error>> {
-        let new_form = NewForm::map(&filing, issuer_id);
+    /// Stores the side documents of a day through the `save_*` methods above, then links the
+    /// 13F positions to issuers by cusip. Returns the rows written.
+    async fn save_side_documents(&self, documents: &SideDocuments) -> Result<usize, Box<dyn Error>> {
+        let sales = self.save_proposed_sales(&documents.proposed_sales).await?;
+        println!("Stored {sales} proposed sales");
+
+        let ownership = self.save_ownership_events(&documents.ownership_events).await?;
+        println!("Stored {ownership} beneficial ownership events");
 
-        let mut cache = self.form_cache.lock().unwrap();
-        if let Some(&form_id) = cache.get(&new_form.access_no) {
-            return Ok(form_id);
+        let holdings = self.save_institutional_holdings(&documents.institutional_holdings).await?;
+        println!("Stored {holdings} institutional holdings");
+
+        let facts = self.save_xbrl_facts(&documents.xbrl_facts).await?;
+        println!("Stored {facts} xbrl facts");
+
+        let events = self.save_material_events(&documents.material_events).await?;
+        println!("Stored {events} material events");
+
+        let linked = self.resolve_cusips().await?;
+        println!("Linked {linked} institutional holdings to their issuer");
+
+        Ok(sales + ownership + holdings + facts + events)
+    }
+
+    /// The checkpoint recorded for `date`, `None` when mining never reached that day.
+    async fn get_checkpoint(&self, date: NaiveDate) -> Result<Option<Checkpoint>, Box<dyn Error>>;
+
+    /// Inserts or replaces the checkpoint of its day.
+    async fn put_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), Box<dyn Error>>;
+
+    /// Stores a day of filings through [`FilingStore::save_filings`] and its side documents
+    /// through [`FilingStore::save_side_documents`], and commits them together with the
+    /// `COMMITTED` checkpoint in one transaction. On error nothing of the day is kept, so it can
+    /// be mined again from scratch.
+    async fn save_day(&self, date: NaiveDate, entries: usize, filings: &[Filing], documents: &SideDocuments) -> Result<SaveStats, Box<dyn Error>>;
+
+    /// Stores a day of filings. The default goes row by row through the methods above,
+    /// running up to [`POOL_SIZE`] filings at once, backends with a bulk path override it.
+    /// Stops at the first filing that fails, so [`FilingStore::save_day`] rolls the day back.
+    async fn save_filings(&self, filings: &[Filing]) -> Result<SaveStats, Box<dyn Error>> {
+        // the daily index lists a filing under every cik it involves, only the first copy is inserted
+        let mut seen = HashSet::new();
+        let (filings, skipped): (Vec<&Filing>, Vec<&Filing>) = filings.iter()
+            .filter(|f| seen.insert(&f.access_no))
+            .partition(|f| !f.owners.is_empty());
+
+        let mut stats = SaveStats { skipped: skipped.len(), ..Default::default() };
+        let total = filings.len();
+
+        let mut saved = stream::iter(filings)
+            .map(|filing| self.save_filing(filing))
+            .buffer_unordered(POOL_SIZE as usize);
+
+        while let Some(rows) = saved.next().await {
+            stats.rows += rows?;
+            stats.filings += 1;
+            println!("insert {}/{}", stats.filings, total);
         }
 
-        let query = format!("SELECT form_id FROM form WHERE AccessNo = '{}'", new_form.access_no);
-        let stream = client.query(query, &[]).await?;
+        Ok(stats)
+    }
 
-        if let Some(row) = stream.into_row().await? {
-            let form_id: i64 = row.get("form_id").unwrap();
-            cache.insert(new_form.access_no.clone(), form_id);
-            return Ok(form_id);
+    /// Stores one filing with its owners, footnotes, documents and rows, returning how many
+    /// transaction and holding rows were written. Rows are attributed to the primary owner,
+    /// every owner is linked to the form.
+    async fn save_filing(&self, filing: &Filing) -> Result<usize, Box<dyn Error>> {
+        if self.get_form_version(&filing.access_no).await?.is_some() {
+            return Ok(0);
         }
 
-        let insert_query = format!(
-            "INSERT INTO form (IssuerId, DateReported, FormType, TxtURL, WebURL, AccessNo) VALUES ({}, '{}', '{}', '{}', '{}', '{}')",
-            new_form.issuer_id, new_form.date_reported, new_form.form_type, new_form.txt_url, new_form.web_url, new_form.access_no
-        );
+        let issuer = self.create_issuer(filing).await?;
 
-        client.execute(insert_query, &[]).await?;
+        let mut owner_ids = Vec::<i32>::new();
+        for owner in &filing.owners {
+            owner_ids.push(self.create_individual(owner).await?);
+        }
+        let ind = *owner_ids.first().ok_or("filing has no reporting owner")?;
 
-        Err("Failed to create form".into())
-    }
+        let form_id = self.create_form(filing, issuer).await?;
 
-    pub async fn insert_nonderiv(&self, client: &mut Client<Compat<TcpStream>>, filing: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn std::error::Error>> {
-        let new_trans = NewNonDerivTransaction::map(filing, form_id, issuer_id, ind_id);
+        for (owner, ind_id) in filing.owners.iter().zip(&owner_ids) {
+            self.link_form_owner(owner, form_id, *ind_id).await?;
+        }
 
-        let query = format!(
-            "SELECT * FROM non_deriv_transaction WHERE FormId = {} AND DateReported = '{}' AND SharesBalance = {}",
-            form_id, filing.trans_date, new_trans.shares_balance
-        );
+        self.insert_footnotes(filing, form_id).await?;
+        self.insert_documents(filing, form_id).await?;
 
-        let stream = client.query(query, &[]).await?;
+        let mut rows = 0;
+        for trans in &filing.transactions {
+            let row = self.insert_nonderiv(trans, form_id, issuer, ind).await?;
+            self.insert_trans_footnotes(trans, row.transaction_id).await?;
+            rows += 1;
+        }
 
-        if let Some(row) = stream.into_row().await? {
-            return Ok(NonDerivTransaction::from_row(&row)?);
+        for trans in &filing.derivatives {
+            let row = self.insert_deriv(filing, trans, form_id, issuer, ind).await?;
+            self.insert_deriv_footnotes(trans, row.transaction_id).await?;
+            rows += 1;
         }
 
-        let insert_query = format!(
-            "INSERT INTO non_deriv_transaction (DateReported, FormId, IssuerId, IndividualId, ActionCode, OwnershipCode, TransactionCode, SharesBalance, SharesTraded, AvgPrice, Amount, Relationships) VALUES ('{}', {}, {}, {}, '{}', '{}', '{}', {}, {}, {}, {}, ARRAY[{}])",
-            new_trans.date_reported, new_trans.form_id, new_trans.issuer_id, new_trans.individual_id, new_trans.action_code.unwrap_or_default(), new_trans.ownership_code.unwrap_or_default(), new_trans.transaction_code.unwrap_or_default(), new_trans.shares_balance, new_trans.shares_traded, new_trans.avg_price, new_trans.amount, new_trans.relationships.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(",")
-        );
+        for holding in &filing.holdings {
+            let row = self.insert_holding(filing, holding, form_id, issuer, ind).await?;
+            self.insert_holding_footnotes(holding, row.holding_id).await?;
+            rows += 1;
+        }
 
-        client.execute(insert_query, &[]).await?;
+        if filing.is_amendment() {
+            self.link_amendment(filing, form_id, issuer, ind).await?;
+        }
 
-        let query = format!(
-            "SELECT * FROM non_deriv_transaction WHERE FormId = {} AND DateReported = '{}' AND SharesBalance = {}",
-            form_id, filing.trans_date, new_trans.shares_balance
-        );
+        Ok(rows)
+    }
+}
 
-        let stream = client.query(query, &[]).await?;
+/// Which backend to store filings in.
+///
+/// Read from `SECFILINGS_STORE` (`mssql`, `postgres` or `sqlite`, SQL Server when unset) and
+/// `SECFILINGS_DATABASE_URL`, which holds the connection string or, for SQLite, the file path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreConfig {
+    MsSql { connection: String },
+    Postgres { connection: String },
+    Sqlite { path: String },
+}
 
-        if let Some(row) = stream.into_row().await? {
-            return Ok(NonDerivTransaction::from_row(&row)?);
+impl StoreConfig {
+    pub fn from_env() -> Result<StoreConfig, Box<dyn Error>> {
+        let url = env::var("SECFILINGS_DATABASE_URL").ok();
+        let backend = env::var("SECFILINGS_STORE").unwrap_or_else(|_| "mssql".to_string());
+
+        match backend.to_lowercase().as_str() {
+            "mssql" | "sqlserver" => Ok(StoreConfig::MsSql {
+                connection: url.unwrap_or_else(|| mssql::CONN_STR_PORT.to_string()),
+            }),
+            "postgres" | "postgresql" => Ok(StoreConfig::Postgres {
+                connection: url.unwrap_or_else(|| "host=localhost user=postgres dbname=sec".to_string()),
+            }),
+            "sqlite" => Ok(StoreConfig::Sqlite {
+                path: url.unwrap_or_else(|| "sec.db".to_string()),
+            }),
+            other => Err(format!("Unknown store '{other}', expected mssql, postgres or sqlite").into()),
         }
+    }
 
-        Err("Failed to insert non-derivative transaction".into())
+    pub async fn open(&self) -> Result<Box<dyn FilingStore>, Box<dyn Error>> {
+        Ok(match self {
+            StoreConfig::MsSql { connection } => Box::new(MsSqlStore::connect(connection).await?),
+            StoreConfig::Postgres { connection } => Box::new(PostgresStore::connect(connection).await?),
+            StoreConfig::Sqlite { path } => Box::new(SqliteStore::open(path)?),
+        })
     }
-}
\ No newline at end of file
+}
+
+/// Statements that map cusips to `issuer` rows and link the unresolved 13F positions, run in
+/// order. The issuer part of a cusip, its first six characters, is learned from the Schedule
+/// 13D and 13G statements filed under the issuer cik, then from 13F issuer names that match a
+/// single issuer name once case, dots and commas are ignored. A prefix claimed by more than
+/// one issuer is left unresolved. The last statement is the one that links positions.
+pub(crate) const RESOLVE_CUSIPS: [&str; 3] = [
+    "INSERT INTO issuer_cusip (cusip6, issuer_id) \
+     SELECT SUBSTRING(b.issuer_cusip, 1, 6), MIN(b.issuer_id) FROM beneficial_ownership b \
+     WHERE b.issuer_cusip <> '' \
+       AND NOT EXISTS (SELECT 1 FROM issuer_cusip c WHERE c.cusip6 = SUBSTRING(b.issuer_cusip, 1, 6)) \
+     GROUP BY SUBSTRING(b.issuer_cusip, 1, 6) HAVING COUNT(DISTINCT b.issuer_id) = 1",
+    "INSERT INTO issuer_cusip (cusip6, issuer_id) \
+     SELECT SUBSTRING(h.cusip, 1, 6), MIN(i.issuer_id) FROM institutional_holding h \
+     JOIN issuer i ON REPLACE(REPLACE(UPPER(i.name), '.', ''), ',', '') = REPLACE(REPLACE(UPPER(h.issuer_name), '.', ''), ',', '') \
+     WHERE h.issuer_id IS NULL AND h.cusip <> '' \
+       AND NOT EXISTS (SELECT 1 FROM issuer_cusip c WHERE c.cusip6 = SUBSTRING(h.cusip, 1, 6)) \
+     GROUP BY SUBSTRING(h.cusip, 1, 6) HAVING COUNT(DISTINCT i.issuer_id) = 1",
+    "UPDATE institutional_holding \
+     SET issuer_id = (SELECT c.issuer_id FROM issuer_cusip c WHERE c.cusip6 = SUBSTRING(institutional_holding.cusip, 1, 6)) \
+     WHERE issuer_id IS NULL \
+       AND EXISTS (SELECT 1 FROM issuer_cusip c WHERE c.cusip6 = SUBSTRING(institutional_holding.cusip, 1, 6))",
+];
+
+/// Joins enum discriminants into the comma separated form stored in `relationships` and `roles`.
+fn join_ids(ids: &[i32]) -> String {
+    ids.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(",")
+}
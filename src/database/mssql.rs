@@ -0,0 +1,1403 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::error::Error;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use bb8::{ManageConnection, Pool, PooledConnection};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use tiberius::{AuthMethod, Client, Config, Row, ToSql};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+use crate::database::insert_models::{NewDerivTransaction, NewForm, NewFormOwner, NewHolding, NewIndividual, NewIssuer, NewNonDerivTransaction};
+use crate::database::migrations::{self, Migration};
+use crate::database::query_models::{self, Checkpoint, DerivTransaction, Footnote, Form, FormDocument, NonDerivTransaction, SaleMatch};
+use crate::database::{join_ids, FilingStore, SaveStats, SideDocuments, POOL_SIZE, RESOLVE_CUSIPS};
+use crate::secgov::models::{BeneficialOwnership, DerivativeTransaction, Filing, FilingTransaction, Holding, InstitutionalHolding, MaterialEvent, ProposedSale, ReportingOwner, XbrlFact};
+
+pub(crate) static CONN_STR_PORT: Lazy<String> = Lazy::new(|| {
+    env::var("TIBERIUS_TEST_CONNECTION_STRING").unwrap_or_else(|_| {
+        "server=tcp:localhost\\SQLEXPRESS,1433;database=sec;user id=sa;password=tuputamadre02;".to_owned()
+    })
+});
+
+pub async fn get_connection() -> Result<Client<Compat<TcpStream>>, Box<dyn std::error::Error>> {
+    connect(&CONN_STR_PORT).await
+}
+
+/// Opens a connection from an ADO.NET style connection string.
+pub async fn connect(conn_str: &str) -> Result<Client<Compat<TcpStream>>, Box<dyn std::error::Error>> {
+    Ok(open(config(conn_str)?).await?)
+}
+
+fn config(conn_str: &str) -> Result<Config, tiberius::error::Error> {
+    let mut config = Config::from_ado_string(conn_str)?;
+    // let mut config = Config::new();
+    // config.authentication(AuthMethod::sql_server("sa", "tuputamadre02"));
+    // config.host("127.0.0.1");
+    // // config.instance_name("SQLEXPRESS");
+    // config.port(1433);
+    // config.database("sec");
+
+    config.trust_cert();
+
+    Ok(config)
+}
+
+async fn open(config: Config) -> Result<Client<Compat<TcpStream>>, tiberius::error::Error> {
+    let tcp = TcpStream::connect(config.get_addr()).await?;
+    tcp.set_nodelay(true)?;
+
+    Client::connect(config, tcp.compat_write()).await
+}
+
+/// Hands out tiberius connections to a [`bb8::Pool`].
+pub struct TiberiusManager {
+    config: Config,
+}
+
+#[async_trait]
+impl ManageConnection for TiberiusManager {
+    type Connection = Client<Compat<TcpStream>>;
+    type Error = tiberius::error::Error;
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        open(self.config.clone()).await
+    }
+
+    async fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        conn.simple_query("SELECT 1").await?.into_row().await?;
+        Ok(())
+    }
+
+    fn has_broken(&self, _: &mut Self::Connection) -> bool {
+        false
+    }
+}
+
+/// Most parameters SQL Server takes in one request is 2100, bulk statements stay below it.
+const MAX_PARAMS: usize = 2000;
+
+/// Runs `template` once per chunk of `rows`, with `{values}` replaced by that chunk's
+/// `(@P1, ...), (...)` list, and returns every row the statements output.
+async fn run_chunked(client: &mut Client<Compat<TcpStream>>, template: &str, rows: &[Vec<&dyn ToSql>]) -> Result<Vec<Row>, Box<dyn Error>> {
+    let mut output = Vec::new();
+    let Some(width) = rows.first().map(|row| row.len()) else {
+        return Ok(output);
+    };
+
+    for chunk in rows.chunks((MAX_PARAMS / width).max(1)) {
+        let values = (0..chunk.len())
+            .map(|r| {
+                let params = (1..=width).map(|c| format!("@P{}", r * width + c)).collect::<Vec<_>>();
+                format!("({})", params.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let params: Vec<&dyn ToSql> = chunk.iter().flatten().copied().collect();
+        let result = client.query(template.replace("{values}", &values), &params).await?.into_first_result().await?;
+        output.extend(result);
+    }
+
+    Ok(output)
+}
+
+/// Matches the `source` issuers of a `MERGE issuer` on their cik, filling the name and symbol
+/// of stored issuers that lack them, and outputs their cik, id, name and symbol.
+const MERGE_ISSUER: &str = "ON target.cik = source.cik \
+    WHEN MATCHED THEN UPDATE SET target.name = COALESCE(NULLIF(target.name, ''), source.name), \
+        target.symbol = COALESCE(NULLIF(target.symbol, ''), source.symbol) \
+    WHEN NOT MATCHED THEN INSERT (name, symbol, cik) VALUES (source.name, source.symbol, source.cik) \
+    OUTPUT source.cik, INSERTED.issuer_id, INSERTED.name, INSERTED.symbol;";
+
+/// Ids output by a `MERGE` bulk insert, keyed by the ordinal of the source row they belong to.
+fn ordinal_ids(output: &[Row]) -> HashMap<i32, i64> {
+    output.iter()
+        .map(|row| (row.get::<i32, _>(0).unwrap(), row.get::<i64, _>(1).unwrap()))
+        .collect()
+}
+
+/// SQL Server queries behind [`MsSqlStore`]. The key caches are shared by concurrent inserts,
+/// so they are only locked between awaits, never across one.
+#[derive(Default)]
+pub struct SqlHelper {
+    issuers_cache: Arc<Mutex<HashMap<String, i32>>>,
+    form_cache: Arc<Mutex<HashMap<String, i64>>>,
+    ind_cache: Arc<Mutex<HashMap<String, i32>>>,
+}
+
+impl SqlHelper {
+    pub fn new() -> SqlHelper {
+        SqlHelper::default()
+    }
+
+    /// Upserts an issuer on its cik, filling the name and symbol it was stored without. Only
+    /// issuers with both are cached, the others are merged again until a filing brings them.
+    pub async fn create_issuer(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing) -> Result<i32, Box<dyn std::error::Error>> {
+        let new_issuer = NewIssuer::map(filing);
+
+        if let Some(&issuer_id) = self.issuers_cache.lock().unwrap().get(&new_issuer.cik.to_string()) {
+            return Ok(issuer_id);
+        }
+
+        let (statement, params) = Self::issuer_statement(&new_issuer);
+        let stream = client.query(statement, &params).await?;
+
+        match stream.into_row().await? {
+            Some(row) => Ok(self.cache_issuer(&row)),
+            None => Err("Failed to create issuer".into()),
+        }
+    }
+
+    /// Upsert of an issuer, its name and symbol are bound as parameters and never formatted
+    /// into the statement.
+    fn issuer_statement<'a>(new_issuer: &'a NewIssuer) -> (String, [&'a dyn ToSql; 3]) {
+        (
+            format!("MERGE issuer WITH (HOLDLOCK) AS target USING (VALUES (@P1, @P2, @P3)) AS source (name, symbol, cik) {MERGE_ISSUER}"),
+            [&new_issuer.issuer_name, &new_issuer.issuer_symbol, &new_issuer.cik],
+        )
+    }
+
+    /// Insert of an individual, its names are bound as parameters.
+    fn individual_statement<'a>(new_ind: &'a NewIndividual) -> (&'static str, [&'a dyn ToSql; 4]) {
+        (
+            "INSERT INTO individual (full_name, cik, first_name, last_name) OUTPUT INSERTED.individual_id VALUES (@P1, @P2, @P3, @P4)",
+            [&new_ind.full_name, &new_ind.cik, &new_ind.first_name, &new_ind.last_name],
+        )
+    }
+
+    /// Caches the issuer output by [`MERGE_ISSUER`] once it has a name and symbol, returning its id.
+    fn cache_issuer(&self, row: &Row) -> i32 {
+        let (cik, issuer_id) = (row.get::<&str, _>(0).unwrap(), row.get::<i32, _>(1).unwrap());
+        let complete = [row.get::<&str, _>(2), row.get::<&str, _>(3)].iter().all(|v| !v.unwrap_or_default().is_empty());
+
+        if complete {
+            self.issuers_cache.lock().unwrap().insert(cik.to_string(), issuer_id);
+        }
+
+        issuer_id
+    }
+
+    pub async fn create_individual(&self, client: &mut Client<Compat<TcpStream>>, owner: &ReportingOwner) -> Result<i32, Box<dyn std::error::Error>> {
+        let new_ind = NewIndividual::map(owner);
+
+        if let Some(&individual_id) = self.ind_cache.lock().unwrap().get(&new_ind.cik.to_string()) {
+            return Ok(individual_id);
+        }
+
+        let stream = client.query("SELECT individual_id FROM individual WHERE cik = @P1", &[&new_ind.cik]).await?;
+
+        if let Some(row) = stream.into_row().await? {
+            let individual_id: i32 = row.get("individual_id").unwrap();
+            self.ind_cache.lock().unwrap().insert(new_ind.cik.to_string(), individual_id);
+            return Ok(individual_id);
+        }
+
+        let (statement, params) = Self::individual_statement(&new_ind);
+        let stream = client.query(statement, &params).await?;
+
+        if let Some(row) = stream.into_row().await? {
+            let individual_id: i32 = row.get("individual_id").unwrap();
+            self.ind_cache.lock().unwrap().insert(new_ind.cik.to_string(), individual_id);
+            return Ok(individual_id);
+        }
+
+        Err("Failed to create individual".into())
+    }
+
+    pub async fn create_form(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing, issuer_id: i32) -> Result<i64, Box<dyn std::error::Error>> {
+        let new_form = NewForm::map(filing, issuer_id);
+
+        if let Some(&form_id) = self.form_cache.lock().unwrap().get(&new_form.access_no) {
+            return Ok(form_id);
+        }
+
+        let stream = client.query("SELECT form_id FROM form WHERE access_no = @P1", &[&new_form.access_no]).await?;
+
+        if let Some(row) = stream.into_row().await? {
+            let form_id: i64 = row.get("form_id").unwrap();
+            self.form_cache.lock().unwrap().insert(new_form.access_no.clone(), form_id);
+            return Ok(form_id);
+        }
+
+        let stream = client.query(
+            "INSERT INTO form (issuer_id, date_reported, form_type, txt_url, web_url, access_no, filed_date, original_submission) \
+             OUTPUT INSERTED.form_id VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8)",
+            &[&new_form.issuer_id, &new_form.date_reported, &new_form.form_type, &new_form.txt_url, &new_form.web_url, &new_form.access_no, &new_form.filed_date, &new_form.original_submission],
+        ).await?;
+
+        if let Some(row) = stream.into_row().await? {
+            let form_id: i64 = row.get("form_id").unwrap();
+            self.form_cache.lock().unwrap().insert(new_form.access_no.clone(), form_id);
+            return Ok(form_id);
+        }
+
+        Err("Failed to create form".into())
+    }
+
+    /// Links a form to one of its reporting owners, keeping that owner's relationship and address.
+    pub async fn link_form_owner(&self, client: &mut Client<Compat<TcpStream>>, owner: &ReportingOwner, form_id: i64, ind_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+        let new_link = NewFormOwner::map(owner, form_id, ind_id);
+        let relationships = join_ids(&new_link.relationships);
+        let roles = join_ids(&new_link.roles);
+
+        client.execute(
+            "IF NOT EXISTS (SELECT 1 FROM form_owner WHERE form_id = @P1 AND individual_id = @P2) \
+             INSERT INTO form_owner (form_id, individual_id, relationships, officer_title, other_text, roles, street1, street2, city, state, zip_code) \
+             VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11)",
+            &[&new_link.form_id, &new_link.individual_id, &relationships, &new_link.officer_title, &new_link.other_text, &roles, &new_link.street1, &new_link.street2, &new_link.city, &new_link.state, &new_link.zip_code],
+        ).await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_nonderiv(&self, client: &mut Client<Compat<TcpStream>>, filing: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn std::error::Error>> {
+        let new_trans = NewNonDerivTransaction::map(filing, form_id, issuer_id, ind_id);
+        let relationships = join_ids(&new_trans.relationships);
+        let roles = join_ids(&new_trans.roles);
+
+        let stream = client.query(
+            "INSERT INTO non_deriv_transaction (date_reported, form_id, issuer_id, individual_id, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships, officer_title, other_text, roles) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14, @P15)",
+            &[&new_trans.date_reported, &new_trans.form_id, &new_trans.issuer_id, &new_trans.individual_id, &new_trans.action_code, &new_trans.ownership_code, &new_trans.transaction_code, &new_trans.shares_balance, &new_trans.shares_traded, &new_trans.avg_price, &new_trans.amount, &relationships, &new_trans.officer_title, &new_trans.other_text, &roles],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => Ok(NonDerivTransaction::from_row(&row)?),
+            None => Err("Failed to insert non-derivative transaction".into()),
+        }
+    }
+
+    pub async fn insert_deriv(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<DerivTransaction, Box<dyn std::error::Error>> {
+        let new_trans = NewDerivTransaction::map(filing, trans, form_id, issuer_id, ind_id);
+        let relationships = join_ids(&new_trans.relationships);
+
+        let stream = client.query(
+            "INSERT INTO deriv_transaction (date_reported, form_id, issuer_id, individual_id, security_title, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14, @P15, @P16, @P17, @P18)",
+            &[&new_trans.date_reported, &new_trans.form_id, &new_trans.issuer_id, &new_trans.individual_id, &new_trans.security_title, &new_trans.conversion_price, &new_trans.exercise_date, &new_trans.expiration_date, &new_trans.underlying_title, &new_trans.underlying_shares, &new_trans.action_code, &new_trans.ownership_code, &new_trans.transaction_code, &new_trans.shares_balance, &new_trans.shares_traded, &new_trans.avg_price, &new_trans.amount, &relationships],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => DerivTransaction::from_row(&row),
+            None => Err("Failed to insert derivative transaction".into()),
+        }
+    }
+
+    pub async fn insert_holding(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<query_models::Holding, Box<dyn std::error::Error>> {
+        let new_holding = NewHolding::map(filing, holding, form_id, issuer_id, ind_id);
+
+        let stream = client.query(
+            "INSERT INTO holding (date_reported, form_id, issuer_id, individual_id, security_title, shares_owned, ownership_code, ownership_nature, derivative, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14)",
+            &[&new_holding.date_reported, &new_holding.form_id, &new_holding.issuer_id, &new_holding.individual_id, &new_holding.security_title, &new_holding.shares_owned, &new_holding.ownership_code, &new_holding.ownership_nature, &new_holding.derivative,
+                &new_holding.conversion_price, &new_holding.exercise_date, &new_holding.expiration_date, &new_holding.underlying_title, &new_holding.underlying_shares],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => query_models::Holding::from_row(&row),
+            None => Err("Failed to insert holding".into()),
+        }
+    }
+
+    /// Stores the footnote text of a form. Footnotes are free text, so they are bound as parameters.
+    pub async fn insert_footnotes(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing, form_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        for (key, text) in &filing.footnotes {
+            client.execute(
+                "IF NOT EXISTS (SELECT 1 FROM footnote WHERE form_id = @P1 AND footnote_key = @P2) \
+                 INSERT INTO footnote (form_id, footnote_key, text) VALUES (@P1, @P2, @P3)",
+                &[&form_id, key, text],
+            ).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn insert_documents(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing, form_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        for doc in &filing.documents {
+            client.execute(
+                "IF NOT EXISTS (SELECT 1 FROM form_document WHERE form_id = @P1 AND name = @P2) \
+                 INSERT INTO form_document (form_id, name, seq, doc_type, description, size) VALUES (@P1, @P2, @P3, @P4, @P5, @P6)",
+                &[&form_id, &doc.name, &doc.seq, &doc.doc_type, &doc.description, &doc.size],
+            ).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Links a non-derivative transaction to the footnotes annotating each of its fields.
+    pub async fn insert_trans_footnotes(&self, client: &mut Client<Compat<TcpStream>>, trans: &FilingTransaction, transaction_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        Self::insert_field_footnotes(client, "transaction_footnote", "transaction_id", transaction_id, &trans.footnotes).await
+    }
+
+    pub async fn insert_deriv_footnotes(&self, client: &mut Client<Compat<TcpStream>>, trans: &DerivativeTransaction, transaction_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        Self::insert_field_footnotes(client, "deriv_transaction_footnote", "transaction_id", transaction_id, &trans.footnotes).await
+    }
+
+    pub async fn insert_holding_footnotes(&self, client: &mut Client<Compat<TcpStream>>, holding: &Holding, holding_id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        Self::insert_field_footnotes(client, "holding_footnote", "holding_id", holding_id, &holding.footnotes).await
+    }
+
+    /// Links a row to the footnotes of its fields, `table` being the footnote table of its kind
+    /// and `id_column` the key of the row in it.
+    async fn insert_field_footnotes(client: &mut Client<Compat<TcpStream>>, table: &str, id_column: &str, id: i64, footnotes: &HashMap<String, Vec<String>>) -> Result<(), Box<dyn std::error::Error>> {
+        let query = format!(
+            "IF NOT EXISTS (SELECT 1 FROM {table} WHERE {id_column} = @P1 AND field = @P2 AND footnote_key = @P3) \
+             INSERT INTO {table} ({id_column}, field, footnote_key) VALUES (@P1, @P2, @P3)"
+        );
+
+        for (field, keys) in footnotes {
+            for key in keys {
+                client.execute(query.as_str(), &[&id, field, key]).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_footnotes(&self, client: &mut Client<Compat<TcpStream>>, form_id: i64) -> Result<Vec<Footnote>, Box<dyn std::error::Error>> {
+        let rows = client
+            .query("SELECT form_id, footnote_key, text FROM footnote WHERE form_id = @P1", &[&form_id])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(Footnote::from_row).collect()
+    }
+
+    pub async fn get_documents(&self, client: &mut Client<Compat<TcpStream>>, form_id: i64) -> Result<Vec<FormDocument>, Box<dyn std::error::Error>> {
+        let rows = client
+            .query("SELECT form_id, name, seq, doc_type, description, size FROM form_document WHERE form_id = @P1 ORDER BY CASE WHEN seq IS NULL THEN 1 ELSE 0 END, seq, name", &[&form_id])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(FormDocument::from_row).collect()
+    }
+
+    pub async fn get_form_version(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Option<Form>, Box<dyn std::error::Error>> {
+        let stream = client.query("SELECT * FROM form WHERE access_no = @P1", &[&access_no]).await?;
+
+        match stream.into_row().await? {
+            Some(row) => Ok(Some(Form::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_form_by_id(&self, client: &mut Client<Compat<TcpStream>>, form_id: i64) -> Result<Option<Form>, Box<dyn std::error::Error>> {
+        let stream = client.query("SELECT * FROM form WHERE form_id = @P1", &[&form_id]).await?;
+
+        match stream.into_row().await? {
+            Some(row) => Ok(Some(Form::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn link_amendment(&self, client: &mut Client<Compat<TcpStream>>, filing: &Filing, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<Option<i64>, Box<dyn Error>> {
+        let stream = client.query(
+            "SELECT TOP 1 form_id FROM form WHERE issuer_id = @P1 AND form_type = @P2 AND form_id <> @P3 AND amends_form_id IS NULL \
+             AND EXISTS (SELECT 1 FROM form_owner o WHERE o.form_id = form.form_id AND o.individual_id = @P4) \
+             AND (filed_date = @P5 OR ((filed_date IS NULL OR @P5 IS NULL) AND date_reported = @P6)) \
+             ORDER BY form_id DESC",
+            &[&issuer_id, &filing.base_form_type(), &form_id, &ind_id, &filing.original_submission, &filing.form_date],
+        ).await?;
+
+        let Some(original) = stream.into_row().await?.and_then(|row| row.get::<i64, _>(0)) else {
+            return Ok(None);
+        };
+
+        client.execute(
+            "UPDATE form SET amends_form_id = @P1 WHERE form_id = @P2; \
+             UPDATE form SET superseded_by = @P2 WHERE (form_id = @P1 OR amends_form_id = @P1) AND form_id <> @P2; \
+             UPDATE non_deriv_transaction SET superseded_by = @P2 WHERE form_id IN (SELECT form_id FROM form WHERE superseded_by = @P2); \
+             UPDATE deriv_transaction SET superseded_by = @P2 WHERE form_id IN (SELECT form_id FROM form WHERE superseded_by = @P2); \
+             UPDATE holding SET superseded_by = @P2 WHERE form_id IN (SELECT form_id FROM form WHERE superseded_by = @P2);",
+            &[&original, &form_id],
+        ).await?;
+
+        Ok(Some(original))
+    }
+
+    pub async fn get_nonderiv(&self, client: &mut Client<Compat<TcpStream>>, form_id: i64) -> Result<Vec<NonDerivTransaction>, Box<dyn std::error::Error>> {
+        let rows = client
+            .query("SELECT * FROM non_deriv_transaction WHERE form_id = @P1 AND superseded_by IS NULL ORDER BY transaction_id", &[&form_id])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(NonDerivTransaction::from_row).collect()
+    }
+
+    pub async fn get_baseline(&self, client: &mut Client<Compat<TcpStream>>, issuer_id: i32, ind_id: i32) -> Result<Vec<query_models::Holding>, Box<dyn Error>> {
+        let rows = client
+            .query(
+                "SELECT * FROM holding WHERE superseded_by IS NULL AND form_id = ( \
+                     SELECT MAX(f.form_id) FROM form f JOIN form_owner o ON o.form_id = f.form_id \
+                     WHERE f.issuer_id = @P1 AND o.individual_id = @P2 AND f.form_type IN ('3', '3/A') AND f.superseded_by IS NULL) \
+                 ORDER BY holding_id",
+                &[&issuer_id, &ind_id],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::Holding::from_row).collect()
+    }
+
+    pub async fn insert_proposed_sale(&self, client: &mut Client<Compat<TcpStream>>, sale: &ProposedSale) -> Result<query_models::ProposedSale, Box<dyn Error>> {
+        let stream = client.query(
+            "INSERT INTO proposed_sale (access_no, form_type, form_url, filed_date, issuer_cik, issuer_name, seller_cik, seller_name, relationship, \
+             security_class, broker, shares, market_value, approx_sale_date, plan_adoption_date) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14, @P15)",
+            &[&sale.access_no, &sale.form_type, &sale.form_url, &sale.filed_date, &sale.issuer_cik, &sale.issuer_name, &sale.seller_cik, &sale.seller_name,
+                &sale.relationship, &sale.security_class, &sale.broker, &sale.shares, &sale.market_value, &sale.approx_sale_date, &sale.plan_adoption_date],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => query_models::ProposedSale::from_row(&row),
+            None => Err("Failed to insert proposed sale".into()),
+        }
+    }
+
+    pub async fn get_proposed_sales(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Vec<query_models::ProposedSale>, Box<dyn Error>> {
+        let rows = client
+            .query("SELECT * FROM proposed_sale WHERE access_no = @P1 ORDER BY proposed_sale_id", &[&access_no])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::ProposedSale::from_row).collect()
+    }
+
+    pub async fn get_sale_matches(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Vec<SaleMatch>, Box<dyn Error>> {
+        let rows = client
+            .query(
+                "SELECT p.proposed_sale_id, t.transaction_id, t.form_id, t.date_reported, t.shares_traded, t.avg_price \
+                 FROM proposed_sale p \
+                 JOIN issuer s ON s.cik = p.issuer_cik \
+                 JOIN individual i ON i.cik = p.seller_cik \
+                 JOIN form_owner o ON o.individual_id = i.individual_id \
+                 JOIN non_deriv_transaction t ON t.form_id = o.form_id AND t.issuer_id = s.issuer_id \
+                 WHERE p.access_no = @P1 AND t.transaction_code = 'S' AND t.action_code = 'D' AND t.superseded_by IS NULL \
+                 AND t.date_reported >= COALESCE(p.filed_date, p.approx_sale_date) \
+                 AND t.date_reported <= DATEADD(DAY, 90, COALESCE(p.approx_sale_date, p.filed_date)) \
+                 ORDER BY p.proposed_sale_id, t.date_reported, t.transaction_id",
+                &[&access_no],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(SaleMatch::from_row).collect()
+    }
+
+    pub async fn insert_ownership_event(&self, client: &mut Client<Compat<TcpStream>>, event: &BeneficialOwnership, issuer_id: i32) -> Result<query_models::BeneficialOwnership, Box<dyn Error>> {
+        let stream = client.query(
+            "INSERT INTO beneficial_ownership (issuer_id, access_no, form_type, form_url, filed_date, event_date, issuer_cusip, filer_cik, filer_name, \
+             amendment_no, percent_of_class, aggregate_shares, sole_voting, shared_voting, sole_dispositive, shared_dispositive) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14, @P15, @P16)",
+            &[&issuer_id, &event.access_no, &event.form_type, &event.form_url, &event.filed_date, &event.event_date, &event.issuer_cusip, &event.filer_cik,
+                &event.filer_name, &event.amendment_no, &event.percent_of_class, &event.aggregate_shares, &event.sole_voting,
+                &event.shared_voting, &event.sole_dispositive, &event.shared_dispositive],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => query_models::BeneficialOwnership::from_row(&row),
+            None => Err("Failed to insert beneficial ownership".into()),
+        }
+    }
+
+    pub async fn get_ownership_events(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Vec<query_models::BeneficialOwnership>, Box<dyn Error>> {
+        let rows = client
+            .query("SELECT * FROM beneficial_ownership WHERE access_no = @P1 ORDER BY ownership_id", &[&access_no])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::BeneficialOwnership::from_row).collect()
+    }
+
+    pub async fn insert_institutional_holding(&self, client: &mut Client<Compat<TcpStream>>, holding: &InstitutionalHolding) -> Result<query_models::InstitutionalHolding, Box<dyn Error>> {
+        let stream = client.query(
+            "INSERT INTO institutional_holding (access_no, form_type, amendment_type, form_url, filed_date, period_of_report, manager_cik, manager_name, issuer_name, title_of_class, cusip, \
+             market_value, shares, share_type, put_call, investment_discretion, voting_sole, voting_shared, voting_none) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14, @P15, @P16, @P17, @P18, @P19)",
+            &[&holding.access_no, &holding.form_type, &holding.amendment_type, &holding.form_url, &holding.filed_date, &holding.period_of_report,
+                &holding.manager_cik, &holding.manager_name, &holding.issuer_name, &holding.title_of_class, &holding.cusip, &holding.market_value,
+                &holding.shares, &holding.share_type, &holding.put_call, &holding.investment_discretion, &holding.voting_sole, &holding.voting_shared,
+                &holding.voting_none],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => query_models::InstitutionalHolding::from_row(&row),
+            None => Err("Failed to insert institutional holding".into()),
+        }
+    }
+
+    pub async fn get_institutional_holdings(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let rows = client
+            .query("SELECT * FROM institutional_holding WHERE access_no = @P1 ORDER BY holding_id", &[&access_no])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::InstitutionalHolding::from_row).collect()
+    }
+
+    pub async fn get_manager_reports(&self, client: &mut Client<Compat<TcpStream>>, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let rows = client
+            .query(
+                "SELECT * FROM institutional_holding WHERE manager_cik = @P1 AND period_of_report = @P2 ORDER BY filed_date, holding_id",
+                &[&manager_cik, &period_of_report],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::InstitutionalHolding::from_row).collect()
+    }
+
+    pub async fn resolve_cusips(&self, client: &mut Client<Compat<TcpStream>>) -> Result<usize, Box<dyn Error>> {
+        let mut linked = 0;
+        for statement in RESOLVE_CUSIPS {
+            linked = client.execute(statement, &[]).await?.total();
+        }
+
+        Ok(linked as usize)
+    }
+
+    pub async fn insert_xbrl_fact(&self, client: &mut Client<Compat<TcpStream>>, fact: &XbrlFact, issuer_id: i32) -> Result<query_models::XbrlFact, Box<dyn Error>> {
+        let stream = client.query(
+            "INSERT INTO xbrl_fact (issuer_id, access_no, form_type, fiscal_year, fiscal_period, taxonomy, concept, label, report, report_group, fact_value, unit, decimals, \
+             period_start, period_end, dimensions) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9, @P10, @P11, @P12, @P13, @P14, @P15, @P16)",
+            &[&issuer_id, &fact.access_no, &fact.form_type, &fact.fiscal_year, &fact.fiscal_period, &fact.taxonomy, &fact.concept, &fact.label,
+                &fact.report, &fact.report_group, &fact.value, &fact.unit, &fact.decimals, &fact.period_start, &fact.period_end, &fact.dimensions],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => query_models::XbrlFact::from_row(&row),
+            None => Err("Failed to insert xbrl fact".into()),
+        }
+    }
+
+    pub async fn get_report_facts(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let rows = client
+            .query("SELECT * FROM xbrl_fact WHERE access_no = @P1 ORDER BY fact_id", &[&access_no])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::XbrlFact::from_row).collect()
+    }
+
+    pub async fn get_period_facts(&self, client: &mut Client<Compat<TcpStream>>, issuer_id: i32, period_end: NaiveDate) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let rows = client
+            .query(
+                "SELECT * FROM xbrl_fact WHERE issuer_id = @P1 AND period_end = @P2 ORDER BY concept, dimensions, fact_id",
+                &[&issuer_id, &period_end],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::XbrlFact::from_row).collect()
+    }
+
+    pub async fn insert_material_event(&self, client: &mut Client<Compat<TcpStream>>, event: &MaterialEvent, issuer_id: i32) -> Result<query_models::MaterialEvent, Box<dyn Error>> {
+        let stream = client.query(
+            "INSERT INTO material_event (issuer_id, access_no, form_type, form_url, filed_date, event_date, item, item_title, section) \
+             OUTPUT INSERTED.* VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7, @P8, @P9)",
+            &[&issuer_id, &event.access_no, &event.form_type, &event.form_url, &event.filed_date, &event.event_date, &event.item, &event.item_title, &event.section],
+        ).await?;
+
+        match stream.into_row().await? {
+            Some(row) => query_models::MaterialEvent::from_row(&row),
+            None => Err("Failed to insert material event".into()),
+        }
+    }
+
+    pub async fn get_material_events(&self, client: &mut Client<Compat<TcpStream>>, access_no: &str) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let rows = client
+            .query("SELECT * FROM material_event WHERE access_no = @P1 ORDER BY event_id", &[&access_no])
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::MaterialEvent::from_row).collect()
+    }
+
+    pub async fn get_issuer_events(&self, client: &mut Client<Compat<TcpStream>>, issuer_id: i32, from: NaiveDate, to: NaiveDate) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let rows = client
+            .query(
+                "SELECT * FROM material_event WHERE issuer_id = @P1 AND COALESCE(event_date, filed_date) BETWEEN @P2 AND @P3 \
+                 ORDER BY COALESCE(event_date, filed_date), event_id",
+                &[&issuer_id, &from, &to],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        rows.iter().map(query_models::MaterialEvent::from_row).collect()
+    }
+
+    pub async fn get_checkpoint(&self, client: &mut Client<Compat<TcpStream>>, date: NaiveDate) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+        let stream = client.query("SELECT * FROM mining_checkpoint WHERE mine_date = @P1", &[&date]).await?;
+
+        match stream.into_row().await? {
+            Some(row) => Ok(Some(Checkpoint::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn put_checkpoint(&self, client: &mut Client<Compat<TcpStream>>, checkpoint: &Checkpoint) -> Result<(), Box<dyn Error>> {
+        client.execute(
+            "MERGE mining_checkpoint WITH (HOLDLOCK) AS target \
+             USING (VALUES (@P1, @P2, @P3, @P4)) AS source (mine_date, entries, transactions, status) \
+             ON target.mine_date = source.mine_date \
+             WHEN MATCHED THEN UPDATE SET entries = source.entries, transactions = source.transactions, \
+                 status = source.status, updated_at = SYSUTCDATETIME() \
+             WHEN NOT MATCHED THEN INSERT (mine_date, entries, transactions, status) \
+                 VALUES (source.mine_date, source.entries, source.transactions, source.status);",
+            &[&checkpoint.mine_date, &checkpoint.entries, &checkpoint.transactions, &checkpoint.status.as_str()],
+        ).await?;
+
+        Ok(())
+    }
+
+    /// Forgets every cached key, after a rollback they may point at rows that no longer exist.
+    pub fn clear_caches(&self) {
+        self.issuers_cache.lock().unwrap().clear();
+        self.form_cache.lock().unwrap().clear();
+        self.ind_cache.lock().unwrap().clear();
+    }
+
+    /// Stores a day of filings with multi-row statements instead of one round trip per row.
+    ///
+    /// Issuers, individuals and forms are merged on their natural keys first, then the owners,
+    /// footnotes, transactions and holdings of the forms that were not stored yet are inserted
+    /// in chunks. Forms that already existed are left as they are.
+    pub async fn save_filings(&self, client: &mut Client<Compat<TcpStream>>, filings: &[Filing]) -> Result<SaveStats, Box<dyn Error>> {
+        // only the first copy of a filing listed under several ciks counts, as in FilingStore::save_filings
+        let mut seen = HashSet::new();
+        let (filings, skipped): (Vec<&Filing>, Vec<&Filing>) = filings.iter()
+            .filter(|f| seen.insert(&f.access_no))
+            .partition(|f| !f.owners.is_empty());
+
+        let issuers = self.merge_issuers(client, &filings).await?;
+        let individuals = self.merge_individuals(client, &filings).await?;
+        let new_forms = self.merge_forms(client, &filings, &issuers).await?;
+
+        let mut forms = HashMap::new();
+        {
+            let cache = self.form_cache.lock().unwrap();
+            for filing in &filings {
+                forms.insert(filing.access_no.as_str(), cache[&filing.access_no]);
+            }
+        }
+
+        let new: Vec<&Filing> = filings.iter()
+            .filter(|f| new_forms.contains(&f.access_no))
+            .copied()
+            .collect();
+        // rows carry the primary owner, every owner of a joint filing is reached through form_owner
+        let ids = |filing: &Filing| (forms[filing.access_no.as_str()], issuers[&filing.company_cik], individuals[&filing.owners[0].cik]);
+
+        self.bulk_form_owners(client, &new, &forms, &individuals).await?;
+        self.bulk_footnotes(client, &new, &forms).await?;
+        self.bulk_documents(client, &new, &forms).await?;
+        let mut rows = self.bulk_nonderiv(client, &new, &ids).await?;
+        rows += self.bulk_deriv(client, &new, &ids).await?;
+        rows += self.bulk_holdings(client, &new, &ids).await?;
+
+        for filing in new.iter().filter(|f| f.is_amendment()) {
+            let (form_id, issuer_id, ind_id) = ids(filing);
+            self.link_amendment(client, filing, form_id, issuer_id, ind_id).await?;
+        }
+
+        Ok(SaveStats { filings: filings.len(), rows, skipped: skipped.len() })
+    }
+
+    async fn merge_issuers(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing]) -> Result<HashMap<String, i32>, Box<dyn Error>> {
+        let mut ids = HashMap::new();
+        let mut pending = Vec::new();
+        {
+            let cache = self.issuers_cache.lock().unwrap();
+            for filing in filings {
+                let new_issuer = NewIssuer::map(filing);
+                match cache.get(new_issuer.cik) {
+                    Some(&id) => { ids.insert(new_issuer.cik.to_string(), id); }
+                    None if !pending.iter().any(|p: &NewIssuer| p.cik == new_issuer.cik) => pending.push(new_issuer),
+                    None => {}
+                }
+            }
+        }
+
+        let rows: Vec<Vec<&dyn ToSql>> = pending.iter()
+            .map(|i| vec![&i.issuer_name as &dyn ToSql, &i.issuer_symbol, &i.cik])
+            .collect();
+
+        let output = run_chunked(
+            client,
+            &format!("MERGE issuer WITH (HOLDLOCK) AS target USING (VALUES {{values}}) AS source (name, symbol, cik) {MERGE_ISSUER}"),
+            &rows,
+        ).await?;
+
+        for row in output {
+            let issuer_id = self.cache_issuer(&row);
+            ids.insert(row.get::<&str, _>(0).unwrap().to_string(), issuer_id);
+        }
+
+        Ok(ids)
+    }
+
+    async fn merge_individuals(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing]) -> Result<HashMap<String, i32>, Box<dyn Error>> {
+        let mut ids = HashMap::new();
+        let mut pending = Vec::new();
+        {
+            let cache = self.ind_cache.lock().unwrap();
+            for owner in filings.iter().flat_map(|f| &f.owners) {
+                let new_ind = NewIndividual::map(owner);
+                match cache.get(new_ind.cik) {
+                    Some(&id) => { ids.insert(new_ind.cik.to_string(), id); }
+                    None if !pending.iter().any(|p: &NewIndividual| p.cik == new_ind.cik) => pending.push(new_ind),
+                    None => {}
+                }
+            }
+        }
+
+        let rows: Vec<Vec<&dyn ToSql>> = pending.iter()
+            .map(|i| vec![&i.full_name as &dyn ToSql, &i.cik, &i.first_name, &i.last_name])
+            .collect();
+
+        let output = run_chunked(
+            client,
+            "MERGE individual WITH (HOLDLOCK) AS target USING (VALUES {values}) AS source (full_name, cik, first_name, last_name) \
+             ON target.cik = source.cik \
+             WHEN MATCHED THEN UPDATE SET target.cik = source.cik \
+             WHEN NOT MATCHED THEN INSERT (full_name, cik, first_name, last_name) VALUES (source.full_name, source.cik, source.first_name, source.last_name) \
+             OUTPUT source.cik, INSERTED.individual_id;",
+            &rows,
+        ).await?;
+
+        let mut cache = self.ind_cache.lock().unwrap();
+        for row in output {
+            let (cik, id) = (row.get::<&str, _>(0).unwrap().to_string(), row.get::<i32, _>(1).unwrap());
+            cache.insert(cik.clone(), id);
+            ids.insert(cik, id);
+        }
+
+        Ok(ids)
+    }
+
+    /// Merges the forms into the form cache and returns the accession numbers that were new.
+    async fn merge_forms(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], issuers: &HashMap<String, i32>) -> Result<HashSet<String>, Box<dyn Error>> {
+        let mut pending = Vec::new();
+        {
+            let cache = self.form_cache.lock().unwrap();
+            for filing in filings {
+                if !cache.contains_key(&filing.access_no) && !pending.iter().any(|p: &NewForm| p.access_no == filing.access_no) {
+                    pending.push(NewForm::map(filing, issuers[&filing.company_cik]));
+                }
+            }
+        }
+
+        let rows: Vec<Vec<&dyn ToSql>> = pending.iter()
+            .map(|f| vec![&f.issuer_id as &dyn ToSql, &f.date_reported, &f.form_type, &f.txt_url, &f.web_url, &f.access_no, &f.filed_date, &f.original_submission])
+            .collect();
+
+        let output = run_chunked(
+            client,
+            "MERGE form WITH (HOLDLOCK) AS target \
+             USING (VALUES {values}) AS source (issuer_id, date_reported, form_type, txt_url, web_url, access_no, filed_date, original_submission) \
+             ON target.access_no = source.access_no \
+             WHEN MATCHED THEN UPDATE SET target.access_no = source.access_no \
+             WHEN NOT MATCHED THEN INSERT (issuer_id, date_reported, form_type, txt_url, web_url, access_no, filed_date, original_submission) \
+                 VALUES (source.issuer_id, source.date_reported, source.form_type, source.txt_url, source.web_url, source.access_no, source.filed_date, source.original_submission) \
+             OUTPUT $action, source.access_no, INSERTED.form_id;",
+            &rows,
+        ).await?;
+
+        let mut new_forms = HashSet::new();
+        let mut cache = self.form_cache.lock().unwrap();
+        for row in output {
+            let access_no = row.get::<&str, _>(1).unwrap().to_string();
+            if row.get::<&str, _>(0) == Some("INSERT") {
+                new_forms.insert(access_no.clone());
+            }
+            cache.insert(access_no, row.get::<i64, _>(2).unwrap());
+        }
+
+        Ok(new_forms)
+    }
+
+    async fn bulk_form_owners(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], forms: &HashMap<&str, i64>, individuals: &HashMap<String, i32>) -> Result<(), Box<dyn Error>> {
+        let mut seen = HashSet::new();
+        let links: Vec<_> = filings.iter()
+            .flat_map(|f| f.owners.iter().map(|o| NewFormOwner::map(o, forms[f.access_no.as_str()], individuals[&o.cik])))
+            .filter(|l| seen.insert((l.form_id, l.individual_id)))
+            .map(|l| { let (relationships, roles) = (join_ids(&l.relationships), join_ids(&l.roles)); (l, relationships, roles) })
+            .collect();
+
+        let rows: Vec<Vec<&dyn ToSql>> = links.iter()
+            .map(|(l, relationships, roles)| vec![&l.form_id as &dyn ToSql, &l.individual_id, relationships, &l.officer_title, &l.other_text, roles, &l.street1, &l.street2, &l.city, &l.state, &l.zip_code])
+            .collect();
+
+        run_chunked(
+            client,
+            "INSERT INTO form_owner (form_id, individual_id, relationships, officer_title, other_text, roles, street1, street2, city, state, zip_code) VALUES {values}",
+            &rows,
+        ).await?;
+
+        Ok(())
+    }
+
+    async fn bulk_footnotes(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], forms: &HashMap<&str, i64>) -> Result<(), Box<dyn Error>> {
+        let rows: Vec<Vec<&dyn ToSql>> = filings.iter()
+            .flat_map(|f| f.footnotes.iter().map(|(key, text)| vec![&forms[f.access_no.as_str()] as &dyn ToSql, key, text]))
+            .collect();
+
+        run_chunked(client, "INSERT INTO footnote (form_id, footnote_key, text) VALUES {values}", &rows).await?;
+
+        Ok(())
+    }
+
+    async fn bulk_documents(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], forms: &HashMap<&str, i64>) -> Result<(), Box<dyn Error>> {
+        let rows: Vec<Vec<&dyn ToSql>> = filings.iter()
+            .flat_map(|f| f.documents.iter().map(|doc| {
+                vec![&forms[f.access_no.as_str()] as &dyn ToSql, &doc.name, &doc.seq, &doc.doc_type, &doc.description, &doc.size]
+            }))
+            .collect();
+
+        run_chunked(client, "INSERT INTO form_document (form_id, name, seq, doc_type, description, size) VALUES {values}", &rows).await?;
+
+        Ok(())
+    }
+
+    /// Inserts the non-derivative transactions and their footnote links. The transaction ids come
+    /// back through a `MERGE` on an always false condition, which unlike `INSERT` can output the
+    /// ordinal of the source row each id belongs to.
+    async fn bulk_nonderiv(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], ids: &impl Fn(&Filing) -> (i64, i32, i32)) -> Result<usize, Box<dyn Error>> {
+        let mut trans = Vec::new();
+        for filing in filings {
+            let (form_id, issuer_id, ind_id) = ids(filing);
+            for t in &filing.transactions {
+                let new_trans = NewNonDerivTransaction::map(t, form_id, issuer_id, ind_id);
+                let (relationships, roles) = (join_ids(&new_trans.relationships), join_ids(&new_trans.roles));
+                trans.push((trans.len() as i32, new_trans, relationships, roles, t));
+            }
+        }
+
+        let rows: Vec<Vec<&dyn ToSql>> = trans.iter()
+            .map(|(ord, t, relationships, roles, _)| vec![ord as &dyn ToSql, &t.date_reported, &t.form_id, &t.issuer_id, &t.individual_id, &t.action_code, &t.ownership_code, &t.transaction_code, &t.shares_balance, &t.shares_traded, &t.avg_price, &t.amount, relationships, &t.officer_title, &t.other_text, roles])
+            .collect();
+
+        let output = run_chunked(
+            client,
+            "MERGE non_deriv_transaction AS target \
+             USING (VALUES {values}) AS source (ord, date_reported, form_id, issuer_id, individual_id, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships, officer_title, other_text, roles) \
+             ON 1 = 0 \
+             WHEN NOT MATCHED THEN INSERT (date_reported, form_id, issuer_id, individual_id, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships, officer_title, other_text, roles) \
+                 VALUES (source.date_reported, source.form_id, source.issuer_id, source.individual_id, source.action_code, source.ownership_code, source.transaction_code, source.shares_balance, source.shares_traded, source.avg_price, source.amount, source.relationships, source.officer_title, source.other_text, source.roles) \
+             OUTPUT source.ord, INSERTED.transaction_id;",
+            &rows,
+        ).await?;
+
+        let transaction_ids = ordinal_ids(&output);
+        let footnotes: Vec<_> = trans.iter().map(|(ord, _, _, _, t)| (transaction_ids[ord], &t.footnotes)).collect();
+        Self::bulk_field_footnotes(client, "transaction_footnote (transaction_id, field, footnote_key)", &footnotes).await?;
+
+        Ok(trans.len())
+    }
+
+    /// Inserts the footnote links of rows inserted in bulk, given the id of each row and the
+    /// footnotes of its fields. `target` is the footnote table with its column list.
+    async fn bulk_field_footnotes(client: &mut Client<Compat<TcpStream>>, target: &str, footnotes: &[(i64, &HashMap<String, Vec<String>>)]) -> Result<(), Box<dyn Error>> {
+        let links: Vec<(i64, &String, &String)> = footnotes.iter()
+            .flat_map(|(id, fields)| fields.iter().flat_map(move |(field, keys)| keys.iter().map(move |key| (*id, field, key))))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let rows: Vec<Vec<&dyn ToSql>> = links.iter()
+            .map(|(id, field, key)| vec![id as &dyn ToSql, *field, *key])
+            .collect();
+
+        run_chunked(client, &format!("INSERT INTO {target} VALUES {{values}}"), &rows).await?;
+
+        Ok(())
+    }
+
+    async fn bulk_deriv(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], ids: &impl Fn(&Filing) -> (i64, i32, i32)) -> Result<usize, Box<dyn Error>> {
+        let trans: Vec<_> = filings.iter()
+            .flat_map(|f| {
+                let (form_id, issuer_id, ind_id) = ids(f);
+                f.derivatives.iter().map(move |t| (NewDerivTransaction::map(f, t, form_id, issuer_id, ind_id), t))
+            })
+            .enumerate()
+            .map(|(ord, (new_trans, t))| { let relationships = join_ids(&new_trans.relationships); (ord as i32, new_trans, relationships, t) })
+            .collect();
+
+        let rows: Vec<Vec<&dyn ToSql>> = trans.iter()
+            .map(|(ord, t, relationships, _)| vec![ord as &dyn ToSql, &t.date_reported, &t.form_id, &t.issuer_id, &t.individual_id, &t.security_title, &t.conversion_price, &t.exercise_date, &t.expiration_date, &t.underlying_title, &t.underlying_shares, &t.action_code, &t.ownership_code, &t.transaction_code, &t.shares_balance, &t.shares_traded, &t.avg_price, &t.amount, relationships])
+            .collect();
+
+        let output = run_chunked(
+            client,
+            "MERGE deriv_transaction AS target \
+             USING (VALUES {values}) AS source (ord, date_reported, form_id, issuer_id, individual_id, security_title, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships) \
+             ON 1 = 0 \
+             WHEN NOT MATCHED THEN INSERT (date_reported, form_id, issuer_id, individual_id, security_title, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships) \
+                 VALUES (source.date_reported, source.form_id, source.issuer_id, source.individual_id, source.security_title, source.conversion_price, source.exercise_date, source.expiration_date, source.underlying_title, source.underlying_shares, source.action_code, source.ownership_code, source.transaction_code, source.shares_balance, source.shares_traded, source.avg_price, source.amount, source.relationships) \
+             OUTPUT source.ord, INSERTED.transaction_id;",
+            &rows,
+        ).await?;
+
+        let transaction_ids = ordinal_ids(&output);
+        let footnotes: Vec<_> = trans.iter().map(|(ord, _, _, t)| (transaction_ids[ord], &t.footnotes)).collect();
+        Self::bulk_field_footnotes(client, "deriv_transaction_footnote (transaction_id, field, footnote_key)", &footnotes).await?;
+
+        Ok(trans.len())
+    }
+
+    async fn bulk_holdings(&self, client: &mut Client<Compat<TcpStream>>, filings: &[&Filing], ids: &impl Fn(&Filing) -> (i64, i32, i32)) -> Result<usize, Box<dyn Error>> {
+        let holdings: Vec<_> = filings.iter()
+            .flat_map(|f| {
+                let (form_id, issuer_id, ind_id) = ids(f);
+                f.holdings.iter().map(move |h| (NewHolding::map(f, h, form_id, issuer_id, ind_id), h))
+            })
+            .enumerate()
+            .map(|(ord, (new_holding, h))| (ord as i32, new_holding, h))
+            .collect();
+
+        let rows: Vec<Vec<&dyn ToSql>> = holdings.iter()
+            .map(|(ord, h, _)| vec![ord as &dyn ToSql, &h.date_reported, &h.form_id, &h.issuer_id, &h.individual_id, &h.security_title, &h.shares_owned, &h.ownership_code, &h.ownership_nature, &h.derivative,
+                &h.conversion_price, &h.exercise_date, &h.expiration_date, &h.underlying_title, &h.underlying_shares])
+            .collect();
+
+        let output = run_chunked(
+            client,
+            "MERGE holding AS target \
+             USING (VALUES {values}) AS source (ord, date_reported, form_id, issuer_id, individual_id, security_title, shares_owned, ownership_code, ownership_nature, derivative, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares) \
+             ON 1 = 0 \
+             WHEN NOT MATCHED THEN INSERT (date_reported, form_id, issuer_id, individual_id, security_title, shares_owned, ownership_code, ownership_nature, derivative, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares) \
+                 VALUES (source.date_reported, source.form_id, source.issuer_id, source.individual_id, source.security_title, source.shares_owned, source.ownership_code, source.ownership_nature, source.derivative, source.conversion_price, source.exercise_date, source.expiration_date, source.underlying_title, source.underlying_shares) \
+             OUTPUT source.ord, INSERTED.holding_id;",
+            &rows,
+        ).await?;
+
+        let holding_ids = ordinal_ids(&output);
+        let footnotes: Vec<_> = holdings.iter().map(|(ord, _, h)| (holding_ids[ord], &h.footnotes)).collect();
+        Self::bulk_field_footnotes(client, "holding_footnote (holding_id, field, footnote_key)", &footnotes).await?;
+
+        Ok(holdings.len())
+    }
+}
+
+/// Connection a [`MsSqlStore`] call runs on, the one of the day being saved or one from the pool.
+enum StoreClient<'a> {
+    Pooled(Box<PooledConnection<'a, TiberiusManager>>),
+    Day(tokio::sync::MappedMutexGuard<'a, PooledConnection<'static, TiberiusManager>>),
+}
+
+impl Deref for StoreClient<'_> {
+    type Target = Client<Compat<TcpStream>>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            StoreClient::Pooled(client) => client,
+            StoreClient::Day(client) => client,
+        }
+    }
+}
+
+impl DerefMut for StoreClient<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            StoreClient::Pooled(client) => client,
+            StoreClient::Day(client) => client,
+        }
+    }
+}
+
+/// [`FilingStore`] backed by SQL Server, the [`SqlHelper`] queries over a pool of connections.
+pub struct MsSqlStore {
+    pool: Pool<TiberiusManager>,
+    helper: SqlHelper,
+    /// Connection holding the transaction of [`FilingStore::save_day`], every call runs on it
+    /// while the day is being saved.
+    day: tokio::sync::Mutex<Option<PooledConnection<'static, TiberiusManager>>>,
+}
+
+impl MsSqlStore {
+    pub async fn connect(conn_str: &str) -> Result<MsSqlStore, Box<dyn Error>> {
+        let manager = TiberiusManager { config: config(conn_str)? };
+
+        Ok(MsSqlStore {
+            pool: Pool::builder().max_size(POOL_SIZE).build(manager).await?,
+            helper: SqlHelper::new(),
+            day: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// The connection of the day being saved, or one from the pool outside of
+    /// [`FilingStore::save_day`].
+    async fn client(&self) -> Result<StoreClient<'_>, Box<dyn Error>> {
+        match tokio::sync::MutexGuard::try_map(self.day.lock().await, Option::as_mut) {
+            Ok(day) => Ok(StoreClient::Day(day)),
+            Err(_) => Ok(StoreClient::Pooled(Box::new(self.pool.get().await?))),
+        }
+    }
+
+    async fn apply(client: &mut Client<Compat<TcpStream>>, migration: &Migration) -> Result<(), Box<dyn Error>> {
+        client.simple_query(migration.sql).await?.into_results().await?;
+
+        client.execute(
+            "INSERT INTO schema_migrations (version, name) VALUES (@P1, @P2)",
+            &[&migration.version, &migration.name],
+        ).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl FilingStore for MsSqlStore {
+    /// Each migration runs in its own transaction together with its `schema_migrations` row,
+    /// so a failing script leaves the database at the previous version.
+    async fn migrate(&self) -> Result<Vec<i32>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+
+        client.simple_query(
+            "IF OBJECT_ID('schema_migrations', 'U') IS NULL \
+             CREATE TABLE schema_migrations (\
+                 version INT NOT NULL CONSTRAINT pk_schema_migrations PRIMARY KEY, \
+                 name NVARCHAR(128) NOT NULL, \
+                 applied_at DATETIME2 NOT NULL CONSTRAINT df_schema_migrations_applied_at DEFAULT SYSUTCDATETIME()\
+             )",
+        ).await?.into_results().await?;
+
+        let applied: Vec<i32> = client
+            .query("SELECT version FROM schema_migrations", &[])
+            .await?
+            .into_first_result()
+            .await?
+            .iter()
+            .filter_map(|row| row.get::<i32, _>("version"))
+            .collect();
+
+        let mut newly_applied = Vec::new();
+        for migration in migrations::pending(migrations::MSSQL, &applied) {
+            client.simple_query("BEGIN TRANSACTION").await?.into_results().await?;
+
+            if let Err(e) = Self::apply(&mut client, migration).await {
+                client.simple_query("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION").await?.into_results().await?;
+                return Err(format!("Migration {} ({}) failed: {}", migration.version, migration.name, e).into());
+            }
+
+            client.simple_query("COMMIT TRANSACTION").await?.into_results().await?;
+            println!("Applied migration {} ({})", migration.version, migration.name);
+            newly_applied.push(migration.version);
+        }
+
+        Ok(newly_applied)
+    }
+
+    async fn create_issuer(&self, filing: &Filing) -> Result<i32, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.create_issuer(&mut client, filing).await
+    }
+
+    async fn create_individual(&self, owner: &ReportingOwner) -> Result<i32, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.create_individual(&mut client, owner).await
+    }
+
+    async fn create_form(&self, filing: &Filing, issuer_id: i32) -> Result<i64, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.create_form(&mut client, filing, issuer_id).await
+    }
+
+    async fn link_form_owner(&self, owner: &ReportingOwner, form_id: i64, ind_id: i32) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.link_form_owner(&mut client, owner, form_id, ind_id).await
+    }
+
+    async fn insert_footnotes(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_footnotes(&mut client, filing, form_id).await
+    }
+
+    async fn insert_documents(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_documents(&mut client, filing, form_id).await
+    }
+
+    async fn insert_nonderiv(&self, trans: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_nonderiv(&mut client, trans, form_id, issuer_id, ind_id).await
+    }
+
+    async fn insert_trans_footnotes(&self, trans: &FilingTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_trans_footnotes(&mut client, trans, transaction_id).await
+    }
+
+    async fn insert_deriv(&self, filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<DerivTransaction, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_deriv(&mut client, filing, trans, form_id, issuer_id, ind_id).await
+    }
+
+    async fn insert_deriv_footnotes(&self, trans: &DerivativeTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_deriv_footnotes(&mut client, trans, transaction_id).await
+    }
+
+    async fn insert_holding(&self, filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<query_models::Holding, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_holding(&mut client, filing, holding, form_id, issuer_id, ind_id).await
+    }
+
+    async fn insert_holding_footnotes(&self, holding: &Holding, holding_id: i64) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_holding_footnotes(&mut client, holding, holding_id).await
+    }
+
+    async fn link_amendment(&self, filing: &Filing, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<Option<i64>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.link_amendment(&mut client, filing, form_id, issuer_id, ind_id).await
+    }
+
+    async fn get_form_version(&self, access_no: &str) -> Result<Option<Form>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_form_version(&mut client, access_no).await
+    }
+
+    async fn get_form_by_id(&self, form_id: i64) -> Result<Option<Form>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_form_by_id(&mut client, form_id).await
+    }
+
+    async fn get_nonderiv(&self, form_id: i64) -> Result<Vec<NonDerivTransaction>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_nonderiv(&mut client, form_id).await
+    }
+
+    async fn get_footnotes(&self, form_id: i64) -> Result<Vec<Footnote>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_footnotes(&mut client, form_id).await
+    }
+
+    async fn get_documents(&self, form_id: i64) -> Result<Vec<FormDocument>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_documents(&mut client, form_id).await
+    }
+
+    async fn get_baseline(&self, issuer_id: i32, ind_id: i32) -> Result<Vec<query_models::Holding>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_baseline(&mut client, issuer_id, ind_id).await
+    }
+
+    async fn insert_proposed_sale(&self, sale: &ProposedSale) -> Result<query_models::ProposedSale, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_proposed_sale(&mut client, sale).await
+    }
+
+    async fn get_proposed_sales(&self, access_no: &str) -> Result<Vec<query_models::ProposedSale>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_proposed_sales(&mut client, access_no).await
+    }
+
+    async fn get_sale_matches(&self, access_no: &str) -> Result<Vec<SaleMatch>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_sale_matches(&mut client, access_no).await
+    }
+
+    async fn insert_ownership_event(&self, event: &BeneficialOwnership, issuer_id: i32) -> Result<query_models::BeneficialOwnership, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_ownership_event(&mut client, event, issuer_id).await
+    }
+
+    async fn get_ownership_events(&self, access_no: &str) -> Result<Vec<query_models::BeneficialOwnership>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_ownership_events(&mut client, access_no).await
+    }
+
+    async fn insert_institutional_holding(&self, holding: &InstitutionalHolding) -> Result<query_models::InstitutionalHolding, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_institutional_holding(&mut client, holding).await
+    }
+
+    async fn get_institutional_holdings(&self, access_no: &str) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_institutional_holdings(&mut client, access_no).await
+    }
+
+    async fn get_manager_reports(&self, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_manager_reports(&mut client, manager_cik, period_of_report).await
+    }
+
+    async fn resolve_cusips(&self) -> Result<usize, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.resolve_cusips(&mut client).await
+    }
+
+    async fn insert_xbrl_fact(&self, fact: &XbrlFact, issuer_id: i32) -> Result<query_models::XbrlFact, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_xbrl_fact(&mut client, fact, issuer_id).await
+    }
+
+    async fn get_report_facts(&self, access_no: &str) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_report_facts(&mut client, access_no).await
+    }
+
+    async fn get_period_facts(&self, issuer_id: i32, period_end: NaiveDate) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_period_facts(&mut client, issuer_id, period_end).await
+    }
+
+    async fn insert_material_event(&self, event: &MaterialEvent, issuer_id: i32) -> Result<query_models::MaterialEvent, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.insert_material_event(&mut client, event, issuer_id).await
+    }
+
+    async fn get_material_events(&self, access_no: &str) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_material_events(&mut client, access_no).await
+    }
+
+    async fn get_issuer_events(&self, issuer_id: i32, from: NaiveDate, to: NaiveDate) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_issuer_events(&mut client, issuer_id, from, to).await
+    }
+
+    async fn get_checkpoint(&self, date: NaiveDate) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.get_checkpoint(&mut client, date).await
+    }
+
+    async fn put_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.put_checkpoint(&mut client, checkpoint).await
+    }
+
+    async fn save_filings(&self, filings: &[Filing]) -> Result<SaveStats, Box<dyn Error>> {
+        let mut client = self.client().await?;
+        self.helper.save_filings(&mut client, filings).await
+    }
+
+    /// Pins a pooled connection for the day, so the bulk path, the side documents and the
+    /// checkpoint all run on it inside one transaction.
+    async fn save_day(&self, date: NaiveDate, entries: usize, filings: &[Filing], documents: &SideDocuments) -> Result<SaveStats, Box<dyn Error>> {
+        let mut day = self.pool.get_owned().await?;
+        day.simple_query("BEGIN TRANSACTION").await?.into_results().await?;
+        *self.day.lock().await = Some(day);
+
+        let saved = async {
+            let stats = self.save_filings(filings).await?;
+            self.save_side_documents(documents).await?;
+            self.put_checkpoint(&stats.checkpoint(date, entries)).await?;
+            Ok::<_, Box<dyn Error>>(stats)
+        }.await;
+
+        let mut client = self.day.lock().await.take().ok_or("day connection was released")?;
+        match saved {
+            Ok(stats) => {
+                client.simple_query("COMMIT TRANSACTION").await?.into_results().await?;
+                Ok(stats)
+            }
+            Err(e) => {
+                self.helper.clear_caches();
+                client.simple_query("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION").await?.into_results().await?;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiberius::ColumnData;
+
+    fn quoted_filing() -> Filing {
+        Filing {
+            access_no: "0000000000-25-000001".to_string(),
+            company: "MCDONALD'S CORP".to_string(),
+            symbol: "MCD".to_string(),
+            company_cik: "0000063908".to_string(),
+            owners: vec![ReportingOwner {
+                cik: "0009999999".to_string(),
+                name: "O'BRIEN ELLEN H".to_string(),
+                officer_title: "EVP, Chief People's Officer".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn quoted_names_are_mapped_verbatim() {
+        let filing = quoted_filing();
+
+        let issuer = NewIssuer::map(&filing);
+        assert_eq!(issuer.issuer_name, "MCDONALD'S CORP");
+
+        let individual = NewIndividual::map(&filing.owners[0]);
+        assert_eq!(individual.full_name, "O'BRIEN ELLEN H");
+        assert_eq!(individual.last_name.as_deref(), Some("O'BRIEN"));
+        assert_eq!(individual.first_name.as_deref(), Some("ELLEN H"));
+
+        let link = NewFormOwner::map(&filing.owners[0], 1, 1);
+        assert_eq!(link.officer_title, "EVP, Chief People's Officer");
+    }
+
+    #[test]
+    fn quoted_names_are_bound_as_parameters() {
+        let filing = quoted_filing();
+        let text = |param: &&dyn ToSql| match param.to_sql() {
+            ColumnData::String(value) => value.map(|v| v.into_owned()),
+            other => panic!("not a string parameter: {other:?}"),
+        };
+
+        let issuer = NewIssuer::map(&filing);
+        let (statement, params) = SqlHelper::issuer_statement(&issuer);
+        assert!(!statement.contains("MCDONALD"));
+        assert!(statement.contains("VALUES (@P1, @P2, @P3)"));
+        assert_eq!(text(&params[0]).as_deref(), Some("MCDONALD'S CORP"));
+
+        let individual = NewIndividual::map(&filing.owners[0]);
+        let (statement, params) = SqlHelper::individual_statement(&individual);
+        assert!(!statement.contains('\''));
+        assert_eq!(text(&params[0]).as_deref(), Some("O'BRIEN ELLEN H"));
+        assert_eq!(text(&params[3]).as_deref(), Some("O'BRIEN"));
+    }
+
+    /// Needs a live SQL Server with the schema applied, run it with `cargo test -- --ignored`
+    /// and the connection string in `TIBERIUS_TEST_CONNECTION_STRING`.
+    #[tokio::test]
+    #[ignore]
+    async fn quoted_names_round_trip() {
+        let conn_str = env::var("TIBERIUS_TEST_CONNECTION_STRING").expect("TIBERIUS_TEST_CONNECTION_STRING is not set");
+
+        let filing = quoted_filing();
+        let helper = SqlHelper::new();
+        let mut client = connect(&conn_str).await.unwrap();
+
+        let issuer_id = helper.create_issuer(&mut client, &filing).await.unwrap();
+        let ind_id = helper.create_individual(&mut client, &filing.owners[0]).await.unwrap();
+
+        let row = client.query("SELECT name FROM issuer WHERE issuer_id = @P1", &[&issuer_id])
+            .await.unwrap().into_row().await.unwrap().unwrap();
+        assert_eq!(row.get::<&str, _>("name"), Some("MCDONALD'S CORP"));
+
+        let row = client.query("SELECT full_name FROM individual WHERE individual_id = @P1", &[&ind_id])
+            .await.unwrap().into_row().await.unwrap().unwrap();
+        assert_eq!(row.get::<&str, _>("full_name"), Some("O'BRIEN ELLEN H"));
+    }
+}
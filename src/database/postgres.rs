@@ -0,0 +1,1016 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use async_trait::async_trait;
+use bb8::{ManageConnection, Pool};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use tokio_postgres::{Client, Config, NoTls, Row};
+
+use crate::database::insert_models::{NewDerivTransaction, NewForm, NewFormOwner, NewHolding, NewIndividual, NewIssuer, NewNonDerivTransaction};
+use crate::database::migrations::{self, Migration};
+use crate::database::query_models::{self, parse_decimal, parse_ids, Checkpoint, CheckpointStatus, DerivTransaction, Footnote, Form, FormDocument, NonDerivTransaction, SaleMatch};
+use crate::database::{join_ids, FilingStore, SaveStats, SideDocuments, POOL_SIZE, RESOLVE_CUSIPS};
+use crate::secgov::models::{BeneficialOwnership, DerivativeTransaction, Filing, FilingTransaction, Holding, InstitutionalHolding, MaterialEvent, ProposedSale, ReportingOwner, XbrlFact};
+
+// tokio-postgres has no BigDecimal support, decimals are bound as `$n::TEXT::NUMERIC`
+// and read back through a `::TEXT` cast so no precision is lost on the way.
+
+const NONDERIV_COLUMNS: &str = "transaction_id, date_reported, form_id, issuer_id, individual_id, action_code, ownership_code, transaction_code, \
+    shares_balance::TEXT AS shares_balance, shares_traded::TEXT AS shares_traded, avg_price::TEXT AS avg_price, amount::TEXT AS amount, \
+    relationships, officer_title, other_text, roles, superseded_by";
+
+const DERIV_COLUMNS: &str = "transaction_id, date_reported, form_id, issuer_id, individual_id, security_title, conversion_price::TEXT AS conversion_price, \
+    exercise_date, expiration_date, underlying_title, underlying_shares::TEXT AS underlying_shares, action_code, ownership_code, transaction_code, \
+    shares_balance::TEXT AS shares_balance, shares_traded::TEXT AS shares_traded, avg_price::TEXT AS avg_price, amount::TEXT AS amount, relationships, superseded_by";
+
+const HOLDING_COLUMNS: &str = "holding_id, date_reported, form_id, issuer_id, individual_id, security_title, shares_owned::TEXT AS shares_owned, \
+    ownership_code, ownership_nature, derivative, conversion_price::TEXT AS conversion_price, exercise_date, expiration_date, underlying_title, \
+    underlying_shares::TEXT AS underlying_shares, superseded_by";
+
+const PROPOSED_SALE_COLUMNS: &str = "proposed_sale_id, access_no, form_type, form_url, filed_date, issuer_cik, issuer_name, seller_cik, seller_name, \
+    relationship, security_class, broker, shares::TEXT AS shares, market_value::TEXT AS market_value, approx_sale_date, plan_adoption_date";
+
+const OWNERSHIP_COLUMNS: &str = "ownership_id, issuer_id, access_no, form_type, form_url, filed_date, event_date, issuer_cusip, filer_cik, filer_name, \
+    amendment_no, percent_of_class::TEXT AS percent_of_class, aggregate_shares::TEXT AS aggregate_shares, sole_voting::TEXT AS sole_voting, \
+    shared_voting::TEXT AS shared_voting, sole_dispositive::TEXT AS sole_dispositive, shared_dispositive::TEXT AS shared_dispositive";
+
+const INSTITUTIONAL_COLUMNS: &str = "holding_id, issuer_id, access_no, form_type, amendment_type, form_url, filed_date, period_of_report, manager_cik, \
+    manager_name, issuer_name, title_of_class, cusip, market_value::TEXT AS market_value, shares::TEXT AS shares, share_type, put_call, \
+    investment_discretion, voting_sole::TEXT AS voting_sole, voting_shared::TEXT AS voting_shared, voting_none::TEXT AS voting_none";
+
+const FACT_COLUMNS: &str = "fact_id, issuer_id, access_no, form_type, fiscal_year, fiscal_period, taxonomy, concept, label, report, report_group, \
+    fact_value::TEXT AS fact_value, unit, decimals, period_start, period_end, dimensions";
+
+fn text(value: &Option<BigDecimal>) -> Option<String> {
+    value.as_ref().map(|v| v.to_string())
+}
+
+fn form_from_row(row: &Row) -> Result<Form, Box<dyn Error>> {
+    Ok(Form {
+        form_id: row.try_get("form_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        date_reported: row.try_get("date_reported")?,
+        form_type: row.try_get("form_type")?,
+        txt_url: row.try_get("txt_url")?,
+        access_no: row.try_get("access_no")?,
+        web_url: row.try_get("web_url")?,
+        filed_date: row.try_get("filed_date")?,
+        original_submission: row.try_get("original_submission")?,
+        amends_form_id: row.try_get("amends_form_id")?,
+        superseded_by: row.try_get("superseded_by")?,
+    })
+}
+
+fn nonderiv_from_row(row: &Row) -> Result<NonDerivTransaction, Box<dyn Error>> {
+    Ok(NonDerivTransaction {
+        transaction_id: row.try_get("transaction_id")?,
+        date_reported: row.try_get("date_reported")?,
+        form_id: row.try_get("form_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        individual_id: row.try_get("individual_id")?,
+        action_code: row.try_get("action_code")?,
+        ownership_code: row.try_get("ownership_code")?,
+        transaction_code: row.try_get("transaction_code")?,
+        shares_balance: parse_decimal(row.try_get("shares_balance")?),
+        shares_traded: parse_decimal(row.try_get("shares_traded")?),
+        avg_price: parse_decimal(row.try_get("avg_price")?),
+        amount: parse_decimal(row.try_get("amount")?),
+        relationships: parse_ids(row.try_get("relationships")?),
+        officer_title: row.try_get("officer_title")?,
+        other_text: row.try_get("other_text")?,
+        roles: parse_ids(row.try_get("roles")?),
+        superseded_by: row.try_get("superseded_by")?,
+    })
+}
+
+fn deriv_from_row(row: &Row) -> Result<DerivTransaction, Box<dyn Error>> {
+    Ok(DerivTransaction {
+        transaction_id: row.try_get("transaction_id")?,
+        date_reported: row.try_get("date_reported")?,
+        form_id: row.try_get("form_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        individual_id: row.try_get("individual_id")?,
+        security_title: row.try_get("security_title")?,
+        conversion_price: parse_decimal(row.try_get("conversion_price")?),
+        exercise_date: row.try_get("exercise_date")?,
+        expiration_date: row.try_get("expiration_date")?,
+        underlying_title: row.try_get("underlying_title")?,
+        underlying_shares: parse_decimal(row.try_get("underlying_shares")?),
+        action_code: row.try_get("action_code")?,
+        ownership_code: row.try_get("ownership_code")?,
+        transaction_code: row.try_get("transaction_code")?,
+        shares_balance: parse_decimal(row.try_get("shares_balance")?),
+        shares_traded: parse_decimal(row.try_get("shares_traded")?),
+        avg_price: parse_decimal(row.try_get("avg_price")?),
+        amount: parse_decimal(row.try_get("amount")?),
+        relationships: parse_ids(row.try_get("relationships")?),
+        superseded_by: row.try_get("superseded_by")?,
+    })
+}
+
+fn holding_from_row(row: &Row) -> Result<query_models::Holding, Box<dyn Error>> {
+    Ok(query_models::Holding {
+        holding_id: row.try_get("holding_id")?,
+        date_reported: row.try_get("date_reported")?,
+        form_id: row.try_get("form_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        individual_id: row.try_get("individual_id")?,
+        security_title: row.try_get("security_title")?,
+        shares_owned: parse_decimal(row.try_get("shares_owned")?),
+        ownership_code: row.try_get("ownership_code")?,
+        ownership_nature: row.try_get("ownership_nature")?,
+        derivative: row.try_get("derivative")?,
+        conversion_price: parse_decimal(row.try_get("conversion_price")?),
+        exercise_date: row.try_get("exercise_date")?,
+        expiration_date: row.try_get("expiration_date")?,
+        underlying_title: row.try_get("underlying_title")?,
+        underlying_shares: parse_decimal(row.try_get("underlying_shares")?),
+        superseded_by: row.try_get("superseded_by")?,
+    })
+}
+
+fn proposed_sale_from_row(row: &Row) -> Result<query_models::ProposedSale, Box<dyn Error>> {
+    Ok(query_models::ProposedSale {
+        proposed_sale_id: row.try_get("proposed_sale_id")?,
+        access_no: row.try_get("access_no")?,
+        form_type: row.try_get("form_type")?,
+        form_url: row.try_get("form_url")?,
+        filed_date: row.try_get("filed_date")?,
+        issuer_cik: row.try_get("issuer_cik")?,
+        issuer_name: row.try_get("issuer_name")?,
+        seller_cik: row.try_get("seller_cik")?,
+        seller_name: row.try_get("seller_name")?,
+        relationship: row.try_get("relationship")?,
+        security_class: row.try_get("security_class")?,
+        broker: row.try_get("broker")?,
+        shares: parse_decimal(row.try_get("shares")?),
+        market_value: parse_decimal(row.try_get("market_value")?),
+        approx_sale_date: row.try_get("approx_sale_date")?,
+        plan_adoption_date: row.try_get("plan_adoption_date")?,
+    })
+}
+
+fn ownership_from_row(row: &Row) -> Result<query_models::BeneficialOwnership, Box<dyn Error>> {
+    Ok(query_models::BeneficialOwnership {
+        ownership_id: row.try_get("ownership_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        access_no: row.try_get("access_no")?,
+        form_type: row.try_get("form_type")?,
+        form_url: row.try_get("form_url")?,
+        filed_date: row.try_get("filed_date")?,
+        event_date: row.try_get("event_date")?,
+        issuer_cusip: row.try_get("issuer_cusip")?,
+        filer_cik: row.try_get("filer_cik")?,
+        filer_name: row.try_get("filer_name")?,
+        amendment_no: row.try_get("amendment_no")?,
+        percent_of_class: parse_decimal(row.try_get("percent_of_class")?),
+        aggregate_shares: parse_decimal(row.try_get("aggregate_shares")?),
+        sole_voting: parse_decimal(row.try_get("sole_voting")?),
+        shared_voting: parse_decimal(row.try_get("shared_voting")?),
+        sole_dispositive: parse_decimal(row.try_get("sole_dispositive")?),
+        shared_dispositive: parse_decimal(row.try_get("shared_dispositive")?),
+    })
+}
+
+fn institutional_from_row(row: &Row) -> Result<query_models::InstitutionalHolding, Box<dyn Error>> {
+    Ok(query_models::InstitutionalHolding {
+        holding_id: row.try_get("holding_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        access_no: row.try_get("access_no")?,
+        form_type: row.try_get("form_type")?,
+        amendment_type: row.try_get("amendment_type")?,
+        form_url: row.try_get("form_url")?,
+        filed_date: row.try_get("filed_date")?,
+        period_of_report: row.try_get("period_of_report")?,
+        manager_cik: row.try_get("manager_cik")?,
+        manager_name: row.try_get("manager_name")?,
+        issuer_name: row.try_get("issuer_name")?,
+        title_of_class: row.try_get("title_of_class")?,
+        cusip: row.try_get("cusip")?,
+        market_value: parse_decimal(row.try_get("market_value")?),
+        shares: parse_decimal(row.try_get("shares")?),
+        share_type: row.try_get("share_type")?,
+        put_call: row.try_get("put_call")?,
+        investment_discretion: row.try_get("investment_discretion")?,
+        voting_sole: parse_decimal(row.try_get("voting_sole")?),
+        voting_shared: parse_decimal(row.try_get("voting_shared")?),
+        voting_none: parse_decimal(row.try_get("voting_none")?),
+    })
+}
+
+fn fact_from_row(row: &Row) -> Result<query_models::XbrlFact, Box<dyn Error>> {
+    Ok(query_models::XbrlFact {
+        fact_id: row.try_get("fact_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        access_no: row.try_get("access_no")?,
+        form_type: row.try_get("form_type")?,
+        fiscal_year: row.try_get("fiscal_year")?,
+        fiscal_period: row.try_get("fiscal_period")?,
+        taxonomy: row.try_get("taxonomy")?,
+        concept: row.try_get("concept")?,
+        label: row.try_get("label")?,
+        report: row.try_get("report")?,
+        report_group: row.try_get("report_group")?,
+        value: parse_decimal(row.try_get("fact_value")?).unwrap_or_default(),
+        unit: row.try_get("unit")?,
+        decimals: row.try_get("decimals")?,
+        period_start: row.try_get("period_start")?,
+        period_end: row.try_get("period_end")?,
+        dimensions: row.try_get("dimensions")?,
+    })
+}
+
+fn event_from_row(row: &Row) -> Result<query_models::MaterialEvent, Box<dyn Error>> {
+    Ok(query_models::MaterialEvent {
+        event_id: row.try_get("event_id")?,
+        issuer_id: row.try_get("issuer_id")?,
+        access_no: row.try_get("access_no")?,
+        form_type: row.try_get("form_type")?,
+        form_url: row.try_get("form_url")?,
+        filed_date: row.try_get("filed_date")?,
+        event_date: row.try_get("event_date")?,
+        item: row.try_get("item")?,
+        item_title: row.try_get("item_title")?,
+        section: row.try_get("section")?,
+    })
+}
+
+fn checkpoint_from_row(row: &Row) -> Result<Checkpoint, Box<dyn Error>> {
+    let status: String = row.try_get("status")?;
+
+    Ok(Checkpoint {
+        mine_date: row.try_get("mine_date")?,
+        entries: row.try_get("entries")?,
+        transactions: row.try_get("transactions")?,
+        status: CheckpointStatus::parse(&status).ok_or(format!("Unknown checkpoint status {status}"))?,
+    })
+}
+
+/// Hands out tokio-postgres connections to a [`bb8::Pool`].
+pub struct PostgresManager {
+    config: Config,
+}
+
+#[async_trait]
+impl ManageConnection for PostgresManager {
+    type Connection = Client;
+    type Error = tokio_postgres::Error;
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        let (client, connection) = self.config.connect(NoTls).await?;
+
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                println!("Postgres connection error: {}", e);
+            }
+        });
+
+        Ok(client)
+    }
+
+    async fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        conn.simple_query("").await.map(|_| ())
+    }
+
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        conn.is_closed()
+    }
+}
+
+/// [`FilingStore`] backed by PostgreSQL. Issuers, individuals and forms are upserted on their
+/// natural keys, so there is no cache in front of them.
+pub struct PostgresStore {
+    pool: Pool<PostgresManager>,
+}
+
+impl PostgresStore {
+    pub async fn connect(conn_str: &str) -> Result<PostgresStore, Box<dyn Error>> {
+        let manager = PostgresManager { config: conn_str.parse()? };
+
+        Ok(PostgresStore {
+            pool: Pool::builder().max_size(POOL_SIZE).build(manager).await?,
+        })
+    }
+}
+
+/// The queries of [`PostgresStore`] on one connection taken from its pool. A day is saved
+/// through a single `PgConn` so that all of it falls inside one transaction.
+struct PgConn<'a>(&'a Client);
+
+impl PgConn<'_> {
+    async fn apply(&self, migration: &Migration) -> Result<(), Box<dyn Error>> {
+        self.0.batch_execute(migration.sql).await?;
+
+        self.0.execute(
+            "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+            &[&migration.version, &migration.name],
+        ).await?;
+
+        Ok(())
+    }
+
+    /// Links a row to the footnotes annotating each of its fields, `table` being the footnote
+    /// table of its kind and `id_column` the key of the row in it.
+    async fn insert_field_footnotes(&self, table: &str, id_column: &str, id: i64, footnotes: &HashMap<String, Vec<String>>) -> Result<(), Box<dyn Error>> {
+        let query = format!("INSERT INTO {table} ({id_column}, field, footnote_key) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING");
+
+        for (field, keys) in footnotes {
+            for key in keys {
+                self.0.execute(&query, &[&id, field, key]).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl FilingStore for PgConn<'_> {
+    async fn migrate(&self) -> Result<Vec<i32>, Box<dyn Error>> {
+        let client = self.0;
+
+        client.batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (\
+                 version INTEGER PRIMARY KEY, \
+                 name VARCHAR(128) NOT NULL, \
+                 applied_at TIMESTAMPTZ NOT NULL DEFAULT now()\
+             )",
+        ).await?;
+
+        let applied: Vec<i32> = client
+            .query("SELECT version FROM schema_migrations", &[])
+            .await?
+            .iter()
+            .map(|row| row.get("version"))
+            .collect();
+
+        let mut newly_applied = Vec::new();
+        for migration in migrations::pending(migrations::POSTGRES, &applied) {
+            client.batch_execute("BEGIN").await?;
+
+            if let Err(e) = self.apply(migration).await {
+                client.batch_execute("ROLLBACK").await?;
+                return Err(format!("Migration {} ({}) failed: {}", migration.version, migration.name, e).into());
+            }
+
+            client.batch_execute("COMMIT").await?;
+            println!("Applied migration {} ({})", migration.version, migration.name);
+            newly_applied.push(migration.version);
+        }
+
+        Ok(newly_applied)
+    }
+
+    async fn create_issuer(&self, filing: &Filing) -> Result<i32, Box<dyn Error>> {
+        let client = self.0;
+        let new_issuer = NewIssuer::map(filing);
+
+        let row = client.query_one(
+            "INSERT INTO issuer (name, symbol, cik) VALUES ($1, $2, $3) \
+             ON CONFLICT (cik) DO UPDATE SET name = COALESCE(NULLIF(issuer.name, ''), EXCLUDED.name), \
+             symbol = COALESCE(NULLIF(issuer.symbol, ''), EXCLUDED.symbol) RETURNING issuer_id",
+            &[&new_issuer.issuer_name, &new_issuer.issuer_symbol, &new_issuer.cik],
+        ).await?;
+
+        Ok(row.try_get("issuer_id")?)
+    }
+
+    async fn create_individual(&self, owner: &ReportingOwner) -> Result<i32, Box<dyn Error>> {
+        let client = self.0;
+        let new_ind = NewIndividual::map(owner);
+
+        let row = client.query_one(
+            "INSERT INTO individual (full_name, cik, first_name, last_name) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (cik) DO UPDATE SET cik = EXCLUDED.cik RETURNING individual_id",
+            &[&new_ind.full_name, &new_ind.cik, &new_ind.first_name, &new_ind.last_name],
+        ).await?;
+
+        Ok(row.try_get("individual_id")?)
+    }
+
+    async fn create_form(&self, filing: &Filing, issuer_id: i32) -> Result<i64, Box<dyn Error>> {
+        let client = self.0;
+        let new_form = NewForm::map(filing, issuer_id);
+
+        let row = client.query_one(
+            "INSERT INTO form (issuer_id, date_reported, form_type, txt_url, web_url, access_no, filed_date, original_submission) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+             ON CONFLICT (access_no) DO UPDATE SET access_no = EXCLUDED.access_no RETURNING form_id",
+            &[&new_form.issuer_id, &new_form.date_reported, &new_form.form_type, &new_form.txt_url, &new_form.web_url, &new_form.access_no, &new_form.filed_date, &new_form.original_submission],
+        ).await?;
+
+        Ok(row.try_get("form_id")?)
+    }
+
+    async fn link_form_owner(&self, owner: &ReportingOwner, form_id: i64, ind_id: i32) -> Result<(), Box<dyn Error>> {
+        let client = self.0;
+        let new_link = NewFormOwner::map(owner, form_id, ind_id);
+        let relationships = join_ids(&new_link.relationships);
+        let roles = join_ids(&new_link.roles);
+
+        client.execute(
+            "INSERT INTO form_owner (form_id, individual_id, relationships, officer_title, other_text, roles, street1, street2, city, state, zip_code) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) ON CONFLICT DO NOTHING",
+            &[&new_link.form_id, &new_link.individual_id, &relationships, &new_link.officer_title, &new_link.other_text, &roles, &new_link.street1, &new_link.street2, &new_link.city, &new_link.state, &new_link.zip_code],
+        ).await?;
+
+        Ok(())
+    }
+
+    async fn insert_footnotes(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        let client = self.0;
+        for (key, text) in &filing.footnotes {
+            client.execute(
+                "INSERT INTO footnote (form_id, footnote_key, text) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                &[&form_id, key, text],
+            ).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn insert_documents(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        let client = self.0;
+        for doc in &filing.documents {
+            client.execute(
+                "INSERT INTO form_document (form_id, name, seq, doc_type, description, size) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT DO NOTHING",
+                &[&form_id, &doc.name, &doc.seq, &doc.doc_type, &doc.description, &doc.size],
+            ).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn insert_nonderiv(&self, trans: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn Error>> {
+        let client = self.0;
+        let new_trans = NewNonDerivTransaction::map(trans, form_id, issuer_id, ind_id);
+
+        let query = format!(
+            "INSERT INTO non_deriv_transaction (date_reported, form_id, issuer_id, individual_id, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships, officer_title, other_text, roles) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8::TEXT::NUMERIC, $9::TEXT::NUMERIC, $10::TEXT::NUMERIC, $11::TEXT::NUMERIC, $12, $13, $14, $15) \
+             RETURNING {NONDERIV_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&new_trans.date_reported, &new_trans.form_id, &new_trans.issuer_id, &new_trans.individual_id, &new_trans.action_code, &new_trans.ownership_code, &new_trans.transaction_code, &text(&new_trans.shares_balance), &text(&new_trans.shares_traded), &text(&new_trans.avg_price), &text(&new_trans.amount), &join_ids(&new_trans.relationships), &new_trans.officer_title, &new_trans.other_text, &join_ids(&new_trans.roles)],
+        ).await?;
+
+        nonderiv_from_row(&row)
+    }
+
+    async fn insert_trans_footnotes(&self, trans: &FilingTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        self.insert_field_footnotes("transaction_footnote", "transaction_id", transaction_id, &trans.footnotes).await
+    }
+
+    async fn insert_deriv(&self, filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<DerivTransaction, Box<dyn Error>> {
+        let client = self.0;
+        let new_trans = NewDerivTransaction::map(filing, trans, form_id, issuer_id, ind_id);
+
+        let query = format!(
+            "INSERT INTO deriv_transaction (date_reported, form_id, issuer_id, individual_id, security_title, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships) \
+             VALUES ($1, $2, $3, $4, $5, $6::TEXT::NUMERIC, $7, $8, $9, $10::TEXT::NUMERIC, $11, $12, $13, $14::TEXT::NUMERIC, $15::TEXT::NUMERIC, $16::TEXT::NUMERIC, $17::TEXT::NUMERIC, $18) \
+             RETURNING {DERIV_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&new_trans.date_reported, &new_trans.form_id, &new_trans.issuer_id, &new_trans.individual_id, &new_trans.security_title, &text(&new_trans.conversion_price), &new_trans.exercise_date, &new_trans.expiration_date, &new_trans.underlying_title, &text(&new_trans.underlying_shares), &new_trans.action_code, &new_trans.ownership_code, &new_trans.transaction_code, &text(&new_trans.shares_balance), &text(&new_trans.shares_traded), &text(&new_trans.avg_price), &text(&new_trans.amount), &join_ids(&new_trans.relationships)],
+        ).await?;
+
+        deriv_from_row(&row)
+    }
+
+    async fn insert_deriv_footnotes(&self, trans: &DerivativeTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        self.insert_field_footnotes("deriv_transaction_footnote", "transaction_id", transaction_id, &trans.footnotes).await
+    }
+
+    async fn insert_holding(&self, filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<query_models::Holding, Box<dyn Error>> {
+        let client = self.0;
+        let new_holding = NewHolding::map(filing, holding, form_id, issuer_id, ind_id);
+
+        let query = format!(
+            "INSERT INTO holding (date_reported, form_id, issuer_id, individual_id, security_title, shares_owned, ownership_code, ownership_nature, derivative, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares) \
+             VALUES ($1, $2, $3, $4, $5, $6::TEXT::NUMERIC, $7, $8, $9, $10::TEXT::NUMERIC, $11, $12, $13, $14::TEXT::NUMERIC) \
+             RETURNING {HOLDING_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&new_holding.date_reported, &new_holding.form_id, &new_holding.issuer_id, &new_holding.individual_id, &new_holding.security_title, &text(&new_holding.shares_owned), &new_holding.ownership_code, &new_holding.ownership_nature, &new_holding.derivative,
+                &text(&new_holding.conversion_price), &new_holding.exercise_date, &new_holding.expiration_date, &new_holding.underlying_title, &text(&new_holding.underlying_shares)],
+        ).await?;
+
+        holding_from_row(&row)
+    }
+
+    async fn insert_holding_footnotes(&self, holding: &Holding, holding_id: i64) -> Result<(), Box<dyn Error>> {
+        self.insert_field_footnotes("holding_footnote", "holding_id", holding_id, &holding.footnotes).await
+    }
+
+    async fn link_amendment(&self, filing: &Filing, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<Option<i64>, Box<dyn Error>> {
+        let client = self.0;
+
+        let original = client.query_opt(
+            "SELECT form_id FROM form WHERE issuer_id = $1 AND form_type = $2 AND form_id <> $3 AND amends_form_id IS NULL \
+             AND EXISTS (SELECT 1 FROM form_owner o WHERE o.form_id = form.form_id AND o.individual_id = $4) \
+             AND (filed_date = $5 OR ((filed_date IS NULL OR $5 IS NULL) AND date_reported = $6)) \
+             ORDER BY form_id DESC LIMIT 1",
+            &[&issuer_id, &filing.base_form_type(), &form_id, &ind_id, &filing.original_submission, &filing.form_date],
+        ).await?;
+
+        let Some(original) = original.map(|row| row.get::<_, i64>(0)) else {
+            return Ok(None);
+        };
+
+        client.execute("UPDATE form SET amends_form_id = $1 WHERE form_id = $2", &[&original, &form_id]).await?;
+        client.execute(
+            "UPDATE form SET superseded_by = $1 WHERE (form_id = $2 OR amends_form_id = $2) AND form_id <> $1",
+            &[&form_id, &original],
+        ).await?;
+
+        for table in ["non_deriv_transaction", "deriv_transaction", "holding"] {
+            client.execute(
+                &format!("UPDATE {table} SET superseded_by = $1 WHERE form_id IN (SELECT form_id FROM form WHERE superseded_by = $1)"),
+                &[&form_id],
+            ).await?;
+        }
+
+        Ok(Some(original))
+    }
+
+    async fn get_form_version(&self, access_no: &str) -> Result<Option<Form>, Box<dyn Error>> {
+        let client = self.0;
+        match client.query_opt("SELECT * FROM form WHERE access_no = $1", &[&access_no]).await? {
+            Some(row) => Ok(Some(form_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_form_by_id(&self, form_id: i64) -> Result<Option<Form>, Box<dyn Error>> {
+        let client = self.0;
+        match client.query_opt("SELECT * FROM form WHERE form_id = $1", &[&form_id]).await? {
+            Some(row) => Ok(Some(form_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_nonderiv(&self, form_id: i64) -> Result<Vec<NonDerivTransaction>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!("SELECT {NONDERIV_COLUMNS} FROM non_deriv_transaction WHERE form_id = $1 AND superseded_by IS NULL ORDER BY transaction_id");
+
+        client.query(&query, &[&form_id]).await?
+            .iter()
+            .map(nonderiv_from_row)
+            .collect()
+    }
+
+    async fn get_footnotes(&self, form_id: i64) -> Result<Vec<Footnote>, Box<dyn Error>> {
+        let client = self.0;
+        client.query("SELECT form_id, footnote_key, text FROM footnote WHERE form_id = $1", &[&form_id]).await?
+            .iter()
+            .map(|row| Ok(Footnote {
+                form_id: row.try_get("form_id")?,
+                footnote_key: row.try_get("footnote_key")?,
+                text: row.try_get("text")?,
+            }))
+            .collect()
+    }
+
+    async fn get_documents(&self, form_id: i64) -> Result<Vec<FormDocument>, Box<dyn Error>> {
+        let client = self.0;
+        client.query("SELECT form_id, name, seq, doc_type, description, size FROM form_document WHERE form_id = $1 ORDER BY CASE WHEN seq IS NULL THEN 1 ELSE 0 END, seq, name", &[&form_id]).await?
+            .iter()
+            .map(|row| Ok(FormDocument {
+                form_id: row.try_get("form_id")?,
+                name: row.try_get("name")?,
+                seq: row.try_get("seq")?,
+                doc_type: row.try_get("doc_type")?,
+                description: row.try_get("description")?,
+                size: row.try_get("size")?,
+            }))
+            .collect()
+    }
+
+    async fn get_baseline(&self, issuer_id: i32, ind_id: i32) -> Result<Vec<query_models::Holding>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!(
+            "SELECT {HOLDING_COLUMNS} FROM holding WHERE superseded_by IS NULL AND form_id = ( \
+                 SELECT MAX(f.form_id) FROM form f JOIN form_owner o ON o.form_id = f.form_id \
+                 WHERE f.issuer_id = $1 AND o.individual_id = $2 AND f.form_type IN ('3', '3/A') AND f.superseded_by IS NULL) \
+             ORDER BY holding_id"
+        );
+
+        client.query(&query, &[&issuer_id, &ind_id]).await?
+            .iter()
+            .map(holding_from_row)
+            .collect()
+    }
+
+    async fn insert_proposed_sale(&self, sale: &ProposedSale) -> Result<query_models::ProposedSale, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!(
+            "INSERT INTO proposed_sale (access_no, form_type, form_url, filed_date, issuer_cik, issuer_name, seller_cik, seller_name, relationship, \
+             security_class, broker, shares, market_value, approx_sale_date, plan_adoption_date) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12::TEXT::NUMERIC, $13::TEXT::NUMERIC, $14, $15) \
+             RETURNING {PROPOSED_SALE_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&sale.access_no, &sale.form_type, &sale.form_url, &sale.filed_date, &sale.issuer_cik, &sale.issuer_name, &sale.seller_cik, &sale.seller_name,
+                &sale.relationship, &sale.security_class, &sale.broker, &text(&sale.shares), &text(&sale.market_value), &sale.approx_sale_date, &sale.plan_adoption_date],
+        ).await?;
+
+        proposed_sale_from_row(&row)
+    }
+
+    async fn get_proposed_sales(&self, access_no: &str) -> Result<Vec<query_models::ProposedSale>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!("SELECT {PROPOSED_SALE_COLUMNS} FROM proposed_sale WHERE access_no = $1 ORDER BY proposed_sale_id");
+
+        client.query(&query, &[&access_no]).await?
+            .iter()
+            .map(proposed_sale_from_row)
+            .collect()
+    }
+
+    async fn get_sale_matches(&self, access_no: &str) -> Result<Vec<SaleMatch>, Box<dyn Error>> {
+        let client = self.0;
+
+        client.query(
+            "SELECT p.proposed_sale_id, t.transaction_id, t.form_id, t.date_reported, t.shares_traded::TEXT AS shares_traded, t.avg_price::TEXT AS avg_price \
+             FROM proposed_sale p \
+             JOIN issuer s ON s.cik = p.issuer_cik \
+             JOIN individual i ON i.cik = p.seller_cik \
+             JOIN form_owner o ON o.individual_id = i.individual_id \
+             JOIN non_deriv_transaction t ON t.form_id = o.form_id AND t.issuer_id = s.issuer_id \
+             WHERE p.access_no = $1 AND t.transaction_code = 'S' AND t.action_code = 'D' AND t.superseded_by IS NULL \
+             AND t.date_reported >= COALESCE(p.filed_date, p.approx_sale_date) \
+             AND t.date_reported <= COALESCE(p.approx_sale_date, p.filed_date) + 90 \
+             ORDER BY p.proposed_sale_id, t.date_reported, t.transaction_id",
+            &[&access_no],
+        ).await?
+            .iter()
+            .map(|row| Ok(SaleMatch {
+                proposed_sale_id: row.try_get("proposed_sale_id")?,
+                transaction_id: row.try_get("transaction_id")?,
+                form_id: row.try_get("form_id")?,
+                date_reported: row.try_get("date_reported")?,
+                shares_traded: parse_decimal(row.try_get("shares_traded")?),
+                avg_price: parse_decimal(row.try_get("avg_price")?),
+            }))
+            .collect()
+    }
+
+    async fn insert_ownership_event(&self, event: &BeneficialOwnership, issuer_id: i32) -> Result<query_models::BeneficialOwnership, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!(
+            "INSERT INTO beneficial_ownership (issuer_id, access_no, form_type, form_url, filed_date, event_date, issuer_cusip, filer_cik, filer_name, \
+             amendment_no, percent_of_class, aggregate_shares, sole_voting, shared_voting, sole_dispositive, shared_dispositive) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11::TEXT::NUMERIC, $12::TEXT::NUMERIC, $13::TEXT::NUMERIC, $14::TEXT::NUMERIC, \
+             $15::TEXT::NUMERIC, $16::TEXT::NUMERIC) \
+             RETURNING {OWNERSHIP_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&issuer_id, &event.access_no, &event.form_type, &event.form_url, &event.filed_date, &event.event_date, &event.issuer_cusip, &event.filer_cik,
+                &event.filer_name, &event.amendment_no, &text(&event.percent_of_class), &text(&event.aggregate_shares), &text(&event.sole_voting),
+                &text(&event.shared_voting), &text(&event.sole_dispositive), &text(&event.shared_dispositive)],
+        ).await?;
+
+        ownership_from_row(&row)
+    }
+
+    async fn get_ownership_events(&self, access_no: &str) -> Result<Vec<query_models::BeneficialOwnership>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!("SELECT {OWNERSHIP_COLUMNS} FROM beneficial_ownership WHERE access_no = $1 ORDER BY ownership_id");
+
+        client.query(&query, &[&access_no]).await?
+            .iter()
+            .map(ownership_from_row)
+            .collect()
+    }
+
+    async fn insert_institutional_holding(&self, holding: &InstitutionalHolding) -> Result<query_models::InstitutionalHolding, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!(
+            "INSERT INTO institutional_holding (access_no, form_type, amendment_type, form_url, filed_date, period_of_report, manager_cik, manager_name, issuer_name, title_of_class, cusip, \
+             market_value, shares, share_type, put_call, investment_discretion, voting_sole, voting_shared, voting_none) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12::TEXT::NUMERIC, $13::TEXT::NUMERIC, $14, $15, $16, \
+             $17::TEXT::NUMERIC, $18::TEXT::NUMERIC, $19::TEXT::NUMERIC) \
+             RETURNING {INSTITUTIONAL_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&holding.access_no, &holding.form_type, &holding.amendment_type, &holding.form_url, &holding.filed_date, &holding.period_of_report,
+                &holding.manager_cik, &holding.manager_name, &holding.issuer_name, &holding.title_of_class, &holding.cusip, &text(&holding.market_value),
+                &text(&holding.shares), &holding.share_type, &holding.put_call, &holding.investment_discretion, &text(&holding.voting_sole),
+                &text(&holding.voting_shared), &text(&holding.voting_none)],
+        ).await?;
+
+        institutional_from_row(&row)
+    }
+
+    async fn get_institutional_holdings(&self, access_no: &str) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!("SELECT {INSTITUTIONAL_COLUMNS} FROM institutional_holding WHERE access_no = $1 ORDER BY holding_id");
+
+        client.query(&query, &[&access_no]).await?
+            .iter()
+            .map(institutional_from_row)
+            .collect()
+    }
+
+    async fn get_manager_reports(&self, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!(
+            "SELECT {INSTITUTIONAL_COLUMNS} FROM institutional_holding WHERE manager_cik = $1 AND period_of_report = $2 ORDER BY filed_date, holding_id"
+        );
+
+        client.query(&query, &[&manager_cik, &period_of_report]).await?
+            .iter()
+            .map(institutional_from_row)
+            .collect()
+    }
+
+    async fn resolve_cusips(&self) -> Result<usize, Box<dyn Error>> {
+        let mut linked = 0;
+        for statement in RESOLVE_CUSIPS {
+            linked = self.0.execute(statement, &[]).await?;
+        }
+
+        Ok(linked as usize)
+    }
+
+    async fn insert_xbrl_fact(&self, fact: &XbrlFact, issuer_id: i32) -> Result<query_models::XbrlFact, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!(
+            "INSERT INTO xbrl_fact (issuer_id, access_no, form_type, fiscal_year, fiscal_period, taxonomy, concept, label, report, report_group, fact_value, unit, decimals, \
+             period_start, period_end, dimensions) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11::TEXT::NUMERIC, $12, $13, $14, $15, $16) \
+             RETURNING {FACT_COLUMNS}"
+        );
+
+        let row = client.query_one(
+            &query,
+            &[&issuer_id, &fact.access_no, &fact.form_type, &fact.fiscal_year, &fact.fiscal_period, &fact.taxonomy, &fact.concept, &fact.label,
+                &fact.report, &fact.report_group, &fact.value.to_string(), &fact.unit, &fact.decimals, &fact.period_start, &fact.period_end, &fact.dimensions],
+        ).await?;
+
+        fact_from_row(&row)
+    }
+
+    async fn get_report_facts(&self, access_no: &str) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!("SELECT {FACT_COLUMNS} FROM xbrl_fact WHERE access_no = $1 ORDER BY fact_id");
+
+        client.query(&query, &[&access_no]).await?
+            .iter()
+            .map(fact_from_row)
+            .collect()
+    }
+
+    async fn get_period_facts(&self, issuer_id: i32, period_end: NaiveDate) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let client = self.0;
+        let query = format!("SELECT {FACT_COLUMNS} FROM xbrl_fact WHERE issuer_id = $1 AND period_end = $2 ORDER BY concept, dimensions, fact_id");
+
+        client.query(&query, &[&issuer_id, &period_end]).await?
+            .iter()
+            .map(fact_from_row)
+            .collect()
+    }
+
+    async fn insert_material_event(&self, event: &MaterialEvent, issuer_id: i32) -> Result<query_models::MaterialEvent, Box<dyn Error>> {
+        let client = self.0;
+
+        let row = client.query_one(
+            "INSERT INTO material_event (issuer_id, access_no, form_type, form_url, filed_date, event_date, item, item_title, section) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *",
+            &[&issuer_id, &event.access_no, &event.form_type, &event.form_url, &event.filed_date, &event.event_date, &event.item, &event.item_title, &event.section],
+        ).await?;
+
+        event_from_row(&row)
+    }
+
+    async fn get_material_events(&self, access_no: &str) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let client = self.0;
+
+        client.query("SELECT * FROM material_event WHERE access_no = $1 ORDER BY event_id", &[&access_no]).await?
+            .iter()
+            .map(event_from_row)
+            .collect()
+    }
+
+    async fn get_issuer_events(&self, issuer_id: i32, from: NaiveDate, to: NaiveDate) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let client = self.0;
+
+        client.query(
+            "SELECT * FROM material_event WHERE issuer_id = $1 AND COALESCE(event_date, filed_date) BETWEEN $2 AND $3 \
+             ORDER BY COALESCE(event_date, filed_date), event_id",
+            &[&issuer_id, &from, &to],
+        ).await?
+            .iter()
+            .map(event_from_row)
+            .collect()
+    }
+
+    async fn get_checkpoint(&self, date: NaiveDate) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+        match self.0.query_opt("SELECT mine_date, entries, transactions, status FROM mining_checkpoint WHERE mine_date = $1", &[&date]).await? {
+            Some(row) => Ok(Some(checkpoint_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn put_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), Box<dyn Error>> {
+        self.0.execute(
+            "INSERT INTO mining_checkpoint (mine_date, entries, transactions, status) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (mine_date) DO UPDATE SET entries = EXCLUDED.entries, transactions = EXCLUDED.transactions, \
+             status = EXCLUDED.status, updated_at = now()",
+            &[&checkpoint.mine_date, &checkpoint.entries, &checkpoint.transactions, &checkpoint.status.as_str()],
+        ).await?;
+
+        Ok(())
+    }
+
+    /// Every statement of the day runs on this one connection, the first filing or side
+    /// document that fails stops the day and it is rolled back.
+    async fn save_day(&self, date: NaiveDate, entries: usize, filings: &[Filing], documents: &SideDocuments) -> Result<SaveStats, Box<dyn Error>> {
+        self.0.batch_execute("BEGIN").await?;
+
+        let saved = async {
+            let stats = self.save_filings(filings).await?;
+            self.save_side_documents(documents).await?;
+            self.put_checkpoint(&stats.checkpoint(date, entries)).await?;
+            Ok::<_, Box<dyn Error>>(stats)
+        }.await;
+
+        match saved {
+            Ok(stats) => {
+                self.0.batch_execute("COMMIT").await?;
+                Ok(stats)
+            }
+            Err(e) => {
+                self.0.batch_execute("ROLLBACK").await?;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl FilingStore for PostgresStore {
+    async fn migrate(&self) -> Result<Vec<i32>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).migrate().await
+    }
+
+    async fn create_issuer(&self, filing: &Filing) -> Result<i32, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).create_issuer(filing).await
+    }
+
+    async fn create_individual(&self, owner: &ReportingOwner) -> Result<i32, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).create_individual(owner).await
+    }
+
+    async fn create_form(&self, filing: &Filing, issuer_id: i32) -> Result<i64, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).create_form(filing, issuer_id).await
+    }
+
+    async fn link_form_owner(&self, owner: &ReportingOwner, form_id: i64, ind_id: i32) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).link_form_owner(owner, form_id, ind_id).await
+    }
+
+    async fn insert_footnotes(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_footnotes(filing, form_id).await
+    }
+
+    async fn insert_documents(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_documents(filing, form_id).await
+    }
+
+    async fn insert_nonderiv(&self, trans: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_nonderiv(trans, form_id, issuer_id, ind_id).await
+    }
+
+    async fn insert_trans_footnotes(&self, trans: &FilingTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_trans_footnotes(trans, transaction_id).await
+    }
+
+    async fn insert_deriv(&self, filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<DerivTransaction, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_deriv(filing, trans, form_id, issuer_id, ind_id).await
+    }
+
+    async fn insert_deriv_footnotes(&self, trans: &DerivativeTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_deriv_footnotes(trans, transaction_id).await
+    }
+
+    async fn insert_holding(&self, filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<query_models::Holding, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_holding(filing, holding, form_id, issuer_id, ind_id).await
+    }
+
+    async fn insert_holding_footnotes(&self, holding: &Holding, holding_id: i64) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_holding_footnotes(holding, holding_id).await
+    }
+
+    async fn link_amendment(&self, filing: &Filing, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<Option<i64>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).link_amendment(filing, form_id, issuer_id, ind_id).await
+    }
+
+    async fn get_form_version(&self, access_no: &str) -> Result<Option<Form>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_form_version(access_no).await
+    }
+
+    async fn get_form_by_id(&self, form_id: i64) -> Result<Option<Form>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_form_by_id(form_id).await
+    }
+
+    async fn get_nonderiv(&self, form_id: i64) -> Result<Vec<NonDerivTransaction>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_nonderiv(form_id).await
+    }
+
+    async fn get_footnotes(&self, form_id: i64) -> Result<Vec<Footnote>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_footnotes(form_id).await
+    }
+
+    async fn get_documents(&self, form_id: i64) -> Result<Vec<FormDocument>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_documents(form_id).await
+    }
+
+    async fn get_baseline(&self, issuer_id: i32, ind_id: i32) -> Result<Vec<query_models::Holding>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_baseline(issuer_id, ind_id).await
+    }
+
+    async fn insert_proposed_sale(&self, sale: &ProposedSale) -> Result<query_models::ProposedSale, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_proposed_sale(sale).await
+    }
+
+    async fn get_proposed_sales(&self, access_no: &str) -> Result<Vec<query_models::ProposedSale>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_proposed_sales(access_no).await
+    }
+
+    async fn get_sale_matches(&self, access_no: &str) -> Result<Vec<SaleMatch>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_sale_matches(access_no).await
+    }
+
+    async fn insert_ownership_event(&self, event: &BeneficialOwnership, issuer_id: i32) -> Result<query_models::BeneficialOwnership, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_ownership_event(event, issuer_id).await
+    }
+
+    async fn get_ownership_events(&self, access_no: &str) -> Result<Vec<query_models::BeneficialOwnership>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_ownership_events(access_no).await
+    }
+
+    async fn insert_institutional_holding(&self, holding: &InstitutionalHolding) -> Result<query_models::InstitutionalHolding, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_institutional_holding(holding).await
+    }
+
+    async fn get_institutional_holdings(&self, access_no: &str) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_institutional_holdings(access_no).await
+    }
+
+    async fn get_manager_reports(&self, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_manager_reports(manager_cik, period_of_report).await
+    }
+
+    async fn resolve_cusips(&self) -> Result<usize, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).resolve_cusips().await
+    }
+
+    async fn insert_xbrl_fact(&self, fact: &XbrlFact, issuer_id: i32) -> Result<query_models::XbrlFact, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_xbrl_fact(fact, issuer_id).await
+    }
+
+    async fn get_report_facts(&self, access_no: &str) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_report_facts(access_no).await
+    }
+
+    async fn get_period_facts(&self, issuer_id: i32, period_end: NaiveDate) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_period_facts(issuer_id, period_end).await
+    }
+
+    async fn insert_material_event(&self, event: &MaterialEvent, issuer_id: i32) -> Result<query_models::MaterialEvent, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).insert_material_event(event, issuer_id).await
+    }
+
+    async fn get_material_events(&self, access_no: &str) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_material_events(access_no).await
+    }
+
+    async fn get_issuer_events(&self, issuer_id: i32, from: NaiveDate, to: NaiveDate) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_issuer_events(issuer_id, from, to).await
+    }
+
+    async fn get_checkpoint(&self, date: NaiveDate) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).get_checkpoint(date).await
+    }
+
+    async fn put_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).put_checkpoint(checkpoint).await
+    }
+
+    async fn save_day(&self, date: NaiveDate, entries: usize, filings: &[Filing], documents: &SideDocuments) -> Result<SaveStats, Box<dyn Error>> {
+        PgConn(&*self.pool.get().await?).save_day(date, entries, filings, documents).await
+    }
+}
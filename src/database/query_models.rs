@@ -3,6 +3,18 @@ use chrono::NaiveDate;
 use std::str::FromStr;
 use tiberius::Row;
 
+/// Splits a comma separated `relationships` or `roles` column back into discriminants.
+pub(crate) fn parse_ids(text: &str) -> Vec<i32> {
+    text.split(',')
+        .filter_map(|s| s.trim().parse::<i32>().ok())
+        .collect()
+}
+
+/// Decimals are bound as text where the driver has no numeric type, this reads them back.
+pub(crate) fn parse_decimal(text: Option<String>) -> Option<BigDecimal> {
+    text.and_then(|t| BigDecimal::from_str(&t).ok())
+}
+
 #[derive(Debug)]
 pub struct Form {
     pub form_id: i64,
@@ -12,6 +24,12 @@ pub struct Form {
     pub txt_url: String,
     pub access_no: String,
     pub web_url: String,
+    pub filed_date: Option<NaiveDate>,
+    pub original_submission: Option<NaiveDate>,
+    /// The form this amendment restates.
+    pub amends_form_id: Option<i64>,
+    /// The latest amendment restating this form, `None` while it is the effective version.
+    pub superseded_by: Option<i64>,
 }
 
 impl Form {
@@ -19,11 +37,15 @@ impl Form {
         Ok(Form {
             form_id: row.get::<i64, _>("form_id").unwrap(),
             issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
-            date_reported: NaiveDate::from_str(row.get::<&str, _>("date_reported").unwrap()).unwrap(),
+            date_reported: row.get::<NaiveDate, _>("date_reported").unwrap(),
             form_type: row.get::<&str, _>("form_type").unwrap().to_string(),
             txt_url: row.get::<&str, _>("txt_url").unwrap().to_string(),
             access_no: row.get::<&str, _>("access_no").unwrap().to_string(),
             web_url: row.get::<&str, _>("web_url").unwrap().to_string(),
+            filed_date: row.get::<NaiveDate, _>("filed_date"),
+            original_submission: row.get::<NaiveDate, _>("original_submission"),
+            amends_form_id: row.get::<i64, _>("amends_form_id"),
+            superseded_by: row.get::<i64, _>("superseded_by"),
         })
     }
 }
@@ -43,8 +65,8 @@ impl Individual {
             individual_id: row.get::<i32, _>("individual_id").unwrap(),
             cik: row.get::<&str, _>("cik").unwrap().to_string(),
             full_name: row.get::<&str, _>("full_name").unwrap().to_string(),
-            first_name: Option::from(row.get::<&str, _>("first_name").unwrap().to_string()),
-            last_name: Option::from(row.get::<&str, _>("last_name").unwrap().to_string()),
+            first_name: row.get::<&str, _>("first_name").map(|s| s.to_string()),
+            last_name: row.get::<&str, _>("last_name").map(|s| s.to_string()),
         })
     }
 }
@@ -71,48 +93,488 @@ impl Issuer {
 #[derive(Debug)]
 pub struct NonDerivTransaction {
     pub transaction_id: i64,
-    pub date_reported: NaiveDate,
+    pub date_reported: Option<NaiveDate>,
     pub form_id: i64,
     pub issuer_id: i32,
     pub individual_id: i32,
     pub action_code: Option<String>,
     pub ownership_code: Option<String>,
     pub transaction_code: Option<String>,
-    pub shares_balance: BigDecimal,
-    pub shares_traded: BigDecimal,
-    pub avg_price: BigDecimal,
-    pub amount: BigDecimal,
+    pub shares_balance: Option<BigDecimal>,
+    pub shares_traded: Option<BigDecimal>,
+    pub avg_price: Option<BigDecimal>,
+    pub amount: Option<BigDecimal>,
     pub relationships: Vec<i32>,
+    pub officer_title: Option<String>,
+    pub other_text: Option<String>,
+    pub roles: Vec<i32>,
+    /// Form of the amendment that restated this row.
+    pub superseded_by: Option<i64>,
 }
 
 impl NonDerivTransaction {
     pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
-        let shares_balance = BigDecimal::from_str(&row.get::<f64, _>("shares_balance").unwrap().to_string())?;
-        let shares_traded = BigDecimal::from_str(&row.get::<f64, _>("shares_traded").unwrap().to_string())?;
-        let avg_price = BigDecimal::from_str(&row.get::<f64, _>("avg_price").unwrap().to_string())?;
-        let amount = BigDecimal::from_str(&row.get::<f64, _>("amount").unwrap().to_string())?;
-        let relationships_str: String = row.get::<&str, _>("relationships").unwrap().to_string();
-
-        let relationships: Vec<i32> = relationships_str
-            .split(',')
-            .filter_map(|s| s.trim().parse::<i32>().ok())
-            .collect();
+        let shares_balance = row.get::<BigDecimal, _>("shares_balance");
+        let shares_traded = row.get::<BigDecimal, _>("shares_traded");
+        let avg_price = row.get::<BigDecimal, _>("avg_price");
+        let amount = row.get::<BigDecimal, _>("amount");
+        let relationships = parse_ids(row.get::<&str, _>("relationships").unwrap());
+        let roles = parse_ids(row.get::<&str, _>("roles").unwrap_or_default());
 
         Ok(NonDerivTransaction {
             transaction_id: row.get::<i64, _>("transaction_id").unwrap(),
-            date_reported: NaiveDate::from_str(row.get::<&str, _>("date_reported").unwrap()).unwrap(),
+            date_reported: row.get::<NaiveDate, _>("date_reported"),
             form_id: row.get::<i64, _>("form_id").unwrap(),
             issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
             individual_id: row.get::<i32, _>("individual_id").unwrap(),
-            action_code: Option::from(row.get::<&str, _>("action_code").unwrap().to_string()),
-            ownership_code: Option::from(row.get::<&str, _>("ownership_code").unwrap().to_string()),
-            transaction_code: Option::from(row.get::<&str, _>("transaction_code").unwrap().to_string()),
+            action_code: row.get::<&str, _>("action_code").map(|s| s.to_string()),
+            ownership_code: row.get::<&str, _>("ownership_code").map(|s| s.to_string()),
+            transaction_code: row.get::<&str, _>("transaction_code").map(|s| s.to_string()),
             shares_balance,
             shares_traded,
             avg_price,
             amount,
             relationships,
+            officer_title: row.get::<&str, _>("officer_title").map(|s| s.to_string()),
+            other_text: row.get::<&str, _>("other_text").map(|s| s.to_string()),
+            roles,
+            superseded_by: row.get::<i64, _>("superseded_by"),
+        })
+    }
+}
+
+
+#[derive(Debug)]
+pub struct DerivTransaction {
+    pub transaction_id: i64,
+    pub date_reported: Option<NaiveDate>,
+    pub form_id: i64,
+    pub issuer_id: i32,
+    pub individual_id: i32,
+    pub security_title: String,
+    pub conversion_price: Option<BigDecimal>,
+    pub exercise_date: Option<NaiveDate>,
+    pub expiration_date: Option<NaiveDate>,
+    pub underlying_title: String,
+    pub underlying_shares: Option<BigDecimal>,
+    pub action_code: Option<String>,
+    pub ownership_code: Option<String>,
+    pub transaction_code: Option<String>,
+    pub shares_balance: Option<BigDecimal>,
+    pub shares_traded: Option<BigDecimal>,
+    pub avg_price: Option<BigDecimal>,
+    pub amount: Option<BigDecimal>,
+    pub relationships: Vec<i32>,
+    pub superseded_by: Option<i64>,
+}
+
+impl DerivTransaction {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        let conversion_price = row.get::<BigDecimal, _>("conversion_price");
+        let underlying_shares = row.get::<BigDecimal, _>("underlying_shares");
+        let shares_balance = row.get::<BigDecimal, _>("shares_balance");
+        let shares_traded = row.get::<BigDecimal, _>("shares_traded");
+        let avg_price = row.get::<BigDecimal, _>("avg_price");
+        let amount = row.get::<BigDecimal, _>("amount");
+        let relationships = parse_ids(row.get::<&str, _>("relationships").unwrap());
+
+        Ok(DerivTransaction {
+            transaction_id: row.get::<i64, _>("transaction_id").unwrap(),
+            date_reported: row.get::<NaiveDate, _>("date_reported"),
+            form_id: row.get::<i64, _>("form_id").unwrap(),
+            issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
+            individual_id: row.get::<i32, _>("individual_id").unwrap(),
+            security_title: row.get::<&str, _>("security_title").unwrap().to_string(),
+            conversion_price,
+            exercise_date: row.get::<NaiveDate, _>("exercise_date"),
+            expiration_date: row.get::<NaiveDate, _>("expiration_date"),
+            underlying_title: row.get::<&str, _>("underlying_title").unwrap().to_string(),
+            underlying_shares,
+            action_code: row.get::<&str, _>("action_code").map(|s| s.to_string()),
+            ownership_code: row.get::<&str, _>("ownership_code").map(|s| s.to_string()),
+            transaction_code: row.get::<&str, _>("transaction_code").map(|s| s.to_string()),
+            shares_balance,
+            shares_traded,
+            avg_price,
+            amount,
+            relationships,
+            superseded_by: row.get::<i64, _>("superseded_by"),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Holding {
+    pub holding_id: i64,
+    pub date_reported: NaiveDate,
+    pub form_id: i64,
+    pub issuer_id: i32,
+    pub individual_id: i32,
+    pub security_title: String,
+    pub shares_owned: Option<BigDecimal>,
+    pub ownership_code: Option<String>,
+    pub ownership_nature: Option<String>,
+    pub derivative: bool,
+    pub conversion_price: Option<BigDecimal>,
+    pub exercise_date: Option<NaiveDate>,
+    pub expiration_date: Option<NaiveDate>,
+    pub underlying_title: String,
+    pub underlying_shares: Option<BigDecimal>,
+    pub superseded_by: Option<i64>,
+}
+
+impl Holding {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        let shares_owned = row.get::<BigDecimal, _>("shares_owned");
+        let conversion_price = row.get::<BigDecimal, _>("conversion_price");
+        let underlying_shares = row.get::<BigDecimal, _>("underlying_shares");
+
+        Ok(Holding {
+            holding_id: row.get::<i64, _>("holding_id").unwrap(),
+            date_reported: row.get::<NaiveDate, _>("date_reported").unwrap(),
+            form_id: row.get::<i64, _>("form_id").unwrap(),
+            issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
+            individual_id: row.get::<i32, _>("individual_id").unwrap(),
+            security_title: row.get::<&str, _>("security_title").unwrap().to_string(),
+            shares_owned,
+            ownership_code: row.get::<&str, _>("ownership_code").map(|s| s.to_string()),
+            ownership_nature: row.get::<&str, _>("ownership_nature").map(|s| s.to_string()),
+            derivative: row.get::<bool, _>("derivative").unwrap_or_default(),
+            conversion_price,
+            exercise_date: row.get::<NaiveDate, _>("exercise_date"),
+            expiration_date: row.get::<NaiveDate, _>("expiration_date"),
+            underlying_title: row.get::<&str, _>("underlying_title").unwrap_or_default().to_string(),
+            underlying_shares,
+            superseded_by: row.get::<i64, _>("superseded_by"),
         })
     }
 }
 
+#[derive(Debug)]
+pub struct Footnote {
+    pub form_id: i64,
+    pub footnote_key: String,
+    pub text: String,
+}
+
+impl Footnote {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Footnote {
+            form_id: row.get::<i64, _>("form_id").unwrap(),
+            footnote_key: row.get::<&str, _>("footnote_key").unwrap().to_string(),
+            text: row.get::<&str, _>("text").unwrap().to_string(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct FormDocument {
+    pub form_id: i64,
+    pub name: String,
+    pub seq: Option<i32>,
+    pub doc_type: String,
+    pub description: String,
+    pub size: Option<i64>,
+}
+
+impl FormDocument {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(FormDocument {
+            form_id: row.get::<i64, _>("form_id").unwrap(),
+            name: row.get::<&str, _>("name").unwrap().to_string(),
+            seq: row.get::<i32, _>("seq"),
+            doc_type: row.get::<&str, _>("doc_type").unwrap().to_string(),
+            description: row.get::<&str, _>("description").unwrap().to_string(),
+            size: row.get::<i64, _>("size"),
+        })
+    }
+}
+
+/// Where mining a day got to. A day is `IN_PROGRESS` from the moment its index is fetched
+/// until its filings and checkpoint are committed together.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointStatus {
+    IN_PROGRESS,
+    COMMITTED,
+}
+
+impl CheckpointStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckpointStatus::IN_PROGRESS => "in_progress",
+            CheckpointStatus::COMMITTED => "committed",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<CheckpointStatus> {
+        match text {
+            "in_progress" => Some(CheckpointStatus::IN_PROGRESS),
+            "committed" => Some(CheckpointStatus::COMMITTED),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub mine_date: NaiveDate,
+    pub entries: i32,
+    pub transactions: i32,
+    pub status: CheckpointStatus,
+}
+
+impl Checkpoint {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        let status = row.get::<&str, _>("status").unwrap();
+
+        Ok(Checkpoint {
+            mine_date: row.get::<NaiveDate, _>("mine_date").unwrap(),
+            entries: row.get::<i32, _>("entries").unwrap(),
+            transactions: row.get::<i32, _>("transactions").unwrap(),
+            status: CheckpointStatus::parse(status).ok_or(format!("Unknown checkpoint status {status}"))?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ProposedSale {
+    pub proposed_sale_id: i64,
+    pub access_no: String,
+    pub form_type: String,
+    pub form_url: String,
+    pub filed_date: Option<NaiveDate>,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    pub seller_cik: String,
+    pub seller_name: String,
+    pub relationship: String,
+    pub security_class: String,
+    pub broker: String,
+    pub shares: Option<BigDecimal>,
+    pub market_value: Option<BigDecimal>,
+    pub approx_sale_date: Option<NaiveDate>,
+    pub plan_adoption_date: Option<NaiveDate>,
+}
+
+impl ProposedSale {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(ProposedSale {
+            proposed_sale_id: row.get::<i64, _>("proposed_sale_id").unwrap(),
+            access_no: row.get::<&str, _>("access_no").unwrap().to_string(),
+            form_type: row.get::<&str, _>("form_type").unwrap().to_string(),
+            form_url: row.get::<&str, _>("form_url").unwrap().to_string(),
+            filed_date: row.get::<NaiveDate, _>("filed_date"),
+            issuer_cik: row.get::<&str, _>("issuer_cik").unwrap().to_string(),
+            issuer_name: row.get::<&str, _>("issuer_name").unwrap().to_string(),
+            seller_cik: row.get::<&str, _>("seller_cik").unwrap().to_string(),
+            seller_name: row.get::<&str, _>("seller_name").unwrap().to_string(),
+            relationship: row.get::<&str, _>("relationship").unwrap().to_string(),
+            security_class: row.get::<&str, _>("security_class").unwrap().to_string(),
+            broker: row.get::<&str, _>("broker").unwrap().to_string(),
+            shares: row.get::<BigDecimal, _>("shares"),
+            market_value: row.get::<BigDecimal, _>("market_value"),
+            approx_sale_date: row.get::<NaiveDate, _>("approx_sale_date"),
+            plan_adoption_date: row.get::<NaiveDate, _>("plan_adoption_date"),
+        })
+    }
+}
+
+/// A Form 4 sale that followed a Form 144 notice of the same seller and issuer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaleMatch {
+    pub proposed_sale_id: i64,
+    pub transaction_id: i64,
+    pub form_id: i64,
+    pub date_reported: Option<NaiveDate>,
+    pub shares_traded: Option<BigDecimal>,
+    pub avg_price: Option<BigDecimal>,
+}
+
+impl SaleMatch {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(SaleMatch {
+            proposed_sale_id: row.get::<i64, _>("proposed_sale_id").unwrap(),
+            transaction_id: row.get::<i64, _>("transaction_id").unwrap(),
+            form_id: row.get::<i64, _>("form_id").unwrap(),
+            date_reported: row.get::<NaiveDate, _>("date_reported"),
+            shares_traded: row.get::<BigDecimal, _>("shares_traded"),
+            avg_price: row.get::<BigDecimal, _>("avg_price"),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BeneficialOwnership {
+    pub ownership_id: i64,
+    pub issuer_id: i32,
+    pub access_no: String,
+    pub form_type: String,
+    pub form_url: String,
+    pub filed_date: Option<NaiveDate>,
+    pub event_date: Option<NaiveDate>,
+    pub issuer_cusip: String,
+    pub filer_cik: String,
+    pub filer_name: String,
+    pub amendment_no: Option<i32>,
+    pub percent_of_class: Option<BigDecimal>,
+    pub aggregate_shares: Option<BigDecimal>,
+    pub sole_voting: Option<BigDecimal>,
+    pub shared_voting: Option<BigDecimal>,
+    pub sole_dispositive: Option<BigDecimal>,
+    pub shared_dispositive: Option<BigDecimal>,
+}
+
+impl BeneficialOwnership {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(BeneficialOwnership {
+            ownership_id: row.get::<i64, _>("ownership_id").unwrap(),
+            issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
+            access_no: row.get::<&str, _>("access_no").unwrap().to_string(),
+            form_type: row.get::<&str, _>("form_type").unwrap().to_string(),
+            form_url: row.get::<&str, _>("form_url").unwrap().to_string(),
+            filed_date: row.get::<NaiveDate, _>("filed_date"),
+            event_date: row.get::<NaiveDate, _>("event_date"),
+            issuer_cusip: row.get::<&str, _>("issuer_cusip").unwrap().to_string(),
+            filer_cik: row.get::<&str, _>("filer_cik").unwrap().to_string(),
+            filer_name: row.get::<&str, _>("filer_name").unwrap().to_string(),
+            amendment_no: row.get::<i32, _>("amendment_no"),
+            percent_of_class: row.get::<BigDecimal, _>("percent_of_class"),
+            aggregate_shares: row.get::<BigDecimal, _>("aggregate_shares"),
+            sole_voting: row.get::<BigDecimal, _>("sole_voting"),
+            shared_voting: row.get::<BigDecimal, _>("shared_voting"),
+            sole_dispositive: row.get::<BigDecimal, _>("sole_dispositive"),
+            shared_dispositive: row.get::<BigDecimal, _>("shared_dispositive"),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct InstitutionalHolding {
+    pub holding_id: i64,
+    /// Set once the cusip is resolved to a known issuer, see [`super::FilingStore::resolve_cusips`].
+    pub issuer_id: Option<i32>,
+    pub access_no: String,
+    pub form_type: String,
+    pub amendment_type: Option<String>,
+    pub form_url: String,
+    pub filed_date: Option<NaiveDate>,
+    pub period_of_report: NaiveDate,
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub issuer_name: String,
+    pub title_of_class: String,
+    pub cusip: String,
+    pub market_value: Option<BigDecimal>,
+    pub shares: Option<BigDecimal>,
+    pub share_type: String,
+    pub put_call: Option<String>,
+    pub investment_discretion: String,
+    pub voting_sole: Option<BigDecimal>,
+    pub voting_shared: Option<BigDecimal>,
+    pub voting_none: Option<BigDecimal>,
+}
+
+impl InstitutionalHolding {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(InstitutionalHolding {
+            holding_id: row.get::<i64, _>("holding_id").unwrap(),
+            issuer_id: row.get::<i32, _>("issuer_id"),
+            access_no: row.get::<&str, _>("access_no").unwrap().to_string(),
+            form_type: row.get::<&str, _>("form_type").unwrap().to_string(),
+            amendment_type: row.get::<&str, _>("amendment_type").map(str::to_string),
+            form_url: row.get::<&str, _>("form_url").unwrap().to_string(),
+            filed_date: row.get::<NaiveDate, _>("filed_date"),
+            period_of_report: row.get::<NaiveDate, _>("period_of_report").unwrap(),
+            manager_cik: row.get::<&str, _>("manager_cik").unwrap().to_string(),
+            manager_name: row.get::<&str, _>("manager_name").unwrap().to_string(),
+            issuer_name: row.get::<&str, _>("issuer_name").unwrap().to_string(),
+            title_of_class: row.get::<&str, _>("title_of_class").unwrap().to_string(),
+            cusip: row.get::<&str, _>("cusip").unwrap().to_string(),
+            market_value: row.get::<BigDecimal, _>("market_value"),
+            shares: row.get::<BigDecimal, _>("shares"),
+            share_type: row.get::<&str, _>("share_type").unwrap().to_string(),
+            put_call: row.get::<&str, _>("put_call").map(str::to_string),
+            investment_discretion: row.get::<&str, _>("investment_discretion").unwrap().to_string(),
+            voting_sole: row.get::<BigDecimal, _>("voting_sole"),
+            voting_shared: row.get::<BigDecimal, _>("voting_shared"),
+            voting_none: row.get::<BigDecimal, _>("voting_none"),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct XbrlFact {
+    pub fact_id: i64,
+    pub issuer_id: i32,
+    pub access_no: String,
+    pub form_type: String,
+    pub fiscal_year: Option<i32>,
+    pub fiscal_period: String,
+    pub taxonomy: String,
+    pub concept: String,
+    pub label: String,
+    pub report: String,
+    pub report_group: String,
+    pub value: BigDecimal,
+    pub unit: String,
+    pub decimals: Option<i32>,
+    pub period_start: Option<NaiveDate>,
+    pub period_end: NaiveDate,
+    pub dimensions: String,
+}
+
+impl XbrlFact {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(XbrlFact {
+            fact_id: row.get::<i64, _>("fact_id").unwrap(),
+            issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
+            access_no: row.get::<&str, _>("access_no").unwrap().to_string(),
+            form_type: row.get::<&str, _>("form_type").unwrap().to_string(),
+            fiscal_year: row.get::<i32, _>("fiscal_year"),
+            fiscal_period: row.get::<&str, _>("fiscal_period").unwrap().to_string(),
+            taxonomy: row.get::<&str, _>("taxonomy").unwrap().to_string(),
+            concept: row.get::<&str, _>("concept").unwrap().to_string(),
+            label: row.get::<&str, _>("label").unwrap().to_string(),
+            report: row.get::<&str, _>("report").unwrap().to_string(),
+            report_group: row.get::<&str, _>("report_group").unwrap().to_string(),
+            value: row.get::<BigDecimal, _>("fact_value").unwrap(),
+            unit: row.get::<&str, _>("unit").unwrap().to_string(),
+            decimals: row.get::<i32, _>("decimals"),
+            period_start: row.get::<NaiveDate, _>("period_start"),
+            period_end: row.get::<NaiveDate, _>("period_end").unwrap(),
+            dimensions: row.get::<&str, _>("dimensions").unwrap().to_string(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct MaterialEvent {
+    pub event_id: i64,
+    pub issuer_id: i32,
+    pub access_no: String,
+    pub form_type: String,
+    pub form_url: String,
+    pub filed_date: Option<NaiveDate>,
+    pub event_date: Option<NaiveDate>,
+    pub item: String,
+    pub item_title: String,
+    pub section: String,
+}
+
+impl MaterialEvent {
+    pub fn from_row(row: &Row) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(MaterialEvent {
+            event_id: row.get::<i64, _>("event_id").unwrap(),
+            issuer_id: row.get::<i32, _>("issuer_id").unwrap(),
+            access_no: row.get::<&str, _>("access_no").unwrap().to_string(),
+            form_type: row.get::<&str, _>("form_type").unwrap().to_string(),
+            form_url: row.get::<&str, _>("form_url").unwrap().to_string(),
+            filed_date: row.get::<NaiveDate, _>("filed_date"),
+            event_date: row.get::<NaiveDate, _>("event_date"),
+            item: row.get::<&str, _>("item").unwrap().to_string(),
+            item_title: row.get::<&str, _>("item_title").unwrap().to_string(),
+            section: row.get::<&str, _>("section").unwrap().to_string(),
+        })
+    }
+}
@@ -0,0 +1,1209 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::database::insert_models::{NewDerivTransaction, NewForm, NewFormOwner, NewHolding, NewIndividual, NewIssuer, NewNonDerivTransaction};
+use crate::database::migrations;
+use crate::database::query_models::{self, parse_decimal, parse_ids, Checkpoint, CheckpointStatus, DerivTransaction, Footnote, Form, FormDocument, NonDerivTransaction, SaleMatch};
+use crate::database::{join_ids, FilingStore, SaveStats, SideDocuments, RESOLVE_CUSIPS};
+use crate::secgov::models::{BeneficialOwnership, DerivativeTransaction, Filing, FilingTransaction, Holding, InstitutionalHolding, MaterialEvent, ProposedSale, ReportingOwner, XbrlFact};
+
+/// Decimals are stored as text, SQLite would otherwise round them through a REAL.
+fn text(value: &Option<BigDecimal>) -> Option<String> {
+    value.as_ref().map(|v| v.to_string())
+}
+
+/// Links a row to the footnotes annotating each of its fields, `table` being the footnote table
+/// of its kind and `id_column` the key of the row in it.
+fn insert_field_footnotes(conn: &Connection, table: &str, id_column: &str, id: i64, footnotes: &HashMap<String, Vec<String>>) -> rusqlite::Result<()> {
+    for (field, keys) in footnotes {
+        for key in keys {
+            conn.execute(
+                &format!("INSERT INTO {table} ({id_column}, field, footnote_key) VALUES (?1, ?2, ?3) ON CONFLICT DO NOTHING"),
+                params![id, field, key],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn form_from_row(row: &Row) -> rusqlite::Result<Form> {
+    Ok(Form {
+        form_id: row.get("form_id")?,
+        issuer_id: row.get("issuer_id")?,
+        date_reported: row.get("date_reported")?,
+        form_type: row.get("form_type")?,
+        txt_url: row.get("txt_url")?,
+        access_no: row.get("access_no")?,
+        web_url: row.get("web_url")?,
+        filed_date: row.get("filed_date")?,
+        original_submission: row.get("original_submission")?,
+        amends_form_id: row.get("amends_form_id")?,
+        superseded_by: row.get("superseded_by")?,
+    })
+}
+
+fn nonderiv_from_row(row: &Row) -> rusqlite::Result<NonDerivTransaction> {
+    Ok(NonDerivTransaction {
+        transaction_id: row.get("transaction_id")?,
+        date_reported: row.get("date_reported")?,
+        form_id: row.get("form_id")?,
+        issuer_id: row.get("issuer_id")?,
+        individual_id: row.get("individual_id")?,
+        action_code: row.get("action_code")?,
+        ownership_code: row.get("ownership_code")?,
+        transaction_code: row.get("transaction_code")?,
+        shares_balance: parse_decimal(row.get("shares_balance")?),
+        shares_traded: parse_decimal(row.get("shares_traded")?),
+        avg_price: parse_decimal(row.get("avg_price")?),
+        amount: parse_decimal(row.get("amount")?),
+        relationships: parse_ids(&row.get::<_, String>("relationships")?),
+        officer_title: row.get("officer_title")?,
+        other_text: row.get("other_text")?,
+        roles: parse_ids(&row.get::<_, String>("roles")?),
+        superseded_by: row.get("superseded_by")?,
+    })
+}
+
+fn deriv_from_row(row: &Row) -> rusqlite::Result<DerivTransaction> {
+    Ok(DerivTransaction {
+        transaction_id: row.get("transaction_id")?,
+        date_reported: row.get("date_reported")?,
+        form_id: row.get("form_id")?,
+        issuer_id: row.get("issuer_id")?,
+        individual_id: row.get("individual_id")?,
+        security_title: row.get("security_title")?,
+        conversion_price: parse_decimal(row.get("conversion_price")?),
+        exercise_date: row.get("exercise_date")?,
+        expiration_date: row.get("expiration_date")?,
+        underlying_title: row.get("underlying_title")?,
+        underlying_shares: parse_decimal(row.get("underlying_shares")?),
+        action_code: row.get("action_code")?,
+        ownership_code: row.get("ownership_code")?,
+        transaction_code: row.get("transaction_code")?,
+        shares_balance: parse_decimal(row.get("shares_balance")?),
+        shares_traded: parse_decimal(row.get("shares_traded")?),
+        avg_price: parse_decimal(row.get("avg_price")?),
+        amount: parse_decimal(row.get("amount")?),
+        relationships: parse_ids(&row.get::<_, String>("relationships")?),
+        superseded_by: row.get("superseded_by")?,
+    })
+}
+
+fn holding_from_row(row: &Row) -> rusqlite::Result<query_models::Holding> {
+    Ok(query_models::Holding {
+        holding_id: row.get("holding_id")?,
+        date_reported: row.get("date_reported")?,
+        form_id: row.get("form_id")?,
+        issuer_id: row.get("issuer_id")?,
+        individual_id: row.get("individual_id")?,
+        security_title: row.get("security_title")?,
+        shares_owned: parse_decimal(row.get("shares_owned")?),
+        ownership_code: row.get("ownership_code")?,
+        ownership_nature: row.get("ownership_nature")?,
+        derivative: row.get("derivative")?,
+        conversion_price: parse_decimal(row.get("conversion_price")?),
+        exercise_date: row.get("exercise_date")?,
+        expiration_date: row.get("expiration_date")?,
+        underlying_title: row.get("underlying_title")?,
+        underlying_shares: parse_decimal(row.get("underlying_shares")?),
+        superseded_by: row.get("superseded_by")?,
+    })
+}
+
+fn proposed_sale_from_row(row: &Row) -> rusqlite::Result<query_models::ProposedSale> {
+    Ok(query_models::ProposedSale {
+        proposed_sale_id: row.get("proposed_sale_id")?,
+        access_no: row.get("access_no")?,
+        form_type: row.get("form_type")?,
+        form_url: row.get("form_url")?,
+        filed_date: row.get("filed_date")?,
+        issuer_cik: row.get("issuer_cik")?,
+        issuer_name: row.get("issuer_name")?,
+        seller_cik: row.get("seller_cik")?,
+        seller_name: row.get("seller_name")?,
+        relationship: row.get("relationship")?,
+        security_class: row.get("security_class")?,
+        broker: row.get("broker")?,
+        shares: parse_decimal(row.get("shares")?),
+        market_value: parse_decimal(row.get("market_value")?),
+        approx_sale_date: row.get("approx_sale_date")?,
+        plan_adoption_date: row.get("plan_adoption_date")?,
+    })
+}
+
+fn ownership_from_row(row: &Row) -> rusqlite::Result<query_models::BeneficialOwnership> {
+    Ok(query_models::BeneficialOwnership {
+        ownership_id: row.get("ownership_id")?,
+        issuer_id: row.get("issuer_id")?,
+        access_no: row.get("access_no")?,
+        form_type: row.get("form_type")?,
+        form_url: row.get("form_url")?,
+        filed_date: row.get("filed_date")?,
+        event_date: row.get("event_date")?,
+        issuer_cusip: row.get("issuer_cusip")?,
+        filer_cik: row.get("filer_cik")?,
+        filer_name: row.get("filer_name")?,
+        amendment_no: row.get("amendment_no")?,
+        percent_of_class: parse_decimal(row.get("percent_of_class")?),
+        aggregate_shares: parse_decimal(row.get("aggregate_shares")?),
+        sole_voting: parse_decimal(row.get("sole_voting")?),
+        shared_voting: parse_decimal(row.get("shared_voting")?),
+        sole_dispositive: parse_decimal(row.get("sole_dispositive")?),
+        shared_dispositive: parse_decimal(row.get("shared_dispositive")?),
+    })
+}
+
+fn institutional_from_row(row: &Row) -> rusqlite::Result<query_models::InstitutionalHolding> {
+    Ok(query_models::InstitutionalHolding {
+        holding_id: row.get("holding_id")?,
+        issuer_id: row.get("issuer_id")?,
+        access_no: row.get("access_no")?,
+        form_type: row.get("form_type")?,
+        amendment_type: row.get("amendment_type")?,
+        form_url: row.get("form_url")?,
+        filed_date: row.get("filed_date")?,
+        period_of_report: row.get("period_of_report")?,
+        manager_cik: row.get("manager_cik")?,
+        manager_name: row.get("manager_name")?,
+        issuer_name: row.get("issuer_name")?,
+        title_of_class: row.get("title_of_class")?,
+        cusip: row.get("cusip")?,
+        market_value: parse_decimal(row.get("market_value")?),
+        shares: parse_decimal(row.get("shares")?),
+        share_type: row.get("share_type")?,
+        put_call: row.get("put_call")?,
+        investment_discretion: row.get("investment_discretion")?,
+        voting_sole: parse_decimal(row.get("voting_sole")?),
+        voting_shared: parse_decimal(row.get("voting_shared")?),
+        voting_none: parse_decimal(row.get("voting_none")?),
+    })
+}
+
+fn fact_from_row(row: &Row) -> rusqlite::Result<query_models::XbrlFact> {
+    Ok(query_models::XbrlFact {
+        fact_id: row.get("fact_id")?,
+        issuer_id: row.get("issuer_id")?,
+        access_no: row.get("access_no")?,
+        form_type: row.get("form_type")?,
+        fiscal_year: row.get("fiscal_year")?,
+        fiscal_period: row.get("fiscal_period")?,
+        taxonomy: row.get("taxonomy")?,
+        concept: row.get("concept")?,
+        label: row.get("label")?,
+        report: row.get("report")?,
+        report_group: row.get("report_group")?,
+        value: parse_decimal(row.get("fact_value")?).unwrap_or_default(),
+        unit: row.get("unit")?,
+        decimals: row.get("decimals")?,
+        period_start: row.get("period_start")?,
+        period_end: row.get("period_end")?,
+        dimensions: row.get("dimensions")?,
+    })
+}
+
+fn event_from_row(row: &Row) -> rusqlite::Result<query_models::MaterialEvent> {
+    Ok(query_models::MaterialEvent {
+        event_id: row.get("event_id")?,
+        issuer_id: row.get("issuer_id")?,
+        access_no: row.get("access_no")?,
+        form_type: row.get("form_type")?,
+        form_url: row.get("form_url")?,
+        filed_date: row.get("filed_date")?,
+        event_date: row.get("event_date")?,
+        item: row.get("item")?,
+        item_title: row.get("item_title")?,
+        section: row.get("section")?,
+    })
+}
+
+fn checkpoint_from_row(row: &Row) -> rusqlite::Result<Checkpoint> {
+    let status: String = row.get("status")?;
+
+    Ok(Checkpoint {
+        mine_date: row.get("mine_date")?,
+        entries: row.get("entries")?,
+        transactions: row.get("transactions")?,
+        status: CheckpointStatus::parse(&status).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(3, Type::Text, format!("Unknown checkpoint status {status}").into())
+        })?,
+    })
+}
+
+/// [`FilingStore`] in an embedded SQLite file, so the whole pipeline runs without a server.
+///
+/// rusqlite is synchronous, every call holds the connection only for the statements it runs
+/// and never across an await.
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Opens (or creates) the database file, `:memory:` gives a throwaway in-memory database.
+    pub fn open(path: &str) -> Result<SqliteStore, Box<dyn Error>> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+
+        Ok(SqliteStore { conn: Mutex::new(conn) })
+    }
+}
+
+#[async_trait(?Send)]
+impl FilingStore for SqliteStore {
+    async fn migrate(&self) -> Result<Vec<i32>, Box<dyn Error>> {
+        let mut conn = self.conn.lock().unwrap();
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (\
+                 version INTEGER PRIMARY KEY, \
+                 name TEXT NOT NULL, \
+                 applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP\
+             )",
+        )?;
+
+        let applied = conn
+            .prepare("SELECT version FROM schema_migrations")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<i32>, _>>()?;
+
+        let mut newly_applied = Vec::new();
+        for migration in migrations::pending(migrations::SQLITE, &applied) {
+            let tx = conn.transaction()?;
+
+            tx.execute_batch(migration.sql)
+                .and_then(|_| tx.execute("INSERT INTO schema_migrations (version, name) VALUES (?1, ?2)", params![migration.version, migration.name]))
+                .map_err(|e| format!("Migration {} ({}) failed: {}", migration.version, migration.name, e))?;
+
+            tx.commit()?;
+            println!("Applied migration {} ({})", migration.version, migration.name);
+            newly_applied.push(migration.version);
+        }
+
+        Ok(newly_applied)
+    }
+
+    async fn create_issuer(&self, filing: &Filing) -> Result<i32, Box<dyn Error>> {
+        let new_issuer = NewIssuer::map(filing);
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO issuer (name, symbol, cik) VALUES (?1, ?2, ?3) \
+             ON CONFLICT (cik) DO UPDATE SET name = COALESCE(NULLIF(issuer.name, ''), excluded.name), \
+             symbol = COALESCE(NULLIF(issuer.symbol, ''), excluded.symbol) RETURNING issuer_id",
+            params![new_issuer.issuer_name, new_issuer.issuer_symbol, new_issuer.cik],
+            |row| row.get(0),
+        )?)
+    }
+
+    async fn create_individual(&self, owner: &ReportingOwner) -> Result<i32, Box<dyn Error>> {
+        let new_ind = NewIndividual::map(owner);
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO individual (full_name, cik, first_name, last_name) VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT (cik) DO UPDATE SET cik = excluded.cik RETURNING individual_id",
+            params![new_ind.full_name, new_ind.cik, new_ind.first_name, new_ind.last_name],
+            |row| row.get(0),
+        )?)
+    }
+
+    async fn create_form(&self, filing: &Filing, issuer_id: i32) -> Result<i64, Box<dyn Error>> {
+        let new_form = NewForm::map(filing, issuer_id);
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO form (issuer_id, date_reported, form_type, txt_url, web_url, access_no, filed_date, original_submission) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+             ON CONFLICT (access_no) DO UPDATE SET access_no = excluded.access_no RETURNING form_id",
+            params![new_form.issuer_id, new_form.date_reported, new_form.form_type, new_form.txt_url, new_form.web_url, new_form.access_no, new_form.filed_date, new_form.original_submission],
+            |row| row.get(0),
+        )?)
+    }
+
+    async fn link_form_owner(&self, owner: &ReportingOwner, form_id: i64, ind_id: i32) -> Result<(), Box<dyn Error>> {
+        let new_link = NewFormOwner::map(owner, form_id, ind_id);
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO form_owner (form_id, individual_id, relationships, officer_title, other_text, roles, street1, street2, city, state, zip_code) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) ON CONFLICT DO NOTHING",
+            params![new_link.form_id, new_link.individual_id, join_ids(&new_link.relationships), new_link.officer_title, new_link.other_text, join_ids(&new_link.roles), new_link.street1, new_link.street2, new_link.city, new_link.state, new_link.zip_code],
+        )?;
+
+        Ok(())
+    }
+
+    async fn insert_footnotes(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        for (key, text) in &filing.footnotes {
+            conn.execute(
+                "INSERT INTO footnote (form_id, footnote_key, text) VALUES (?1, ?2, ?3) ON CONFLICT DO NOTHING",
+                params![form_id, key, text],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    async fn insert_documents(&self, filing: &Filing, form_id: i64) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        for doc in &filing.documents {
+            conn.execute(
+                "INSERT INTO form_document (form_id, name, seq, doc_type, description, size) VALUES (?1, ?2, ?3, ?4, ?5, ?6) ON CONFLICT DO NOTHING",
+                params![form_id, doc.name, doc.seq, doc.doc_type, doc.description, doc.size],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    async fn insert_nonderiv(&self, trans: &FilingTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<NonDerivTransaction, Box<dyn Error>> {
+        let new_trans = NewNonDerivTransaction::map(trans, form_id, issuer_id, ind_id);
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO non_deriv_transaction (date_reported, form_id, issuer_id, individual_id, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships, officer_title, other_text, roles) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15) RETURNING *",
+            params![new_trans.date_reported, new_trans.form_id, new_trans.issuer_id, new_trans.individual_id, new_trans.action_code, new_trans.ownership_code, new_trans.transaction_code, text(&new_trans.shares_balance), text(&new_trans.shares_traded), text(&new_trans.avg_price), text(&new_trans.amount), join_ids(&new_trans.relationships), new_trans.officer_title, new_trans.other_text, join_ids(&new_trans.roles)],
+            nonderiv_from_row,
+        )?)
+    }
+
+    async fn insert_trans_footnotes(&self, trans: &FilingTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(insert_field_footnotes(&conn, "transaction_footnote", "transaction_id", transaction_id, &trans.footnotes)?)
+    }
+
+    async fn insert_deriv(&self, filing: &Filing, trans: &DerivativeTransaction, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<DerivTransaction, Box<dyn Error>> {
+        let new_trans = NewDerivTransaction::map(filing, trans, form_id, issuer_id, ind_id);
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO deriv_transaction (date_reported, form_id, issuer_id, individual_id, security_title, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares, action_code, ownership_code, transaction_code, shares_balance, shares_traded, avg_price, amount, relationships) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18) RETURNING *",
+            params![new_trans.date_reported, new_trans.form_id, new_trans.issuer_id, new_trans.individual_id, new_trans.security_title, text(&new_trans.conversion_price), new_trans.exercise_date, new_trans.expiration_date, new_trans.underlying_title, text(&new_trans.underlying_shares), new_trans.action_code, new_trans.ownership_code, new_trans.transaction_code, text(&new_trans.shares_balance), text(&new_trans.shares_traded), text(&new_trans.avg_price), text(&new_trans.amount), join_ids(&new_trans.relationships)],
+            deriv_from_row,
+        )?)
+    }
+
+    async fn insert_deriv_footnotes(&self, trans: &DerivativeTransaction, transaction_id: i64) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(insert_field_footnotes(&conn, "deriv_transaction_footnote", "transaction_id", transaction_id, &trans.footnotes)?)
+    }
+
+    async fn insert_holding(&self, filing: &Filing, holding: &Holding, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<query_models::Holding, Box<dyn Error>> {
+        let new_holding = NewHolding::map(filing, holding, form_id, issuer_id, ind_id);
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO holding (date_reported, form_id, issuer_id, individual_id, security_title, shares_owned, ownership_code, ownership_nature, derivative, conversion_price, exercise_date, expiration_date, underlying_title, underlying_shares) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14) RETURNING *",
+            params![new_holding.date_reported, new_holding.form_id, new_holding.issuer_id, new_holding.individual_id, new_holding.security_title, text(&new_holding.shares_owned), new_holding.ownership_code, new_holding.ownership_nature, new_holding.derivative,
+                text(&new_holding.conversion_price), new_holding.exercise_date, new_holding.expiration_date, new_holding.underlying_title, text(&new_holding.underlying_shares)],
+            holding_from_row,
+        )?)
+    }
+
+    async fn insert_holding_footnotes(&self, holding: &Holding, holding_id: i64) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(insert_field_footnotes(&conn, "holding_footnote", "holding_id", holding_id, &holding.footnotes)?)
+    }
+
+    async fn link_amendment(&self, filing: &Filing, form_id: i64, issuer_id: i32, ind_id: i32) -> Result<Option<i64>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let original: Option<i64> = conn.query_row(
+            "SELECT form_id FROM form WHERE issuer_id = ?1 AND form_type = ?2 AND form_id <> ?3 AND amends_form_id IS NULL \
+             AND EXISTS (SELECT 1 FROM form_owner o WHERE o.form_id = form.form_id AND o.individual_id = ?4) \
+             AND (filed_date = ?5 OR ((filed_date IS NULL OR ?5 IS NULL) AND date_reported = ?6)) \
+             ORDER BY form_id DESC LIMIT 1",
+            params![issuer_id, filing.base_form_type(), form_id, ind_id, filing.original_submission, filing.form_date],
+            |row| row.get(0),
+        ).optional()?;
+
+        let Some(original) = original else {
+            return Ok(None);
+        };
+
+        conn.execute("UPDATE form SET amends_form_id = ?1 WHERE form_id = ?2", params![original, form_id])?;
+        conn.execute(
+            "UPDATE form SET superseded_by = ?1 WHERE (form_id = ?2 OR amends_form_id = ?2) AND form_id <> ?1",
+            params![form_id, original],
+        )?;
+
+        for table in ["non_deriv_transaction", "deriv_transaction", "holding"] {
+            conn.execute(
+                &format!("UPDATE {table} SET superseded_by = ?1 WHERE form_id IN (SELECT form_id FROM form WHERE superseded_by = ?1)"),
+                params![form_id],
+            )?;
+        }
+
+        Ok(Some(original))
+    }
+
+    async fn get_form_version(&self, access_no: &str) -> Result<Option<Form>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row("SELECT * FROM form WHERE access_no = ?1", params![access_no], form_from_row).optional()?)
+    }
+
+    async fn get_form_by_id(&self, form_id: i64) -> Result<Option<Form>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row("SELECT * FROM form WHERE form_id = ?1", params![form_id], form_from_row).optional()?)
+    }
+
+    async fn get_nonderiv(&self, form_id: i64) -> Result<Vec<NonDerivTransaction>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM non_deriv_transaction WHERE form_id = ?1 AND superseded_by IS NULL ORDER BY transaction_id")?
+            .query_map(params![form_id], nonderiv_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_footnotes(&self, form_id: i64) -> Result<Vec<Footnote>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT form_id, footnote_key, text FROM footnote WHERE form_id = ?1")?
+            .query_map(params![form_id], |row| Ok(Footnote {
+                form_id: row.get("form_id")?,
+                footnote_key: row.get("footnote_key")?,
+                text: row.get("text")?,
+            }))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_documents(&self, form_id: i64) -> Result<Vec<FormDocument>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT form_id, name, seq, doc_type, description, size FROM form_document WHERE form_id = ?1 ORDER BY CASE WHEN seq IS NULL THEN 1 ELSE 0 END, seq, name")?
+            .query_map(params![form_id], |row| Ok(FormDocument {
+                form_id: row.get("form_id")?,
+                name: row.get("name")?,
+                seq: row.get("seq")?,
+                doc_type: row.get("doc_type")?,
+                description: row.get("description")?,
+                size: row.get("size")?,
+            }))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_baseline(&self, issuer_id: i32, ind_id: i32) -> Result<Vec<query_models::Holding>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare(
+                "SELECT * FROM holding WHERE superseded_by IS NULL AND form_id = ( \
+                     SELECT MAX(f.form_id) FROM form f JOIN form_owner o ON o.form_id = f.form_id \
+                     WHERE f.issuer_id = ?1 AND o.individual_id = ?2 AND f.form_type IN ('3', '3/A') AND f.superseded_by IS NULL) \
+                 ORDER BY holding_id",
+            )?
+            .query_map(params![issuer_id, ind_id], holding_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn insert_proposed_sale(&self, sale: &ProposedSale) -> Result<query_models::ProposedSale, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO proposed_sale (access_no, form_type, form_url, filed_date, issuer_cik, issuer_name, seller_cik, seller_name, relationship, \
+             security_class, broker, shares, market_value, approx_sale_date, plan_adoption_date) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15) RETURNING *",
+            params![sale.access_no, sale.form_type, sale.form_url, sale.filed_date, sale.issuer_cik, sale.issuer_name, sale.seller_cik, sale.seller_name,
+                sale.relationship, sale.security_class, sale.broker, text(&sale.shares), text(&sale.market_value), sale.approx_sale_date, sale.plan_adoption_date],
+            proposed_sale_from_row,
+        )?)
+    }
+
+    async fn get_proposed_sales(&self, access_no: &str) -> Result<Vec<query_models::ProposedSale>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM proposed_sale WHERE access_no = ?1 ORDER BY proposed_sale_id")?
+            .query_map(params![access_no], proposed_sale_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_sale_matches(&self, access_no: &str) -> Result<Vec<SaleMatch>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare(
+                "SELECT p.proposed_sale_id, t.transaction_id, t.form_id, t.date_reported, t.shares_traded, t.avg_price \
+                 FROM proposed_sale p \
+                 JOIN issuer s ON s.cik = p.issuer_cik \
+                 JOIN individual i ON i.cik = p.seller_cik \
+                 JOIN form_owner o ON o.individual_id = i.individual_id \
+                 JOIN non_deriv_transaction t ON t.form_id = o.form_id AND t.issuer_id = s.issuer_id \
+                 WHERE p.access_no = ?1 AND t.transaction_code = 'S' AND t.action_code = 'D' AND t.superseded_by IS NULL \
+                 AND t.date_reported >= COALESCE(p.filed_date, p.approx_sale_date) \
+                 AND t.date_reported <= date(COALESCE(p.approx_sale_date, p.filed_date), '+90 days') \
+                 ORDER BY p.proposed_sale_id, t.date_reported, t.transaction_id",
+            )?
+            .query_map(params![access_no], |row| Ok(SaleMatch {
+                proposed_sale_id: row.get("proposed_sale_id")?,
+                transaction_id: row.get("transaction_id")?,
+                form_id: row.get("form_id")?,
+                date_reported: row.get("date_reported")?,
+                shares_traded: parse_decimal(row.get("shares_traded")?),
+                avg_price: parse_decimal(row.get("avg_price")?),
+            }))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn insert_ownership_event(&self, event: &BeneficialOwnership, issuer_id: i32) -> Result<query_models::BeneficialOwnership, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO beneficial_ownership (issuer_id, access_no, form_type, form_url, filed_date, event_date, issuer_cusip, filer_cik, filer_name, \
+             amendment_no, percent_of_class, aggregate_shares, sole_voting, shared_voting, sole_dispositive, shared_dispositive) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16) RETURNING *",
+            params![issuer_id, event.access_no, event.form_type, event.form_url, event.filed_date, event.event_date, event.issuer_cusip, event.filer_cik,
+                event.filer_name, event.amendment_no, text(&event.percent_of_class), text(&event.aggregate_shares), text(&event.sole_voting),
+                text(&event.shared_voting), text(&event.sole_dispositive), text(&event.shared_dispositive)],
+            ownership_from_row,
+        )?)
+    }
+
+    async fn get_ownership_events(&self, access_no: &str) -> Result<Vec<query_models::BeneficialOwnership>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM beneficial_ownership WHERE access_no = ?1 ORDER BY ownership_id")?
+            .query_map(params![access_no], ownership_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn insert_institutional_holding(&self, holding: &InstitutionalHolding) -> Result<query_models::InstitutionalHolding, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO institutional_holding (access_no, form_type, amendment_type, form_url, filed_date, period_of_report, manager_cik, manager_name, issuer_name, title_of_class, cusip, \
+             market_value, shares, share_type, put_call, investment_discretion, voting_sole, voting_shared, voting_none) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19) RETURNING *",
+            params![holding.access_no, holding.form_type, holding.amendment_type, holding.form_url, holding.filed_date, holding.period_of_report,
+                holding.manager_cik, holding.manager_name, holding.issuer_name, holding.title_of_class, holding.cusip, text(&holding.market_value),
+                text(&holding.shares), holding.share_type, holding.put_call, holding.investment_discretion, text(&holding.voting_sole),
+                text(&holding.voting_shared), text(&holding.voting_none)],
+            institutional_from_row,
+        )?)
+    }
+
+    async fn get_institutional_holdings(&self, access_no: &str) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM institutional_holding WHERE access_no = ?1 ORDER BY holding_id")?
+            .query_map(params![access_no], institutional_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_manager_reports(&self, manager_cik: &str, period_of_report: NaiveDate) -> Result<Vec<query_models::InstitutionalHolding>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM institutional_holding WHERE manager_cik = ?1 AND period_of_report = ?2 ORDER BY filed_date, holding_id")?
+            .query_map(params![manager_cik, period_of_report], institutional_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn resolve_cusips(&self) -> Result<usize, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut linked = 0;
+        for statement in RESOLVE_CUSIPS {
+            linked = conn.execute(statement, [])?;
+        }
+
+        Ok(linked)
+    }
+
+    async fn insert_xbrl_fact(&self, fact: &XbrlFact, issuer_id: i32) -> Result<query_models::XbrlFact, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO xbrl_fact (issuer_id, access_no, form_type, fiscal_year, fiscal_period, taxonomy, concept, label, report, report_group, fact_value, unit, decimals, \
+             period_start, period_end, dimensions) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16) RETURNING *",
+            params![issuer_id, fact.access_no, fact.form_type, fact.fiscal_year, fact.fiscal_period, fact.taxonomy, fact.concept, fact.label,
+                fact.report, fact.report_group, fact.value.to_string(), fact.unit, fact.decimals, fact.period_start, fact.period_end, fact.dimensions],
+            fact_from_row,
+        )?)
+    }
+
+    async fn get_report_facts(&self, access_no: &str) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM xbrl_fact WHERE access_no = ?1 ORDER BY fact_id")?
+            .query_map(params![access_no], fact_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_period_facts(&self, issuer_id: i32, period_end: NaiveDate) -> Result<Vec<query_models::XbrlFact>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM xbrl_fact WHERE issuer_id = ?1 AND period_end = ?2 ORDER BY concept, dimensions, fact_id")?
+            .query_map(params![issuer_id, period_end], fact_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn insert_material_event(&self, event: &MaterialEvent, issuer_id: i32) -> Result<query_models::MaterialEvent, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "INSERT INTO material_event (issuer_id, access_no, form_type, form_url, filed_date, event_date, item, item_title, section) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) RETURNING *",
+            params![issuer_id, event.access_no, event.form_type, event.form_url, event.filed_date, event.event_date, event.item, event.item_title, event.section],
+            event_from_row,
+        )?)
+    }
+
+    async fn get_material_events(&self, access_no: &str) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare("SELECT * FROM material_event WHERE access_no = ?1 ORDER BY event_id")?
+            .query_map(params![access_no], event_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_issuer_events(&self, issuer_id: i32, from: NaiveDate, to: NaiveDate) -> Result<Vec<query_models::MaterialEvent>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .prepare(
+                "SELECT * FROM material_event WHERE issuer_id = ?1 AND COALESCE(event_date, filed_date) BETWEEN ?2 AND ?3 \
+                 ORDER BY COALESCE(event_date, filed_date), event_id",
+            )?
+            .query_map(params![issuer_id, from, to], event_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    async fn get_checkpoint(&self, date: NaiveDate) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        Ok(conn.query_row("SELECT * FROM mining_checkpoint WHERE mine_date = ?1", params![date], checkpoint_from_row).optional()?)
+    }
+
+    async fn put_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO mining_checkpoint (mine_date, entries, transactions, status) VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT (mine_date) DO UPDATE SET entries = excluded.entries, transactions = excluded.transactions, \
+             status = excluded.status, updated_at = CURRENT_TIMESTAMP",
+            params![checkpoint.mine_date, checkpoint.entries, checkpoint.transactions, checkpoint.status.as_str()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Every insert of the day goes through the one connection, so a plain `BEGIN` on it
+    /// covers them all even though the lock is taken statement by statement.
+    async fn save_day(&self, date: NaiveDate, entries: usize, filings: &[Filing], documents: &SideDocuments) -> Result<SaveStats, Box<dyn Error>> {
+        self.conn.lock().unwrap().execute_batch("BEGIN")?;
+
+        let saved = async {
+            let stats = self.save_filings(filings).await?;
+            self.save_side_documents(documents).await?;
+            self.put_checkpoint(&stats.checkpoint(date, entries)).await?;
+            Ok::<_, Box<dyn Error>>(stats)
+        }.await;
+
+        let conn = self.conn.lock().unwrap();
+        match saved {
+            Ok(stats) => {
+                conn.execute_batch("COMMIT")?;
+                Ok(stats)
+            }
+            Err(e) => {
+                conn.execute_batch("ROLLBACK")?;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SideDocuments;
+    use crate::secgov::models::FilingDocument;
+    use chrono::Days;
+    use std::str::FromStr;
+
+    /// The filings of the 2025-02-14 daily json and an in-memory store with the schema applied.
+    async fn fixture() -> (Vec<Filing>, SqliteStore) {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/filings/2025/02/20250214-filing.json");
+        let transactions: Vec<FilingTransaction> = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        let store = SqliteStore::open(":memory:").unwrap();
+        store.migrate().await.unwrap();
+
+        (Filing::from_transactions(transactions), store)
+    }
+
+    fn quoted_filing() -> Filing {
+        Filing {
+            access_no: "0000000000-25-000001".to_string(),
+            form_type: "4".to_string(),
+            company: "MCDONALD'S CORP".to_string(),
+            symbol: "MCD".to_string(),
+            company_cik: "0000063908".to_string(),
+            owners: vec![ReportingOwner {
+                cik: "0009999999".to_string(),
+                name: "O'BRIEN ELLEN H".to_string(),
+                officer_title: "EVP, Chief People's Officer".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn quoted_names_round_trip() {
+        let filing = quoted_filing();
+
+        let (_, store) = fixture().await;
+        let stats = store.save_filings(&[filing]).await.unwrap();
+        assert_eq!(stats.filings, 1);
+
+        let conn = store.conn.lock().unwrap();
+        let name: String = conn.query_row("SELECT name FROM issuer WHERE cik = '0000063908'", [], |row| row.get(0)).unwrap();
+        assert_eq!(name, "MCDONALD'S CORP");
+
+        let (full_name, last_name): (String, String) = conn.query_row(
+            "SELECT full_name, last_name FROM individual WHERE cik = '0009999999'", [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!(full_name, "O'BRIEN ELLEN H");
+        assert_eq!(last_name, "O'BRIEN");
+
+        let officer_title: String = conn.query_row("SELECT officer_title FROM form_owner", [], |row| row.get(0)).unwrap();
+        assert_eq!(officer_title, "EVP, Chief People's Officer");
+    }
+
+    #[tokio::test]
+    async fn issuer_symbol_filled_by_a_later_filing() {
+        let (filings, store) = fixture().await;
+        let filing = &filings[0];
+
+        // a schedule names the issuer without its trading symbol
+        let event = BeneficialOwnership {
+            access_no: "9999999999-25-000013".to_string(),
+            issuer_cik: filing.company_cik.clone(),
+            ..Default::default()
+        };
+        store.save_ownership_events(std::slice::from_ref(&event)).await.unwrap();
+        store.save_filings(std::slice::from_ref(filing)).await.unwrap();
+
+        let conn = store.conn.lock().unwrap();
+        let (name, symbol): (String, String) = conn.query_row(
+            "SELECT name, symbol FROM issuer WHERE cik = ?1", [&filing.company_cik], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!(name, filing.company);
+        assert_eq!(symbol, filing.symbol);
+    }
+
+    #[tokio::test]
+    async fn failed_filing_rolls_the_day_back() {
+        let date = NaiveDate::from_ymd_opt(2025, 2, 14).unwrap();
+        let stored = quoted_filing();
+        let failing = Filing {
+            access_no: "0000000000-25-000002".to_string(),
+            holdings: vec![Holding::default()],
+            ..quoted_filing()
+        };
+
+        let documents = SideDocuments {
+            proposed_sales: vec![ProposedSale { access_no: "9999999999-25-000144".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+
+        let (_, store) = fixture().await;
+        store.conn.lock().unwrap().execute_batch("DROP TABLE holding_footnote; DROP TABLE holding").unwrap();
+
+        assert!(store.save_day(date, 3, &[stored.clone(), failing], &documents).await.is_err());
+        assert!(store.get_form(&stored.access_no).await.unwrap().is_none());
+        assert!(store.get_proposed_sales("9999999999-25-000144").await.unwrap().is_empty());
+        assert_eq!(store.get_checkpoint(date).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn amendment_supersedes_original() {
+        let (filings, store) = fixture().await;
+        let mut original = filings
+            .into_iter()
+            .find(|f| !f.transactions.is_empty())
+            .unwrap();
+        original.filed_date = NaiveDate::from_ymd_opt(2025, 2, 14);
+
+        let mut amendment = original.clone();
+        amendment.access_no = "9999999999-25-000001".to_string();
+        amendment.form_type = "4/A".to_string();
+        amendment.filed_date = NaiveDate::from_ymd_opt(2025, 2, 18);
+        amendment.original_submission = original.filed_date;
+
+        store.save_filings(&[original.clone()]).await.unwrap();
+        store.save_filings(&[amendment.clone()]).await.unwrap();
+
+        let first = store.get_form_version(&original.access_no).await.unwrap().unwrap();
+        let amended = store.get_form_version(&amendment.access_no).await.unwrap().unwrap();
+        assert_eq!(amended.amends_form_id, Some(first.form_id));
+        assert_eq!(first.superseded_by, Some(amended.form_id));
+
+        // the original accession number resolves to the amendment and only its rows are in effect
+        assert_eq!(store.get_form(&original.access_no).await.unwrap().unwrap().form_id, amended.form_id);
+        assert!(store.get_nonderiv(first.form_id).await.unwrap().is_empty());
+        assert_eq!(store.get_nonderiv(amended.form_id).await.unwrap().len(), amendment.transactions.len());
+    }
+
+    #[tokio::test]
+    async fn form3_holdings_are_the_baseline() {
+        let (filings, store) = fixture().await;
+        let mut form3 = filings[0].clone();
+        form3.form_type = "3".to_string();
+        form3.transactions.clear();
+        form3.derivatives.clear();
+        form3.holdings = vec![Holding {
+            security_title: "Common Stock".to_string(),
+            shares_owned: BigDecimal::from_str("1500").ok(),
+            ownership_code: "D".to_string(),
+            ..Default::default()
+        }];
+
+        store.save_filings(&[form3.clone()]).await.unwrap();
+
+        let form = store.get_form(&form3.access_no).await.unwrap().unwrap();
+        let owner = store.create_individual(&form3.owners[0]).await.unwrap();
+        let baseline = store.get_baseline(form.issuer_id, owner).await.unwrap();
+
+        assert_eq!(baseline.len(), 1);
+        assert_eq!(baseline[0].form_id, form.form_id);
+        assert_eq!(baseline[0].shares_owned, form3.holdings[0].shares_owned);
+    }
+
+    #[tokio::test]
+    async fn form_documents_are_stored_with_the_form() {
+        let (filings, store) = fixture().await;
+        let mut filing = filings[0].clone();
+        filing.documents = vec![
+            FilingDocument {
+                seq: Some(1),
+                name: "wk-form4_1739574321.xml".to_string(),
+                doc_type: "4".to_string(),
+                description: "FORM 4".to_string(),
+                size: Some(4871),
+            },
+            FilingDocument {
+                name: format!("{}.txt", filing.access_no),
+                description: "Complete submission text file".to_string(),
+                size: Some(6324),
+                ..Default::default()
+            },
+        ];
+
+        store.save_filings(std::slice::from_ref(&filing)).await.unwrap();
+
+        let form = store.get_form(&filing.access_no).await.unwrap().unwrap();
+        let documents = store.get_documents(form.form_id).await.unwrap();
+        let names: Vec<(Option<i32>, &str, &str)> = documents.iter().map(|d| (d.seq, d.name.as_str(), d.doc_type.as_str())).collect();
+        assert_eq!(names, vec![
+            (Some(1), "wk-form4_1739574321.xml", "4"),
+            (None, filing.documents[1].name.as_str(), ""),
+        ]);
+        assert_eq!(documents[0].size, Some(4871));
+    }
+
+    #[tokio::test]
+    async fn form144_matches_later_sales() {
+        let (filings, store) = fixture().await;
+        let filing = filings
+            .into_iter()
+            .find(|f| f.transactions.iter().any(|t| t.trans_code == "S" && t.action_code == "D"))
+            .unwrap();
+        let sale_date = filing.transactions.iter().filter_map(|t| t.trans_date).min().unwrap();
+
+        let notice = ProposedSale {
+            access_no: "9999999999-25-000144".to_string(),
+            form_type: "144".to_string(),
+            filed_date: sale_date.pred_opt(),
+            issuer_cik: filing.company_cik.clone(),
+            seller_cik: filing.owners[0].cik.clone(),
+            ..Default::default()
+        };
+
+        store.save_filings(std::slice::from_ref(&filing)).await.unwrap();
+        assert_eq!(store.save_proposed_sales(std::slice::from_ref(&notice)).await.unwrap(), 1);
+        assert_eq!(store.save_proposed_sales(std::slice::from_ref(&notice)).await.unwrap(), 0);
+
+        let form = store.get_form(&filing.access_no).await.unwrap().unwrap();
+        let matches = store.get_sale_matches(&notice.access_no).await.unwrap();
+        let sales = filing.transactions.iter().filter(|t| t.trans_code == "S" && t.action_code == "D").count();
+
+        assert_eq!(matches.len(), sales);
+        assert!(matches.iter().all(|m| m.form_id == form.form_id));
+
+        // a notice expires 90 days after its approximate sale date
+        let stale = ProposedSale {
+            access_no: "9999999999-25-000145".to_string(),
+            filed_date: sale_date.checked_sub_days(Days::new(120)),
+            approx_sale_date: sale_date.checked_sub_days(Days::new(100)),
+            ..notice.clone()
+        };
+        store.save_proposed_sales(std::slice::from_ref(&stale)).await.unwrap();
+        assert!(store.get_sale_matches(&stale.access_no).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn schedule_events_link_to_the_issuer() {
+        let (filings, store) = fixture().await;
+        let filing = filings[0].clone();
+
+        let event = BeneficialOwnership {
+            access_no: "9999999999-25-000013".to_string(),
+            form_type: "SCHEDULE 13G".to_string(),
+            issuer_cik: filing.company_cik.clone(),
+            issuer_name: filing.company.clone(),
+            filer_name: "VANGUARD GROUP INC".to_string(),
+            ..Default::default()
+        };
+
+        store.save_filings(std::slice::from_ref(&filing)).await.unwrap();
+        assert_eq!(store.save_ownership_events(std::slice::from_ref(&event)).await.unwrap(), 1);
+        assert_eq!(store.save_ownership_events(std::slice::from_ref(&event)).await.unwrap(), 0);
+
+        let form = store.get_form(&filing.access_no).await.unwrap().unwrap();
+        let stored = store.get_ownership_events(&event.access_no).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].issuer_id, form.issuer_id);
+    }
+
+    #[tokio::test]
+    async fn statement_listed_under_two_ciks_is_stored_once() {
+        let date = NaiveDate::from_ymd_opt(2025, 2, 14).unwrap();
+        let (filings, store) = fixture().await;
+        let filing = filings[0].clone();
+
+        // a joint statement fetched from the subject company and from the filer
+        let person = |filer_name: &str, cik: &str| BeneficialOwnership {
+            access_no: "9999999999-25-000013".to_string(),
+            form_type: "SCHEDULE 13D".to_string(),
+            form_url: format!("https://www.sec.gov/Archives/edgar/data/{cik}/9999999999-25-000013.txt"),
+            issuer_cik: filing.company_cik.clone(),
+            issuer_name: filing.company.clone(),
+            filer_name: filer_name.to_string(),
+            ..Default::default()
+        };
+        let documents = SideDocuments {
+            ownership_events: vec![
+                person("ELLIOTT INVESTMENT MANAGEMENT L.P.", &filing.company_cik),
+                person("PAUL E. SINGER", &filing.company_cik),
+                person("ELLIOTT INVESTMENT MANAGEMENT L.P.", "1791786"),
+                person("PAUL E. SINGER", "1791786"),
+            ],
+            ..Default::default()
+        };
+
+        store.save_day(date, 2, std::slice::from_ref(&filing), &documents).await.unwrap();
+
+        let stored = store.get_ownership_events("9999999999-25-000013").await.unwrap();
+        assert_eq!(stored.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn material_events_follow_insider_sales() {
+        let (filings, store) = fixture().await;
+        let filing = filings
+            .into_iter()
+            .find(|f| f.transactions.iter().any(|t| t.trans_code == "S" && t.action_code == "D"))
+            .unwrap();
+        let sale_date = filing.transactions.iter().filter_map(|t| t.trans_date).min().unwrap();
+
+        let departure = MaterialEvent {
+            access_no: "9999999999-25-000008".to_string(),
+            form_type: "8-K".to_string(),
+            event_date: sale_date.checked_add_days(Days::new(3)),
+            issuer_cik: filing.company_cik.clone(),
+            issuer_name: filing.company.clone(),
+            item: "5.02".to_string(),
+            ..Default::default()
+        };
+        let exhibits = MaterialEvent { item: "9.01".to_string(), ..departure.clone() };
+        // no event date, the filing date places it a month later
+        let later = MaterialEvent {
+            access_no: "9999999999-25-000009".to_string(),
+            event_date: None,
+            filed_date: sale_date.checked_add_days(Days::new(30)),
+            item: "8.01".to_string(),
+            ..departure.clone()
+        };
+        let events = vec![departure.clone(), exhibits, later];
+
+        store.save_filings(std::slice::from_ref(&filing)).await.unwrap();
+        assert_eq!(store.save_material_events(&events).await.unwrap(), 3);
+        assert_eq!(store.save_material_events(&events).await.unwrap(), 0);
+
+        let form = store.get_form(&filing.access_no).await.unwrap().unwrap();
+        let week = store.get_issuer_events(form.issuer_id, sale_date, sale_date.checked_add_days(Days::new(7)).unwrap()).await.unwrap();
+        let items: Vec<&str> = week.iter().map(|e| e.item.as_str()).collect();
+        assert_eq!(items, vec!["5.02", "9.01"]);
+        assert!(week.iter().all(|e| e.access_no == departure.access_no));
+    }
+
+    #[tokio::test]
+    async fn institutional_holdings_resolve_to_issuers() {
+        let (filings, store) = fixture().await;
+        let (first, second) = (&filings[0], filings.iter().find(|f| f.company_cik != filings[0].company_cik).unwrap());
+
+        let event = BeneficialOwnership {
+            access_no: "9999999999-25-000013".to_string(),
+            form_type: "SCHEDULE 13G".to_string(),
+            issuer_cik: first.company_cik.clone(),
+            issuer_name: first.company.clone(),
+            issuer_cusip: "123456109".to_string(),
+            ..Default::default()
+        };
+
+        let period = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let report = InstitutionalHolding {
+            access_no: "9999999999-25-000131".to_string(),
+            form_type: "13F-HR".to_string(),
+            filed_date: NaiveDate::from_ymd_opt(2025, 2, 14),
+            period_of_report: period,
+            manager_cik: "0001067983".to_string(),
+            ..Default::default()
+        };
+        let holdings = vec![
+            // another class of the schedule issuer
+            InstitutionalHolding { cusip: "123456208".to_string(), issuer_name: "ANY NAME".to_string(), ..report.clone() },
+            // matched on the issuer name, punctuation aside
+            InstitutionalHolding { cusip: "654321109".to_string(), issuer_name: format!("{}.", second.company.replace(['.', ','], "")), ..report.clone() },
+            InstitutionalHolding { cusip: "999999109".to_string(), issuer_name: "UNKNOWN CORP".to_string(), ..report.clone() },
+        ];
+        let added = InstitutionalHolding {
+            access_no: "9999999999-25-000132".to_string(),
+            form_type: "13F-HR/A".to_string(),
+            amendment_type: Some("NEW HOLDINGS".to_string()),
+            filed_date: NaiveDate::from_ymd_opt(2025, 2, 20),
+            cusip: "123456109".to_string(),
+            ..report.clone()
+        };
+
+        store.save_filings(&filings).await.unwrap();
+        store.save_ownership_events(std::slice::from_ref(&event)).await.unwrap();
+        assert_eq!(store.save_institutional_holdings(&holdings).await.unwrap(), 3);
+        assert_eq!(store.save_institutional_holdings(&holdings).await.unwrap(), 0);
+        store.save_institutional_holdings(std::slice::from_ref(&added)).await.unwrap();
+
+        assert_eq!(store.resolve_cusips().await.unwrap(), 3);
+        assert_eq!(store.resolve_cusips().await.unwrap(), 0);
+
+        let first_issuer = store.get_form(&first.access_no).await.unwrap().unwrap().issuer_id;
+        let second_issuer = store.get_form(&second.access_no).await.unwrap().unwrap().issuer_id;
+        let stored = store.get_manager_holdings(&report.manager_cik, period).await.unwrap();
+        let issuers: Vec<Option<i32>> = stored.iter().map(|h| h.issuer_id).collect();
+        assert_eq!(issuers, vec![Some(first_issuer), Some(second_issuer), None, Some(first_issuer)]);
+
+        // a restatement replaces everything reported before it
+        let restated = InstitutionalHolding {
+            access_no: "9999999999-25-000133".to_string(),
+            amendment_type: Some("RESTATEMENT".to_string()),
+            filed_date: NaiveDate::from_ymd_opt(2025, 3, 1),
+            ..added.clone()
+        };
+        store.save_institutional_holdings(std::slice::from_ref(&restated)).await.unwrap();
+        let stored = store.get_manager_holdings(&report.manager_cik, period).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].access_no, restated.access_no);
+    }
+
+    #[tokio::test]
+    async fn xbrl_facts_stored_by_issuer_and_period() {
+        let (_, store) = fixture().await;
+        let period_end = NaiveDate::from_ymd_opt(2024, 9, 28).unwrap();
+        let revenue = XbrlFact {
+            access_no: "0000320193-24-000123".to_string(),
+            form_type: "10-K".to_string(),
+            issuer_cik: "0000320193".to_string(),
+            issuer_name: "Apple Inc.".to_string(),
+            fiscal_year: Some(2024),
+            fiscal_period: "FY".to_string(),
+            taxonomy: "us-gaap".to_string(),
+            concept: "Revenues".to_string(),
+            value: "391035000000".parse().unwrap(),
+            unit: "USD".to_string(),
+            decimals: Some(-6),
+            period_start: NaiveDate::from_ymd_opt(2023, 10, 1),
+            period_end,
+            ..Default::default()
+        };
+        let services = XbrlFact {
+            value: "96169000000".parse().unwrap(),
+            dimensions: "srt:ProductOrServiceAxis=us-gaap:ServiceMember".to_string(),
+            ..revenue.clone()
+        };
+        let eps = XbrlFact {
+            concept: "EarningsPerShareBasic".to_string(),
+            value: "6.11".parse().unwrap(),
+            unit: "USD/shares".to_string(),
+            decimals: Some(2),
+            ..revenue.clone()
+        };
+        let facts = vec![revenue.clone(), services, eps.clone()];
+
+        assert_eq!(store.save_xbrl_facts(&facts).await.unwrap(), 3);
+        assert_eq!(store.save_xbrl_facts(&facts).await.unwrap(), 0);
+
+        let stored = store.get_report_facts(&revenue.access_no).await.unwrap();
+        assert_eq!(stored.len(), 3);
+        assert!(stored.iter().all(|f| f.issuer_id == stored[0].issuer_id));
+
+        let period = store.get_period_facts(stored[0].issuer_id, period_end).await.unwrap();
+        let concepts: Vec<(&str, &str)> = period.iter().map(|f| (f.concept.as_str(), f.dimensions.as_str())).collect();
+        assert_eq!(concepts, vec![
+            ("EarningsPerShareBasic", ""),
+            ("Revenues", ""),
+            ("Revenues", "srt:ProductOrServiceAxis=us-gaap:ServiceMember"),
+        ]);
+        assert_eq!(period[0].value, eps.value);
+    }
+}
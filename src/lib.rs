@@ -1,8 +1,3 @@
 pub mod secgov;
 pub mod miner;
 pub mod database;
-
-#[no_mangle]
-pub extern "C" fn main() -> i32 {
-    3
-}
\ No newline at end of file
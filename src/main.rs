@@ -1,14 +1,38 @@
 use chrono::NaiveDate;
 use chrono_tz::America::New_York;
+use secfilings::database::StoreConfig;
 use secfilings::miner::Miner;
+use secfilings::secgov::FormFilter;
 #[tokio::main]
 async fn main() {
+    let config = StoreConfig::from_env().expect("Invalid store configuration");
+    let store = config.open().await.expect("Failed to open store");
+    store.migrate().await.expect("Failed to migrate database");
+
+    // `secfilings migrate` only updates the schema
+    if std::env::args().nth(1).as_deref() == Some("migrate") {
+        return;
+    }
+
     let start = chrono::Utc::now()
         .with_timezone(&New_York)
         .date_naive();
 
     let end = NaiveDate::from_ymd_opt(2020, 1, 4).unwrap();
-    let mut miner = Miner::new(&start);
+    let filter = FormFilter::form4()
+        .form_type("3")
+        .form_type("5")
+        .form_type("144")
+        .form_type("SC 13D")
+        .form_type("SC 13G")
+        .form_type("SCHEDULE 13D")
+        .form_type("SCHEDULE 13G")
+        .form_type("13F-HR")
+        .form_type("10-K")
+        .form_type("10-Q")
+        .form_type("8-K")
+        .amendments(true);
+    let mut miner = Miner::new(&start, store, filter);
 
     loop {
         if miner.mine_date == end {
@@ -1,24 +1,30 @@
-use crate::database::{get_connection, SqlHelper};
-use crate::secgov::models::FilingTransaction;
-use crate::secgov::{get_daily_entries, process_entries};
+use crate::database::query_models::{Checkpoint, CheckpointStatus};
+use crate::database::{FilingStore, SideDocuments};
+use crate::secgov::models::{BeneficialOwnership, Filing, FilingTransaction, InstitutionalHolding, MaterialEvent, XbrlFact};
+use crate::secgov::xbrl::get_facts;
+use crate::secgov::{get_beneficial_ownership, get_institutional_holdings, get_material_events, get_proposed_sales, process_entries, FormFilter, IndexClient, IndexEntry};
 use chrono::{Datelike, Days, NaiveDate};
 use chrono_tz::America::New_York;
-use futures::stream::{self, StreamExt};
+use futures::future::join_all;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fs;
-use std::fs::File;
-use std::io::BufReader;
+use std::future::Future;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub struct Miner {
     pub mine_date: NaiveDate,
+    store: Box<dyn FilingStore>,
+    index: IndexClient,
+    filter: FormFilter,
 }
 
 impl Miner {
-    pub fn new(start: &NaiveDate) -> Miner {
-        Miner { mine_date: *start }
+    /// Mines from `start` onwards, fetching the index entries `filter` lets through.
+    pub fn new(start: &NaiveDate, store: Box<dyn FilingStore>, filter: FormFilter) -> Miner {
+        Miner { mine_date: *start, store, index: IndexClient::new(), filter }
     }
 
     fn yesterday() -> NaiveDate {
@@ -44,75 +50,105 @@ impl Miner {
         format!("{}/{date}-filing.json", Self::save_dir(self.mine_date))
     }
 
-    fn save_filings_json(&self, filings: &[FilingTransaction]) {
-        let filepath = self.file_path();
+    fn documents_path(&self) -> String {
+        let date = self.mine_date.format("%Y%m%d");
+        format!("{}/{date}-documents.json", Self::save_dir(self.mine_date))
+    }
 
+    fn save_filings_json(&self, filings: &[Filing], documents: &SideDocuments) {
         fs::create_dir_all(Self::save_dir(self.mine_date))
             .expect("Failed to create dir path");
 
         let text = serde_json::to_string(&filings).expect("Failed to serialize struct");
-        fs::write(filepath, text).expect("Unable to write file");
-    }
-
-    async fn save_filings_db(filings: &[FilingTransaction]) -> Result<(), Box<dyn Error>> {
-        let client = Arc::new(Mutex::new(get_connection().await?));
-
-        let helper = Arc::new(Mutex::new(SqlHelper::new()));
-
-        let total = filings.len();
-        let i = Arc::new(Mutex::new(0));
-
-        let stream = stream::iter(filings);
-
-        stream
-            .for_each_concurrent(10, |trans| {
-                let helper = Arc::clone(&helper);
-                let i = Arc::clone(&i);
-                let client = Arc::clone(&client);
-
-                async move {
-                    let mut helper = helper.lock().unwrap();
-                    let mut client = client.lock().unwrap();
-
-                    let issuer = helper.create_issuer(&mut client, trans).await.ok();
-                    let ind = helper.create_individual(&mut client, trans).await.ok();
-
-                    if ind.is_none() || issuer.is_none() {
-                        let mut progress = i.lock().unwrap();
-                        *progress += 1;
-                        println!("failed insert {}/{}", *progress, total);
-                        return;
-                    }
-
-                    let form_id = helper
-                        .create_form(&mut client, trans, issuer.unwrap())
-                        .await;
-                    if let Ok(form_id) = form_id {
-                        let result = helper
-                            .insert_nonderiv(
-                                &mut client,
-                                trans,
-                                form_id,
-                                issuer.unwrap(),
-                                ind.unwrap(),
-                            )
-                            .await;
-
-                        if result.is_err() {
-                            println!("Error occurred adding transaction for form ID: {}", form_id);
-                        }
-                    }
-
-                    let mut progress = i.lock().unwrap();
-                    *progress += 1;
-                    println!("insert {}/{}", *progress, total);
-                }
-            })
-            .await;
+        fs::write(self.file_path(), text).expect("Unable to write file");
+
+        let text = serde_json::to_string(documents).expect("Failed to serialize struct");
+        fs::write(self.documents_path(), text).expect("Unable to write file");
+    }
+
+    /// Side documents saved next to the filings of a day, none for days saved before they were.
+    fn read_documents_json(path: &str) -> SideDocuments {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Commits a day of filings and side documents together with its checkpoint.
+    async fn save_day_db(store: &dyn FilingStore, date: NaiveDate, entries: usize, filings: &[Filing], documents: &SideDocuments) -> Result<(), Box<dyn Error>> {
+        let started = Instant::now();
+        let stats = store.save_day(date, entries, filings, documents).await?;
+        let elapsed = started.elapsed().as_secs_f64();
+
+        println!(
+            "Stored {} filings, {} rows ({} without owner) in {:.1}s, {:.1} filings/s, {:.1} rows/s",
+            stats.filings, stats.rows, stats.skipped, elapsed,
+            stats.filings as f64 / elapsed.max(f64::EPSILON), stats.rows as f64 / elapsed.max(f64::EPSILON)
+        );
 
         Ok(())
     }
 
+    /// Reads a daily json file, falling back to the old format that stored flat transaction rows.
+    /// Filings saved with a single owner instead of `owners` are read as well, see [`Filing::owners`].
+    fn read_filings_json(path: &str) -> Option<Vec<Filing>> {
+        let text = fs::read_to_string(path).ok()?;
+
+        if let Ok(filings) = serde_json::from_str::<Vec<Filing>>(&text) {
+            return Some(filings);
+        }
+
+        serde_json::from_str::<Vec<FilingTransaction>>(&text)
+            .ok()
+            .map(Filing::from_transactions)
+    }
+
+    /// The first entry of every accession. The index lists a submission under each cik it
+    /// involves, a Form 144 under the issuer and the seller, a schedule under the subject company
+    /// and the filer, and each copy would be downloaded and stored again.
+    fn unique_accessions(entries: Vec<IndexEntry>) -> Vec<IndexEntry> {
+        let mut seen = HashSet::new();
+
+        entries.into_iter()
+            .filter(|entry| seen.insert(entry.access_no().unwrap_or_else(|| entry.filepath.clone())))
+            .collect()
+    }
+
+    /// Fetches documents that are not ownership filings, `batch` per second like the filings.
+    /// Entries that fail are logged and left out.
+    async fn fetch_all<'a, T, F, Fut>(entries: &'a [IndexEntry], batch: usize, fetch: F) -> Vec<T>
+    where
+        F: Fn(&'a IndexEntry) -> Fut,
+        Fut: Future<Output = Result<Vec<T>, Box<dyn Error>>>,
+    {
+        let mut documents = Vec::<T>::new();
+
+        for chunk in entries.chunks(batch) {
+            for (entry, result) in chunk.iter().zip(join_all(chunk.iter().map(&fetch)).await) {
+                match result {
+                    Ok(rows) => documents.extend(rows),
+                    Err(err) => println!("Error occurred for filing {}: {}", entry.filepath, err),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
+        documents
+    }
+
+    /// Fetches the Form 144 notices, Schedule 13D/G statements, 13F-HR reports, the XBRL facts
+    /// of 10-K/10-Q filings and the 8-K events of the day.
+    async fn fetch_side_documents(notices: &[IndexEntry], schedules: &[IndexEntry], reports: &[IndexEntry], periodic: &[IndexEntry], current: &[IndexEntry], batch: usize) -> SideDocuments {
+        SideDocuments {
+            proposed_sales: Self::fetch_all(notices, batch, get_proposed_sales).await,
+            ownership_events: Self::fetch_all(schedules, batch, get_beneficial_ownership).await,
+            institutional_holdings: Self::fetch_all(reports, batch, get_institutional_holdings).await,
+            xbrl_facts: Self::fetch_all(periodic, batch, get_facts).await,
+            material_events: Self::fetch_all(current, batch, get_material_events).await,
+        }
+    }
+
     pub async fn run(&mut self, batch: usize) {
         if batch > 10 {
             panic!("Due to SEC limits, batch per second must be <= 10");
@@ -127,19 +163,29 @@ impl Miner {
             return;
         }
 
-        let db = Arc::new(Mutex::new(Vec::<FilingTransaction>::new()));
+        let checkpoint = self.store.get_checkpoint(self.mine_date).await.expect("Error reading checkpoint");
+        if let Some(checkpoint) = &checkpoint {
+            if checkpoint.status == CheckpointStatus::COMMITTED {
+                println!(
+                    "Skip day {} already committed, {} entries, {} transactions",
+                    self.mine_date, checkpoint.entries, checkpoint.transactions
+                );
+                self.increment_day();
+                return;
+            }
+        }
+
+        let db = Arc::new(Mutex::new(Vec::<Filing>::new()));
 
         // check for json file saved previously
         let path = self.file_path();
         let existing = Path::new(&path).exists();
         if existing {
-            let file = File::open(&path).unwrap();
-            let rdr = BufReader::new(file);
-
-            let filings: Result<Vec<FilingTransaction>, serde_json::Error> = serde_json::from_reader(rdr);
-            if let Ok(filings) = filings {
+            if let Some(filings) = Self::read_filings_json(&path) {
                 println!("Inserting from previously saved file {path}");
-                Self::save_filings_db(&filings)
+                let entries = checkpoint.map_or(filings.len(), |c| c.entries as usize);
+                let documents = Self::read_documents_json(&self.documents_path());
+                Self::save_day_db(self.store.as_ref(), self.mine_date, entries, &filings, &documents)
                     .await
                     .expect("Error saving to db");
 
@@ -148,18 +194,48 @@ impl Miner {
             }
         }
 
-        let body = get_daily_entries(self.mine_date).await.unwrap();
+        let entries = self.index.daily_entries(self.mine_date).await.expect("Error fetching daily index");
+        // Form 144 notices, schedules, 13F, periodic and current reports are not ownership documents, they are fetched and stored on their own
+        let (mut notices, mut schedules, mut reports, mut periodic, mut current, mut body) =
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for entry in Self::unique_accessions(self.filter.apply(entries)) {
+            if entry.form_type.starts_with("144") {
+                notices.push(entry);
+            } else if BeneficialOwnership::is_schedule(&entry.form_type) {
+                schedules.push(entry);
+            } else if InstitutionalHolding::is_report(&entry.form_type) {
+                reports.push(entry);
+            } else if XbrlFact::is_periodic_report(&entry.form_type) {
+                periodic.push(entry);
+            } else if MaterialEvent::is_current_report(&entry.form_type) {
+                current.push(entry);
+            } else {
+                body.push(entry);
+            }
+        }
+        let total_entries = body.len() + notices.len() + schedules.len() + reports.len() + periodic.len() + current.len();
 
-        if body.is_empty() {
+        if total_entries == 0 {
             println!("Skip day {} index empty", self.mine_date);
+            Self::save_day_db(self.store.as_ref(), self.mine_date, 0, &[], &SideDocuments::default())
+                .await
+                .expect("Error saving checkpoint");
             self.increment_day();
             return;
         }
 
+        self.store.put_checkpoint(&Checkpoint {
+            mine_date: self.mine_date,
+            entries: total_entries as i32,
+            transactions: 0,
+            status: CheckpointStatus::IN_PROGRESS,
+        }).await.expect("Error saving checkpoint");
+
         let second_delay = Duration::from_secs(1);
 
         let mut skip = 0;
-        let total = body.len() / batch;
+        // the last batch may be partial, its entries are counted in the checkpoint too
+        let total = body.len().div_ceil(batch);
         for i in 0..total {
             println!("Get {i}/{total}");
 
@@ -170,12 +246,116 @@ impl Miner {
             tokio::time::sleep(second_delay).await;
         }
 
-        let filings = db.lock().unwrap();
+        let documents = Self::fetch_side_documents(&notices, &schedules, &reports, &periodic, &current, batch).await;
+
+        // taken out of the mutex so that no lock is held across the awaits below
+        let filings = std::mem::take(&mut *db.lock().unwrap());
 
-        self.save_filings_json(&filings);
+        self.save_filings_json(&filings, &documents);
 
-        Self::save_filings_db(&filings).await.expect("Should have saved to local file and db");
+        Self::save_day_db(self.store.as_ref(), self.mine_date, total_entries, &filings, &documents)
+            .await
+            .expect("Should have saved to local file and db");
 
         self.increment_day();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SqliteStore;
+    use crate::secgov::models::Relationship;
+    use std::env;
+
+    #[tokio::test]
+    async fn saved_day_round_trips_through_sqlite() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/filings/2025/02/20250214-filing.json");
+        let filings = Miner::read_filings_json(path).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 2, 14).unwrap();
+        let store = SqliteStore::open(":memory:").unwrap();
+        assert_eq!(store.migrate().await.unwrap(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        assert!(store.migrate().await.unwrap().is_empty());
+        assert_eq!(store.get_checkpoint(date).await.unwrap(), None);
+
+        Miner::save_day_db(&store, date, filings.len(), &filings, &SideDocuments::default()).await.unwrap();
+        let first = stored_transactions(&store, &filings).await;
+        assert!(first > 0);
+
+        let checkpoint = store.get_checkpoint(date).await.unwrap().unwrap();
+        assert_eq!(checkpoint.status, CheckpointStatus::COMMITTED);
+        assert_eq!(checkpoint.entries as usize, filings.len());
+
+        // replaying the same day must not duplicate anything
+        Miner::save_day_db(&store, date, filings.len(), &filings, &SideDocuments::default()).await.unwrap();
+        assert_eq!(stored_transactions(&store, &filings).await, first);
+        assert_eq!(store.get_checkpoint(date).await.unwrap().unwrap().transactions, 0);
+
+        for filing in filings.iter().filter(|f| !f.transactions.is_empty()) {
+            let form = store.get_form(&filing.access_no).await.unwrap().unwrap();
+            assert_eq!(form.form_type, filing.form_type);
+
+            for trans in store.get_nonderiv(form.form_id).await.unwrap() {
+                assert!(filing.transactions.iter().any(|t| t.trans_date == trans.date_reported
+                    && t.shares_owned == trans.shares_balance
+                    && t.avg_price == trans.avg_price));
+            }
+        }
+    }
+
+    #[test]
+    fn filings_saved_with_a_single_owner_are_read() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/filings/2025/02/20250214-filing.json");
+        let filing = Miner::read_filings_json(path).unwrap().remove(0);
+
+        let mut json = serde_json::to_value(vec![&filing]).unwrap();
+        let saved = json[0].as_object_mut().unwrap();
+        assert!(saved.remove("owners").is_some());
+        saved.insert("owner".to_string(), filing.owners[0].name.clone().into());
+        saved.insert("owner_cik".to_string(), filing.owners[0].cik.clone().into());
+        saved.insert("relationship".to_string(), serde_json::json!(["DIRECTOR"]));
+
+        let legacy = env::temp_dir().join(format!("secfilings-{}-filing.json", std::process::id()));
+        fs::write(&legacy, json.to_string()).unwrap();
+        let filings = Miner::read_filings_json(legacy.to_str().unwrap()).unwrap();
+        fs::remove_file(&legacy).unwrap();
+
+        assert_eq!(filings.len(), 1);
+        assert_eq!(filings[0].transactions.len(), filing.transactions.len());
+        assert_eq!(filings[0].owners.len(), 1);
+        assert_eq!(filings[0].owners[0].cik, filing.owners[0].cik);
+        assert_eq!(filings[0].owners[0].name, filing.owners[0].name);
+        assert!(matches!(filings[0].owners[0].relationship[..], [Relationship::DIRECTOR]));
+    }
+
+    #[test]
+    fn each_accession_is_fetched_once() {
+        let entry = |cik: &str, form_type: &str, filepath: &str| IndexEntry {
+            company_cik: cik.to_string(),
+            company_name: String::new(),
+            form_type: form_type.to_string(),
+            file_date: NaiveDate::from_ymd_opt(2025, 2, 14).unwrap(),
+            filepath: filepath.to_string(),
+        };
+
+        let entries = Miner::unique_accessions(vec![
+            entry("320193", "144", "edgar/data/320193/0001959173-25-002211.txt"),
+            entry("1214156", "144", "edgar/data/1214156/0001959173-25-002211.txt"),
+            entry("1214156", "4", "edgar/data/1214156/0000320193-25-000030.txt"),
+        ]);
+        let ciks: Vec<&str> = entries.iter().map(|e| e.company_cik.as_str()).collect();
+        assert_eq!(ciks, vec!["320193", "1214156"]);
+        assert_eq!(entries[1].form_type, "4");
+    }
+
+    async fn stored_transactions(store: &SqliteStore, filings: &[Filing]) -> usize {
+        let mut total = 0;
+        for filing in filings {
+            if let Some(form) = store.get_form(&filing.access_no).await.unwrap() {
+                total += store.get_nonderiv(form.form_id).await.unwrap().len();
+            }
+        }
+        total
+    }
+}
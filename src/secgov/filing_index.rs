@@ -0,0 +1,325 @@
+use std::error::Error;
+
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+
+use super::models::FilingDocument;
+use super::parser::atomfilings::XMLFiling;
+use super::parser::error::ParseError;
+use super::parser::SubmissionHeader;
+use super::{get_text, IndexEntry, BASEURL};
+
+static ROWS: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<tr[^>]*>(.*?)</tr>").unwrap());
+
+static CELLS: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<td[^>]*>(.*?)</td>").unwrap());
+
+static LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)href="([^"]+)""#).unwrap());
+
+static TAGS: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+static PARTIES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?s)<span class="companyName">\s*(.*?)\s*\((Filer|Subject|Filed by)\)\s*<acronym[^>]*>CIK</acronym>:\s*<a[^>]*>\s*(\d+)"#).unwrap()
+});
+
+static ITEMS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?s)class="infoHead">Items</div>\s*<div class="info">(.*?)</div>"#).unwrap());
+
+static BREAK: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+
+#[derive(Deserialize)]
+struct DirectoryListing {
+    directory: Directory,
+}
+
+#[derive(Deserialize)]
+struct Directory {
+    item: Vec<DirectoryItem>,
+}
+
+#[derive(Deserialize)]
+struct DirectoryItem {
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    size: String,
+}
+
+/// The documents of a submission, read from its accession directory instead of the full
+/// `.txt` submission.
+///
+/// `{access_no}-index.html` lists the documents of the submission with their sequence, type
+/// and size, and gives the filing date, the 8-K items and the parties of the SEC header.
+/// `index.json` lists every file of the directory, including the ones the SEC generates after
+/// acceptance (`MetaLinks.json`, `R1.htm`...), which have no type.
+#[derive(Debug, Default)]
+pub struct FilingIndex {
+    pub access_no: String,
+    /// Url of the accession directory, ending in `/`.
+    pub dir_url: String,
+    pub filed_date: Option<NaiveDate>,
+    pub period_of_report: Option<NaiveDate>,
+    /// Titles of the 8-K items.
+    pub items: Vec<String>,
+    /// Cik and name of the `(Filer)`, `(Subject)` and `(Filed by)` companies of the page.
+    pub filer: Option<(String, String)>,
+    pub subject: Option<(String, String)>,
+    pub filed_by: Option<(String, String)>,
+    pub documents: Vec<FilingDocument>,
+}
+
+impl FilingIndex {
+    /// Accession directory of an index entry, `edgar/data/320193/0000320193-24-000123.txt` lives
+    /// in `edgar/data/320193/000032019324000123/`.
+    pub fn dir_url(entry: &IndexEntry) -> Option<String> {
+        let access_no = XMLFiling::parse_access_num(&entry.filepath)?;
+        let (cik_dir, _) = entry.filepath.rsplit_once('/')?;
+
+        Some(format!("{BASEURL}{cik_dir}/{}/", access_no.replace('-', "")))
+    }
+
+    /// The documents of the submission of an index entry, from its `-index.html` page.
+    pub async fn fetch(entry: &IndexEntry) -> Result<FilingIndex, Box<dyn Error>> {
+        let (access_no, dir_url) = Self::locate(entry)?;
+        let page = get_text(&format!("{dir_url}{access_no}-index.html")).await?;
+
+        Ok(Self::parse_page(&access_no, &dir_url, &page))
+    }
+
+    /// Every file of the accession directory of an index entry, from its `index.json`.
+    pub async fn fetch_directory(entry: &IndexEntry) -> Result<FilingIndex, Box<dyn Error>> {
+        let (access_no, dir_url) = Self::locate(entry)?;
+        let listing = get_text(&format!("{dir_url}index.json")).await?;
+
+        Ok(Self::parse_listing(&access_no, &dir_url, &listing)?)
+    }
+
+    fn locate(entry: &IndexEntry) -> Result<(String, String), ParseError> {
+        let access_no = XMLFiling::parse_access_num(&entry.filepath).unwrap_or_default();
+
+        match Self::dir_url(entry) {
+            Some(dir_url) => Ok((access_no, dir_url)),
+            None => Err(ParseError::NoXmlPayload { access_no }),
+        }
+    }
+
+    /// Rows of the document tables of an `-index.html` page, the submission documents and the
+    /// XBRL data files.
+    pub fn parse_page(access_no: &str, dir_url: &str, page: &str) -> FilingIndex {
+        let dir = format!("{}/", access_no.replace('-', ""));
+
+        let documents = ROWS.captures_iter(page)
+            .filter_map(|row| {
+                let cells: Vec<&str> = CELLS.captures_iter(&row[1]).map(|c| c.get(1).unwrap().as_str()).collect();
+                if cells.len() != 5 {
+                    return None;
+                }
+
+                // the link holds the path in the directory, rendered documents live in a subfolder
+                let href = &LINK.captures(cells[2])?[1];
+                let name = href.split_once(&dir).map_or(href, |(_, name)| name);
+
+                Some(FilingDocument {
+                    seq: Self::cell_text(cells[0]).parse().ok(),
+                    name: name.to_string(),
+                    doc_type: Self::cell_text(cells[3]),
+                    description: Self::cell_text(cells[1]),
+                    size: Self::cell_text(cells[4]).parse().ok(),
+                })
+            })
+            .collect();
+
+        FilingIndex {
+            access_no: access_no.to_string(),
+            dir_url: dir_url.to_string(),
+            filed_date: Self::info_date(page, "Filing Date"),
+            period_of_report: Self::info_date(page, "Period of Report"),
+            items: ITEMS.captures(page)
+                .map(|items| BREAK.split(&items[1]).map(Self::cell_text).filter(|item| !item.is_empty()).collect())
+                .unwrap_or_default(),
+            filer: Self::party(page, "Filer"),
+            subject: Self::party(page, "Subject"),
+            filed_by: Self::party(page, "Filed by"),
+            documents,
+        }
+    }
+
+    /// Files of an `index.json` listing, subfolders left out.
+    pub fn parse_listing(access_no: &str, dir_url: &str, listing: &str) -> Result<FilingIndex, serde_json::Error> {
+        let listing: DirectoryListing = serde_json::from_str(listing)?;
+
+        let documents = listing.directory.item.into_iter()
+            .filter(|item| item.kind != "folder.gif")
+            .map(|item| FilingDocument {
+                size: item.size.parse().ok(),
+                name: item.name,
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(FilingIndex {
+            access_no: access_no.to_string(),
+            dir_url: dir_url.to_string(),
+            documents,
+            ..Default::default()
+        })
+    }
+
+    /// The XML document of type `form_type` as filed, not the copy the SEC renders with a
+    /// stylesheet in a subfolder.
+    pub fn primary_xml(&self, form_type: &str) -> Option<&FilingDocument> {
+        self.documents.iter().find(|doc| {
+            doc.doc_type.eq_ignore_ascii_case(form_type) && doc.name.ends_with(".xml") && !doc.name.contains('/')
+        })
+    }
+
+    /// The document of type `doc_type` as filed, the XML one when the submission also carries
+    /// it in another format.
+    pub fn primary(&self, doc_type: &str) -> Option<&FilingDocument> {
+        self.primary_xml(doc_type).or_else(|| {
+            self.documents.iter().find(|doc| doc.doc_type.eq_ignore_ascii_case(doc_type) && !doc.name.contains('/'))
+        })
+    }
+
+    /// The SEC header of the submission as the page gives it, for a form of type `form_type`.
+    pub fn header(&self, form_type: &str) -> SubmissionHeader {
+        SubmissionHeader {
+            form_type: form_type.to_string(),
+            filed_date: self.filed_date,
+            period_of_report: self.period_of_report,
+            items: self.items.clone(),
+            filer: self.filer.clone(),
+            subject: self.subject.clone(),
+            filed_by: self.filed_by.clone(),
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.documents.iter().map(|doc| doc.name.clone()).collect()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.documents.iter().any(|doc| doc.name == name)
+    }
+
+    /// Downloads a file of the accession directory.
+    pub async fn get(&self, name: &str) -> Result<String, Box<dyn Error>> {
+        get_text(&format!("{}{name}", self.dir_url)).await
+    }
+
+    /// Cik and name of the first company of the page in the role `role`.
+    fn party(page: &str, role: &str) -> Option<(String, String)> {
+        PARTIES.captures_iter(page)
+            .find(|party| &party[2] == role)
+            .map(|party| (party[3].to_string(), Self::cell_text(&party[1])))
+    }
+
+    fn cell_text(cell: &str) -> String {
+        TAGS.replace_all(cell, "").replace("&nbsp;", " ").replace("&amp;", "&").trim().to_string()
+    }
+
+    /// A `YYYY-MM-DD` value of the form information of the page, under its `infoHead` label.
+    fn info_date(page: &str, label: &str) -> Option<NaiveDate> {
+        let pattern = Regex::new(&format!(r#"(?s)class="infoHead">{label}</div>\s*<div class="info">([0-9-]+)</div>"#)).unwrap();
+        let date = pattern.captures(page)?.get(1)?.as_str();
+
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<div class="formGrouping">
+        <div class="infoHead">Filing Date</div>
+        <div class="info">2025-02-14</div>
+        <div class="infoHead">Accepted</div>
+        <div class="info">2025-02-14 18:05:12</div>
+        <div class="infoHead">Period of Report</div>
+        <div class="info">2025-02-12</div>
+    </div>
+    <table class="tableFile" summary="Document Format Files">
+        <tr><th scope="col">Seq</th><th scope="col">Description</th><th scope="col">Document</th><th scope="col">Type</th><th scope="col">Size</th></tr>
+        <tr>
+            <td scope="row">1</td>
+            <td scope="row">FORM 4</td>
+            <td scope="row"><a href="/Archives/edgar/data/320193/000032019325000030/xslF345X05/wk-form4_1739574321.xml">wk-form4_1739574321.html</a></td>
+            <td scope="row">4</td>
+            <td scope="row">&nbsp;</td>
+        </tr>
+        <tr class="blueRow">
+            <td scope="row">1</td>
+            <td scope="row">FORM 4</td>
+            <td scope="row"><a href="/Archives/edgar/data/320193/000032019325000030/wk-form4_1739574321.xml">wk-form4_1739574321.xml</a></td>
+            <td scope="row">4</td>
+            <td scope="row">4871</td>
+        </tr>
+        <tr>
+            <td scope="row">&nbsp;</td>
+            <td scope="row">Complete submission text file</td>
+            <td scope="row"><a href="/Archives/edgar/data/320193/000032019325000030/0000320193-25-000030.txt">0000320193-25-000030.txt</a></td>
+            <td scope="row">&nbsp;</td>
+            <td scope="row">6324</td>
+        </tr>
+    </table>"#;
+
+    #[test]
+    fn index_page_documents() {
+        let index = FilingIndex::parse_page("0000320193-25-000030", "https://www.sec.gov/Archives/edgar/data/320193/000032019325000030/", PAGE);
+
+        assert_eq!(index.filed_date, NaiveDate::from_ymd_opt(2025, 2, 14));
+        assert_eq!(index.period_of_report, NaiveDate::from_ymd_opt(2025, 2, 12));
+        assert_eq!(index.names(), vec!["xslF345X05/wk-form4_1739574321.xml", "wk-form4_1739574321.xml", "0000320193-25-000030.txt"]);
+
+        let primary = index.primary_xml("4").unwrap();
+        assert_eq!(primary.seq, Some(1));
+        assert_eq!(primary.description, "FORM 4");
+        assert_eq!(primary.size, Some(4871));
+        assert_eq!(index.documents[2].seq, None);
+        assert!(index.primary_xml("4/A").is_none());
+
+        let listing = r#"{"directory":{"item":[
+            {"last-modified":"2025-02-14 18:05:12","name":"xslF345X05","type":"folder.gif","size":""},
+            {"last-modified":"2025-02-14 18:05:12","name":"wk-form4_1739574321.xml","type":"text.gif","size":"4871"},
+            {"last-modified":"2025-02-14 18:05:12","name":"0000320193-25-000030-index.html","type":"text.gif","size":""}
+        ],"name":"/Archives/edgar/data/320193/000032019325000030","parent-dir":"/Archives/edgar/data/320193/"}}"#;
+        let directory = FilingIndex::parse_listing(&index.access_no, &index.dir_url, listing).unwrap();
+        assert_eq!(directory.names(), vec!["wk-form4_1739574321.xml", "0000320193-25-000030-index.html"]);
+        assert_eq!(directory.documents[0].size, Some(4871));
+        assert!(directory.contains("wk-form4_1739574321.xml"));
+    }
+
+    #[test]
+    fn index_page_header() {
+        let page = r#"<div class="formGrouping">
+            <div class="infoHead">Filing Date</div>
+            <div class="info">2025-03-04</div>
+            <div class="infoHead">Period of Report</div>
+            <div class="info">2025-03-03</div>
+            <div class="infoHead">Items</div>
+            <div class="info">Item 5.02: Departure of Directors or Certain Officers<br />Item 9.01: Financial Statements and Exhibits</div>
+        </div>
+        <div id="filerDiv">
+            <div class="companyInfo">
+                <span class="companyName">Smith &amp; Co (Filed by)
+                <acronym title="Central Index Key">CIK</acronym>: <a href="/cgi-bin/browse-edgar?action=getcompany&amp;CIK=0001111111">0001111111 (see all company filings)</a></span>
+            </div>
+            <div class="companyInfo">
+                <span class="companyName">Acme Corp (Subject)
+                <acronym title="Central Index Key">CIK</acronym>: <a href="/cgi-bin/browse-edgar?action=getcompany&amp;CIK=0001234567">0001234567 (see all company filings)</a></span>
+            </div>
+        </div>"#;
+
+        let index = FilingIndex::parse_page("0001111111-25-000001", "https://www.sec.gov/Archives/edgar/data/1234567/000111111125000001/", page);
+        assert_eq!(index.items, vec!["Item 5.02: Departure of Directors or Certain Officers", "Item 9.01: Financial Statements and Exhibits"]);
+        assert_eq!(index.filed_by, Some(("0001111111".to_string(), "Smith & Co".to_string())));
+        assert_eq!(index.subject, Some(("0001234567".to_string(), "Acme Corp".to_string())));
+        assert_eq!(index.filer, None);
+
+        let header = index.header("SC 13D");
+        assert_eq!(header.form_type, "SC 13D");
+        assert_eq!(header.period_of_report, NaiveDate::from_ymd_opt(2025, 3, 3));
+    }
+}
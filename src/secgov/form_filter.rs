@@ -0,0 +1,85 @@
+use super::parser::index::IndexEntry;
+
+/// Which index entries the miner fetches, by form type.
+///
+/// A form type matches when it is one of the exact types or starts with one of the prefixes.
+/// Amendments (`4/A`, `SC 13D/A`...) are matched on their base type and only when turned on.
+/// A filter with no types and no prefixes matches every form.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormFilter {
+    types: Vec<String>,
+    prefixes: Vec<String>,
+    amendments: bool,
+}
+
+impl FormFilter {
+    pub fn new() -> FormFilter {
+        FormFilter::default()
+    }
+
+    /// Insider transactions only, what the miner has always fetched.
+    pub fn form4() -> FormFilter {
+        FormFilter::new().form_type("4")
+    }
+
+    pub fn form_type(mut self, form_type: &str) -> FormFilter {
+        self.types.push(form_type.to_uppercase());
+        self
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> FormFilter {
+        self.prefixes.push(prefix.to_uppercase());
+        self
+    }
+
+    pub fn amendments(mut self, amendments: bool) -> FormFilter {
+        self.amendments = amendments;
+        self
+    }
+
+    pub fn matches(&self, form_type: &str) -> bool {
+        let form_type = form_type.to_uppercase();
+
+        let base = match form_type.strip_suffix("/A") {
+            Some(_) if !self.amendments => return false,
+            Some(base) => base,
+            None => form_type.as_str(),
+        };
+
+        if self.types.is_empty() && self.prefixes.is_empty() {
+            return true;
+        }
+
+        self.types.iter().any(|t| t == base) || self.prefixes.iter().any(|p| base.starts_with(p.as_str()))
+    }
+
+    pub fn apply(&self, entries: impl IntoIterator<Item = IndexEntry>) -> Vec<IndexEntry> {
+        entries.into_iter().filter(|e| self.matches(&e.form_type)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_types_and_prefixes() {
+        let filter = FormFilter::new().form_type("4").form_type("144").prefix("SC 13");
+
+        assert!(filter.matches("4"));
+        assert!(filter.matches("144"));
+        assert!(filter.matches("SC 13D"));
+        assert!(filter.matches("SC 13G"));
+        assert!(!filter.matches("424B2"));
+        assert!(!filter.matches("8-K"));
+    }
+
+    #[test]
+    fn amendments_only_when_enabled() {
+        assert!(!FormFilter::form4().matches("4/A"));
+        assert!(FormFilter::form4().amendments(true).matches("4/A"));
+        assert!(FormFilter::new().amendments(true).matches("SC 13D/A"));
+        assert!(!FormFilter::new().matches("SC 13D/A"));
+        assert!(FormFilter::new().matches("13F-HR"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Read;
+use std::sync::Mutex;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use flate2::read::GzDecoder;
+use reqwest::header::{HeaderMap, ACCEPT_ENCODING, CONTENT_ENCODING, HOST, USER_AGENT};
+use reqwest::{Client, StatusCode, Url};
+
+use super::parser::index::{get_quarter, read_index, IndexEntry};
+use super::{body_decoder, BASEURL, SEC_USER_AGENT};
+
+/// Fetches EDGAR master indexes for a day.
+///
+/// The daily index is tried first under every name it has had over the years. A weekday with
+/// no daily index falls back to the quarterly full index filtered to that day, which is how
+/// old dates get backfilled. A 404 everywhere means the SEC published nothing that day.
+pub struct IndexClient {
+    client: Client,
+    /// Quarterly indexes already downloaded, only kept for quarters that are over.
+    quarters: Mutex<HashMap<String, Vec<IndexEntry>>>,
+}
+
+impl IndexClient {
+    pub fn new() -> IndexClient {
+        IndexClient {
+            client: Client::new(),
+            quarters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Candidate daily index urls for `date`, newest naming first.
+    pub fn daily_urls(date: NaiveDate) -> Vec<String> {
+        let dir = format!("{BASEURL}edgar/daily-index/{}/{}", date.year(), get_quarter(date));
+
+        vec![
+            format!("{dir}/master.{}.idx", date.format("%Y%m%d")),
+            format!("{dir}/master.{}.idx.gz", date.format("%Y%m%d")),
+            format!("{dir}/master.{}.idx", date.format("%y%m%d")),
+        ]
+    }
+
+    /// Quarterly full index urls for the quarter `date` falls in.
+    pub fn quarterly_urls(date: NaiveDate) -> Vec<String> {
+        let dir = format!("{BASEURL}edgar/full-index/{}/{}", date.year(), get_quarter(date));
+
+        vec![format!("{dir}/master.idx"), format!("{dir}/master.gz")]
+    }
+
+    /// Every entry filed on `date`, empty on weekends and holidays.
+    pub async fn daily_entries(&self, date: NaiveDate) -> Result<Vec<IndexEntry>, Box<dyn Error>> {
+        for url in Self::daily_urls(date) {
+            if let Some(body) = self.fetch(&url).await? {
+                return Ok(read_index(&body).collect());
+            }
+        }
+
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return Ok(Vec::new());
+        }
+
+        println!("No daily index for {date}, falling back to the quarterly index");
+        let entries = self.quarterly_entries(date).await?;
+
+        Ok(entries.into_iter().filter(|e| e.file_date == date).collect())
+    }
+
+    async fn quarterly_entries(&self, date: NaiveDate) -> Result<Vec<IndexEntry>, Box<dyn Error>> {
+        let key = format!("{}/{}", date.year(), get_quarter(date));
+        if let Some(entries) = self.quarters.lock().unwrap().get(&key) {
+            return Ok(entries.clone());
+        }
+
+        for url in Self::quarterly_urls(date) {
+            if let Some(body) = self.fetch(&url).await? {
+                let entries: Vec<IndexEntry> = read_index(&body).collect();
+
+                // the running quarter still grows every day, so it is fetched again next time
+                if !Self::same_quarter(date, chrono::Local::now().date_naive()) {
+                    self.quarters.lock().unwrap().insert(key, entries.clone());
+                }
+
+                return Ok(entries);
+            }
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn same_quarter(a: NaiveDate, b: NaiveDate) -> bool {
+        a.year() == b.year() && get_quarter(a) == get_quarter(b)
+    }
+
+    /// Downloads an index, `None` when it does not exist.
+    async fn fetch(&self, url: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, SEC_USER_AGENT.parse()?);
+        headers.insert(ACCEPT_ENCODING, "gzip".parse()?);
+        headers.insert(HOST, "www.sec.gov".parse()?);
+
+        println!("Send request to: {url}");
+        let response = self.client.get(Url::parse(url)?).headers(headers).send().await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response = response.error_for_status()?;
+        let content_encoding = response.headers().get(CONTENT_ENCODING).cloned();
+        let body = response.bytes().await?;
+        let mut data = body_decoder(body, content_encoding).await?;
+
+        // `.gz` indexes are gzip files, not just gzip encoded responses
+        if url.ends_with(".gz") {
+            let mut unzipped = Vec::new();
+            GzDecoder::new(&data[..]).read_to_end(&mut unzipped)?;
+            data = unzipped;
+        }
+
+        Ok(Some(String::from_utf8_lossy(&data).into_owned()))
+    }
+}
+
+impl Default for IndexClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_urls_use_the_requested_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 11, 5).unwrap();
+
+        assert_eq!(IndexClient::daily_urls(date), vec![
+            "https://www.sec.gov/Archives/edgar/daily-index/2024/QTR4/master.20241105.idx",
+            "https://www.sec.gov/Archives/edgar/daily-index/2024/QTR4/master.20241105.idx.gz",
+            "https://www.sec.gov/Archives/edgar/daily-index/2024/QTR4/master.241105.idx",
+        ]);
+        assert_eq!(IndexClient::quarterly_urls(date), vec![
+            "https://www.sec.gov/Archives/edgar/full-index/2024/QTR4/master.idx",
+            "https://www.sec.gov/Archives/edgar/full-index/2024/QTR4/master.gz",
+        ]);
+    }
+
+    #[test]
+    fn quarterly_and_daily_dates_both_parse() {
+        let index = "CIK|Company Name|Form Type|Date Filed|Filename\n\
+                     --------------------------------------------------------------------------------\n\
+                     1000045|NICHOLAS FINANCIAL INC|4|20241105|edgar/data/1000045/0001000045-24-000101.txt\n\
+                     1000097|KINGDON CAPITAL MANAGEMENT, L.L.C.|4|2024-11-06|edgar/data/1000097/0001000097-24-000007.txt\n";
+
+        let dates: Vec<NaiveDate> = read_index(index).map(|e| e.file_date).collect();
+        assert_eq!(dates, vec![
+            NaiveDate::from_ymd_opt(2024, 11, 5).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 11, 6).unwrap(),
+        ]);
+    }
+}
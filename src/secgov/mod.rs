@@ -1,42 +1,159 @@
 // Archivo: src/secgov/mod.rs
 
+mod filing_index;
+mod form_filter;
+mod index_client;
 mod parser;
 pub mod models;
+pub mod xbrl;
 
-use self::models::FilingTransaction;
-use self::parser::FilingDoc;
-use chrono::{Datelike, NaiveDate};
+pub use self::filing_index::FilingIndex;
+pub use self::form_filter::FormFilter;
+pub use self::index_client::IndexClient;
+pub use self::parser::error::ParseError;
+pub use self::parser::index::IndexEntry;
+
+use self::models::{BeneficialOwnership, Filing, FilingDocument, InstitutionalHolding, MaterialEvent, ProposedSale};
+use self::parser::{FilingDoc, SubmissionHeader};
+use chrono::NaiveDate;
 use flate2::read::{DeflateDecoder, GzDecoder};
-use parser::index::{extract_index_entries, get_quarter, IndexEntry};
-use reqwest::header::{HeaderMap, ACCEPT_ENCODING, HOST, USER_AGENT};
-use reqwest::{Client, RequestBuilder, Url};
+use once_cell::sync::Lazy;
+use reqwest::{Client, Url};
 use std::error::Error;
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::{Read, Write};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
 
 const BASEURL: &str = "https://www.sec.gov/Archives/";
-type Db = Arc<Mutex<Vec<FilingTransaction>>>;
+const SEC_USER_AGENT: &str = "Joordih Development jj@joordih.dev (Jordi Xavier)";
+type Db = Arc<Mutex<Vec<Filing>>>;
+
+/// When the next download may start. The SEC allows 10 requests per second and a filing can
+/// take several of them, so downloads are spaced out here rather than per batch of entries.
+static NEXT_REQUEST: Lazy<tokio::sync::Mutex<Instant>> = Lazy::new(|| tokio::sync::Mutex::new(Instant::now()));
+
+/// Downloads a document of the archives as text.
+async fn get_text(url: &str) -> Result<String, Box<dyn Error>> {
+    {
+        let mut next = NEXT_REQUEST.lock().await;
+        tokio::time::sleep_until(*next).await;
+        *next = Instant::now() + Duration::from_millis(100);
+    }
 
-pub async fn get_form(entry: &IndexEntry) -> Result<Vec<FilingTransaction>, Box<dyn Error>> {
-    let url = format!("{BASEURL}{}", entry.filepath);
     println!("url: {url}");
 
     let client = Client::new();
     let res = client.get(
-        Url::parse(&url).expect("Failed to parse valid URL")
+        Url::parse(url).expect("Failed to parse valid URL")
     )
-        .header("User-Agent", "Joordih Development jj@joordih.dev (Jordi Xavier)")
+        .header("User-Agent", SEC_USER_AGENT)
         .send()
-        .await?;
+        .await?
+        .error_for_status()?;
+
+    Ok(res.text().await?)
+}
+
+/// Downloads the full `.txt` submission of an index entry, returning its url and body.
+async fn get_submission(entry: &IndexEntry) -> Result<(String, String), Box<dyn Error>> {
+    let url = format!("{BASEURL}{}", entry.filepath);
+    let body = get_text(&url).await?;
+
+    Ok((url, body))
+}
+
+/// The accession index of an entry and the name of its document of type `doc_type`, as `find`
+/// picks it. `None` when the index cannot be read or lists no such document, the caller then
+/// reads the `.txt` submission instead.
+async fn find_document(entry: &IndexEntry, doc_type: &str, find: for<'a> fn(&'a FilingIndex, &str) -> Option<&'a FilingDocument>) -> Option<(FilingIndex, String)> {
+    let index = match FilingIndex::fetch(entry).await {
+        Ok(index) => index,
+        Err(err) => {
+            println!("Reading {} whole, its index failed: {}", entry.filepath, err);
+            return None;
+        }
+    };
 
-    let body = res.text().await?;
+    let name = find(&index, doc_type)?.name.clone();
+    Some((index, name))
+}
+
+/// The ownership filing of an index entry, with the documents its `.txt` submission embeds.
+/// An ownership submission is little more than its XML document, so the `.txt` takes a single
+/// request where the accession index and the XML would take two.
+pub async fn get_form(entry: &IndexEntry) -> Result<Filing, Box<dyn Error>> {
+    let (url, body) = get_submission(entry).await?;
 
-    FilingDoc::new(&url, &body)
+    Ok(FilingDoc::new(&url, &body)?)
 }
 
-fn save_failed(index_url: &str) {
+/// The beneficial ownership events of the Schedule 13D or 13G of an index entry, from its
+/// statement alone.
+pub async fn get_beneficial_ownership(entry: &IndexEntry) -> Result<Vec<BeneficialOwnership>, Box<dyn Error>> {
+    let Some((index, document)) = find_document(entry, &entry.form_type, FilingIndex::primary).await else {
+        let (url, body) = get_submission(entry).await?;
+        return Ok(FilingDoc::beneficial_ownership(&url, &SubmissionHeader::from_text(&body), &body)?);
+    };
+
+    let url = format!("{BASEURL}{}", entry.filepath);
+    let statement = index.get(&document).await?;
+
+    Ok(FilingDoc::beneficial_ownership(&url, &index.header(&entry.form_type), &statement)?)
+}
+
+/// The positions reported on the 13F-HR of an index entry, from its cover page and
+/// information table alone.
+pub async fn get_institutional_holdings(entry: &IndexEntry) -> Result<Vec<InstitutionalHolding>, Box<dyn Error>> {
+    let Some((index, cover)) = find_document(entry, &entry.form_type, FilingIndex::primary_xml).await else {
+        let (url, body) = get_submission(entry).await?;
+        return Ok(FilingDoc::institutional_holdings(&url, &SubmissionHeader::from_text(&body), &body)?);
+    };
+
+    let url = format!("{BASEURL}{}", entry.filepath);
+    let mut content = index.get(&cover).await?;
+    // a report without holdings has no information table
+    if let Some(table) = index.primary_xml("INFORMATION TABLE") {
+        content.push('\n');
+        content.push_str(&index.get(&table.name).await?);
+    }
+
+    Ok(FilingDoc::institutional_holdings(&url, &index.header(&entry.form_type), &content)?)
+}
+
+/// The sales announced by the Form 144 notice of an index entry, from its XML document alone.
+pub async fn get_proposed_sales(entry: &IndexEntry) -> Result<Vec<ProposedSale>, Box<dyn Error>> {
+    let Some((index, document)) = find_document(entry, &entry.form_type, FilingIndex::primary_xml).await else {
+        let (url, body) = get_submission(entry).await?;
+        return Ok(FilingDoc::proposed_sales(&url, &SubmissionHeader::from_text(&body), &body)?);
+    };
+
+    let url = format!("{BASEURL}{}", entry.filepath);
+    let notice = index.get(&document).await?;
+
+    Ok(FilingDoc::proposed_sales(&url, &index.header(&entry.form_type), &notice)?)
+}
+
+/// The items reported on the 8-K of an index entry, from its primary document alone.
+pub async fn get_material_events(entry: &IndexEntry) -> Result<Vec<MaterialEvent>, Box<dyn Error>> {
+    let Some((index, document)) = find_document(entry, &entry.form_type, FilingIndex::primary).await else {
+        let (url, body) = get_submission(entry).await?;
+        let header = SubmissionHeader::from_text(&body);
+        let document = FilingDoc::primary_document(&body, &header.form_type)
+            .ok_or_else(|| ParseError::NoXmlPayload { access_no: entry.access_no().unwrap_or_default() })?;
+        return Ok(FilingDoc::material_events(&url, &header, document)?);
+    };
+
+    let url = format!("{BASEURL}{}", entry.filepath);
+    let report = index.get(&document).await?;
+
+    Ok(FilingDoc::material_events(&url, &index.header(&entry.form_type), &report)?)
+}
+
+fn save_failed(index_url: &str, reason: &str) {
     let log_file_path = "filings/failed.txt";
 
     if let Err(err) = fs::OpenOptions::new()
@@ -45,7 +162,7 @@ fn save_failed(index_url: &str) {
         .open(log_file_path)
         .and_then(|mut file| {
             use std::io::Write;
-            writeln!(file, "Failed to proccess entry: {}", index_url)
+            writeln!(file, "Failed to proccess entry: {} ({})", index_url, reason)
         }) {
         eprintln!("Error occurred writing {} to failed.txt: {}", index_url, err);
     }
@@ -60,54 +177,42 @@ fn save_failed(index_url: &str) {
 }
 
 pub async fn process_entries(entries: &[IndexEntry], db: Db, skip: usize, take: usize) -> Result<(), Box<dyn Error>> {
+    let mut tasks = JoinSet::new();
+
     for entry in entries.iter().cloned().skip(skip).take(take) {
         let db = db.clone();
 
-        tokio::spawn(async move {
+        tasks.spawn(async move {
             let result = get_form(&entry).await;
             match result {
-                Ok(mut filings) => {
+                Ok(filing) => {
                     db.lock()
-                        .and_then(|mut v| { v.append(&mut filings); Ok(()) })
+                        .and_then(|mut v| { v.push(filing); Ok(()) })
                         .expect("Could not push to mutex db");
                 },
                 Err(err) => {
-                    println!("Error occurred for filing {}: {:?}", entry.filepath, err);
-                    save_failed(&entry.filepath);
+                    println!("Error occurred for filing {}: {}", entry.filepath, err);
+                    let reason = match err.downcast_ref::<ParseError>() {
+                        Some(parse_err) => parse_err.kind(),
+                        None => "request",
+                    };
+                    save_failed(&entry.filepath, reason);
                 }
             }
         });
     }
 
+    // every entry is in the db or failed.txt before returning
+    while let Some(task) = tasks.join_next().await {
+        task?;
+    }
+
     Ok(())
 }
 
+/// Every entry of the daily index for `date`, see [`IndexClient`].
 pub async fn get_daily_entries(date: NaiveDate) -> Result<Vec<IndexEntry>, Box<dyn Error>> {
-    let flat_date = date.format("%Y%m%d").to_string();
-    let qtr = get_quarter(date);
-    let index_url = format!(
-        "https://www.sec.gov/Archives/edgar/daily-index/{}/{}/master.20250213.idx",
-        date.year(), qtr
-    );
-
-    let client = Client::new();
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, "Joordih Development jj@joordih.dev (Jordi Xavier)".parse().unwrap());
-    headers.insert(ACCEPT_ENCODING, "gzip".parse().unwrap());
-    headers.insert(HOST, "www.sec.gov".parse().unwrap());
-
-    let request = client.get(
-        Url::parse(&index_url).expect("Failed to parse valid URL")
-    )
-        .headers(headers.clone());
-
-    println!("Send request to: {index_url}");
-    let response = RequestBuilder::send(request).await.unwrap();
-    let content_encoding = response.headers().get(reqwest::header::CONTENT_ENCODING).cloned();
-    let body = response.bytes().await.unwrap();
-
-    let decompressed_data = body_decoder(body, content_encoding).await?;
-    Ok(extract_index_entries(&String::from_utf8_lossy(&decompressed_data)))
+    IndexClient::new().daily_entries(date).await
 }
 
 async fn body_decoder(body: bytes::Bytes, content_encoding: Option<reqwest::header::HeaderValue>) -> Result<Vec<u8>, Box<dyn Error>> {
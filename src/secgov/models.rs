@@ -1,5 +1,9 @@
+use bigdecimal::BigDecimal;
 use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Copy)]
 pub enum Relationship {
@@ -9,18 +13,100 @@ pub enum Relationship {
     OFFICER,
 }
 
+/// Why a parsed value is missing, so an absent price is not confused with a literal zero.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MissingReason {
+    /// The element or its `value` is not in the document.
+    ABSENT,
+    /// The element only carries a `footnoteId`, the footnote explains the value.
+    FOOTNOTE_ONLY,
+    /// The element has text that is not a valid number or date.
+    UNPARSABLE,
+}
+
+/// Normalized role read from the free text `officerTitle` and `otherText` of a reporting owner.
+#[allow(non_camel_case_types)]
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    #[default] OTHER = 1,
+    CEO,
+    CFO,
+    COO,
+    CTO,
+    CAO,
+    PRESIDENT,
+    CHAIR,
+    GENERAL_COUNSEL,
+    VP,
+    SECRETARY,
+    TREASURER,
+}
+
+static ROLE_PATTERNS: Lazy<Vec<(Role, Regex)>> = Lazy::new(|| {
+    [
+        (Role::CEO, r"\b(CEO|CHIEF EXECUTIVE( OFFICER)?|PRINCIPAL EXECUTIVE OFFICER)\b"),
+        (Role::CFO, r"\b(CFO|CHIEF FINANCIAL( OFFICER)?|PRINCIPAL FINANCIAL OFFICER)\b"),
+        (Role::COO, r"\b(COO|CHIEF OPERATING( OFFICER)?)\b"),
+        (Role::CTO, r"\b(CTO|CHIEF TECHNOLOGY( OFFICER)?)\b"),
+        (Role::CAO, r"\b(CAO|CHIEF ACCOUNTING( OFFICER)?|PRINCIPAL ACCOUNTING OFFICER)\b"),
+        (Role::PRESIDENT, r"\bPRES(IDENT)?\b"),
+        (Role::CHAIR, r"\bCHAIR(MAN|WOMAN|PERSON)?\b"),
+        (Role::GENERAL_COUNSEL, r"\b(GENERAL COUNSEL|CHIEF LEGAL( OFFICER)?|CLO)\b"),
+        (Role::VP, r"\b(VP|EVP|SVP|VICE_PRES(IDENT)?)\b"),
+        (Role::SECRETARY, r"\bSECRETARY\b"),
+        (Role::TREASURER, r"\bTREASURER\b"),
+    ]
+    .into_iter()
+    .map(|(role, pattern)| (role, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+static VICE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bVICE[\s-]+(PRES(IDENT)?|CHAIR)").unwrap());
+
+impl Role {
+    /// Classifies a free text title such as "President & CEO" or "Pres. & COO" into every role
+    /// it names.
+    ///
+    /// "Vice President" and "Vice Chair" are collapsed first so they do not count as
+    /// `PRESIDENT` or `CHAIR`. Titles naming none of the known roles yield `OTHER`.
+    pub fn classify(title: &str) -> Vec<Role> {
+        let title = title.to_uppercase().replace('.', "");
+        if title.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let title = VICE_PATTERN.replace_all(&title, "VICE_$1");
+
+        let roles: Vec<Role> = ROLE_PATTERNS.iter()
+            .filter(|(_, pattern)| pattern.is_match(&title))
+            .map(|(role, _)| *role)
+            .collect();
+
+        if roles.is_empty() {
+            vec![Role::OTHER]
+        } else {
+            roles
+        }
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct FilingTransaction {
-    pub trans_date: NaiveDate,
+    pub trans_date: Option<NaiveDate>,
     pub form_date: NaiveDate,
     pub company: String,
     pub symbol: String,
     pub owner: String,
     pub relationship: Vec<Relationship>,
-    pub shares_traded: f32,
-    pub avg_price: f32,
-    pub amount: f32,
-    pub shares_owned: f32,
+    #[serde(default, with = "decimal::option")]
+    pub shares_traded: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub avg_price: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub amount: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub shares_owned: Option<BigDecimal>,
     pub trans_code: String,
     pub ownership_code: String,
     pub action_code: String,
@@ -30,4 +116,513 @@ pub struct FilingTransaction {
     pub form_url: String,
     pub web_url: String,
     pub access_no: String,
-}
\ No newline at end of file
+    /// Footnote ids keyed by the name of the field they annotate, e.g. `avg_price`.
+    #[serde(default)]
+    pub footnotes: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub officer_title: String,
+    #[serde(default)]
+    pub other_text: String,
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    /// Why an optional field above is `None`, keyed by field name.
+    #[serde(default)]
+    pub missing: HashMap<String, MissingReason>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct DerivativeTransaction {
+    pub trans_date: Option<NaiveDate>,
+    pub security_title: String,
+    #[serde(default, with = "decimal::option")]
+    pub conversion_price: Option<BigDecimal>,
+    pub exercise_date: Option<NaiveDate>,
+    pub expiration_date: Option<NaiveDate>,
+    pub underlying_title: String,
+    #[serde(default, with = "decimal::option")]
+    pub underlying_shares: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub shares_traded: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub avg_price: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub amount: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub shares_owned: Option<BigDecimal>,
+    pub trans_code: String,
+    pub ownership_code: String,
+    pub action_code: String,
+    /// `documentType` of the form that reported the row.
+    #[serde(default)]
+    pub form_type: String,
+    /// Footnote ids keyed by the name of the field they annotate, e.g. `conversion_price`.
+    #[serde(default)]
+    pub footnotes: HashMap<String, Vec<String>>,
+    /// Why an optional field above is `None`, keyed by field name.
+    #[serde(default)]
+    pub missing: HashMap<String, MissingReason>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReportingOwner {
+    pub cik: String,
+    pub name: String,
+    pub relationship: Vec<Relationship>,
+    pub officer_title: String,
+    pub other_text: String,
+    pub roles: Vec<Role>,
+    pub street1: String,
+    pub street2: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+}
+
+/// Position reported in a holding row, without any trade behind it.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct Holding {
+    pub security_title: String,
+    #[serde(default, with = "decimal::option")]
+    pub shares_owned: Option<BigDecimal>,
+    pub ownership_code: String,
+    pub ownership_nature: String,
+    pub derivative: bool,
+    /// Terms of a derivative holding, `None` or empty on non derivative rows.
+    #[serde(default, with = "decimal::option")]
+    pub conversion_price: Option<BigDecimal>,
+    #[serde(default)]
+    pub exercise_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub expiration_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub underlying_title: String,
+    #[serde(default, with = "decimal::option")]
+    pub underlying_shares: Option<BigDecimal>,
+    /// `documentType` of the form that reported the row.
+    #[serde(default)]
+    pub form_type: String,
+    /// Footnote ids keyed by the name of the field they annotate, e.g. `shares_owned`.
+    #[serde(default)]
+    pub footnotes: HashMap<String, Vec<String>>,
+    /// Why an optional field above is `None`, keyed by field name.
+    #[serde(default)]
+    pub missing: HashMap<String, MissingReason>,
+}
+
+/// A sale announced on a Form 144 notice, one per class of securities it covers.
+///
+/// The notice is filed under the seller's CIK before the sale happens, the Form 4 reporting
+/// the sale itself follows once it is executed.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ProposedSale {
+    pub access_no: String,
+    pub form_type: String,
+    pub form_url: String,
+    /// `FILED AS OF DATE` of the submission header.
+    #[serde(default)]
+    pub filed_date: Option<NaiveDate>,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    pub seller_cik: String,
+    pub seller_name: String,
+    /// Relationships to the issuer as written on the notice, e.g. `Officer, Director`.
+    pub relationship: String,
+    pub security_class: String,
+    pub broker: String,
+    #[serde(default, with = "decimal::option")]
+    pub shares: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub market_value: Option<BigDecimal>,
+    pub approx_sale_date: Option<NaiveDate>,
+    /// Adoption date of the 10b5-1 plan the sale is made under, if any.
+    pub plan_adoption_date: Option<NaiveDate>,
+}
+
+/// A 5%+ stake reported on a Schedule 13D (activist) or 13G (passive), one per reporting
+/// person of the cover pages.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct BeneficialOwnership {
+    pub access_no: String,
+    /// Submission type, `SC 13D` or `SCHEDULE 13D` for the XML filings, with `/A` on amendments.
+    pub form_type: String,
+    pub form_url: String,
+    #[serde(default)]
+    pub filed_date: Option<NaiveDate>,
+    /// Date of the event that requires the statement, only known for XML filings.
+    pub event_date: Option<NaiveDate>,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    pub issuer_cusip: String,
+    pub filer_cik: String,
+    pub filer_name: String,
+    pub amendment_no: Option<i32>,
+    #[serde(default, with = "decimal::option")]
+    pub percent_of_class: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub aggregate_shares: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub sole_voting: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub shared_voting: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub sole_dispositive: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub shared_dispositive: Option<BigDecimal>,
+}
+
+impl BeneficialOwnership {
+    /// Whether the index form type is a Schedule 13D or 13G, old or XML naming.
+    pub fn is_schedule(form_type: &str) -> bool {
+        let form_type = form_type.to_uppercase();
+        let base = form_type.strip_suffix("/A").unwrap_or(&form_type);
+
+        matches!(base, "SC 13D" | "SC 13G" | "SCHEDULE 13D" | "SCHEDULE 13G")
+    }
+}
+
+/// A position of an institutional manager at the end of a quarter, one per row of the
+/// information table of a 13F-HR.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct InstitutionalHolding {
+    pub access_no: String,
+    pub form_type: String,
+    /// `RESTATEMENT` or `NEW HOLDINGS` on a 13F-HR/A, `None` on the original report.
+    pub amendment_type: Option<String>,
+    pub form_url: String,
+    #[serde(default)]
+    pub filed_date: Option<NaiveDate>,
+    /// Last day of the calendar quarter the report covers.
+    pub period_of_report: NaiveDate,
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub issuer_name: String,
+    pub title_of_class: String,
+    pub cusip: String,
+    /// Market value in dollars. Reports filed before 2023 give it in thousands, those values
+    /// are scaled when the filing date is known.
+    #[serde(default, with = "decimal::option")]
+    pub market_value: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub shares: Option<BigDecimal>,
+    /// `SH` for shares or `PRN` for the principal amount of debt.
+    pub share_type: String,
+    /// `Put` or `Call` when the row is an option position.
+    pub put_call: Option<String>,
+    /// `SOLE`, `DFND` (shared-defined) or `OTR` (shared-other).
+    pub investment_discretion: String,
+    #[serde(default, with = "decimal::option")]
+    pub voting_sole: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub voting_shared: Option<BigDecimal>,
+    #[serde(default, with = "decimal::option")]
+    pub voting_none: Option<BigDecimal>,
+}
+
+impl InstitutionalHolding {
+    /// Whether the index form type is a 13F holdings report, original or amended. Notices
+    /// (13F-NT) list no holdings and are left out.
+    pub fn is_report(form_type: &str) -> bool {
+        matches!(form_type.to_uppercase().as_str(), "13F-HR" | "13F-HR/A")
+    }
+}
+
+/// A numeric fact of the XBRL instance of a periodic report, one per concept, context and unit.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct XbrlFact {
+    pub access_no: String,
+    /// Url of the submission the facts were read from, which differs between the copies of an
+    /// accession the index lists under several ciks.
+    #[serde(default)]
+    pub form_url: String,
+    /// `dei:DocumentType` of the instance, `10-K` or `10-Q` with `/A` on amendments.
+    pub form_type: String,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    /// `dei:DocumentFiscalYearFocus` and `dei:DocumentFiscalPeriodFocus` (`FY`, `Q1`...).
+    pub fiscal_year: Option<i32>,
+    pub fiscal_period: String,
+    /// Prefix of the concept namespace, `us-gaap`, `dei`, `srt` or the filer's own prefix.
+    pub taxonomy: String,
+    pub concept: String,
+    /// Standard label of the concept in `MetaLinks.json`, empty without it.
+    pub label: String,
+    /// Short name of the first report (R file) presenting the concept and its menu category
+    /// (`Statements`, `Details`...), from `MetaLinks.json`.
+    pub report: String,
+    pub report_group: String,
+    #[serde(with = "decimal")]
+    pub value: BigDecimal,
+    /// Measures of the unit, `USD`, `shares` or `USD/shares` for ratios.
+    pub unit: String,
+    /// `None` for `INF`, otherwise the decimals the value is accurate to.
+    pub decimals: Option<i32>,
+    /// `None` for instant facts, whose date is `period_end`.
+    pub period_start: Option<NaiveDate>,
+    pub period_end: NaiveDate,
+    /// `axis=member` pairs of the context, sorted and joined with `;`, empty for the default.
+    pub dimensions: String,
+}
+
+impl XbrlFact {
+    /// Whether the index form type is a 10-K or 10-Q, original or amended.
+    pub fn is_periodic_report(form_type: &str) -> bool {
+        matches!(form_type.to_uppercase().as_str(), "10-K" | "10-K/A" | "10-Q" | "10-Q/A")
+    }
+}
+
+/// A material event reported under a numbered item of an 8-K, one per item of the report.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct MaterialEvent {
+    pub access_no: String,
+    /// `8-K` or `8-K/A`.
+    pub form_type: String,
+    pub form_url: String,
+    #[serde(default)]
+    pub filed_date: Option<NaiveDate>,
+    /// Date of the earliest event reported, the period of report of the SEC header.
+    pub event_date: Option<NaiveDate>,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    /// Item number, `1.01`, `2.02`, `5.02`...
+    pub item: String,
+    pub item_title: String,
+    /// Plain text of the item section of the primary document, empty when the document
+    /// does not head it.
+    pub section: String,
+}
+
+impl MaterialEvent {
+    /// Whether the index form type is an 8-K, original or amended.
+    pub fn is_current_report(form_type: &str) -> bool {
+        matches!(form_type.to_uppercase().as_str(), "8-K" | "8-K/A")
+    }
+}
+
+/// A document of a submission as listed in its accession directory or its `.txt` submission.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FilingDocument {
+    /// Sequence of the document in the submission, `None` for the full text file and the files
+    /// the SEC generates.
+    pub seq: Option<i32>,
+    /// Path in the accession directory, e.g. `xslF345X05/wk-form4_1739574321.xml` for a copy
+    /// rendered with a stylesheet.
+    pub name: String,
+    /// `4`, `EX-99.1`, `GRAPHIC`..., empty for generated files and `index.json` listings.
+    pub doc_type: String,
+    pub description: String,
+    /// Size in bytes, `None` when the documents were read from the `.txt` submission.
+    pub size: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct Filing {
+    pub access_no: String,
+    pub form_type: String,
+    pub form_date: NaiveDate,
+    pub company: String,
+    pub symbol: String,
+    pub company_cik: String,
+    /// Every reporting owner of a joint filing, the first one being the primary filer.
+    #[serde(flatten, with = "owners")]
+    pub owners: Vec<ReportingOwner>,
+    pub form_url: String,
+    pub web_url: String,
+    pub transactions: Vec<FilingTransaction>,
+    pub derivatives: Vec<DerivativeTransaction>,
+    #[serde(default)]
+    pub holdings: Vec<Holding>,
+    /// Footnote text keyed by its id, e.g. `F1`.
+    #[serde(default)]
+    pub footnotes: HashMap<String, String>,
+    /// `FILED AS OF DATE` of the submission header, missing in old daily files.
+    #[serde(default)]
+    pub filed_date: Option<NaiveDate>,
+    /// `dateOfOriginalSubmission`, the filing date of the form an amendment restates.
+    #[serde(default)]
+    pub original_submission: Option<NaiveDate>,
+    /// The owner is no longer subject to Section 16, the box checked on the last Form 4 or 5.
+    #[serde(default)]
+    pub not_subject_to_section16: bool,
+    /// Form 5 only, holdings that should have been on a Form 3 are reported here.
+    #[serde(default)]
+    pub form3_holdings_reported: bool,
+    /// Form 5 only, transactions that should have been on a Form 4 are reported here.
+    #[serde(default)]
+    pub form4_transactions_reported: bool,
+    /// Documents of the submission from its `-index.html`, empty when it was read from the
+    /// `.txt` submission.
+    #[serde(default)]
+    pub documents: Vec<FilingDocument>,
+}
+
+impl Filing {
+    /// Groups the flat transaction rows of an old daily json file back into filings.
+    pub fn from_transactions(transactions: Vec<FilingTransaction>) -> Vec<Filing> {
+        let mut filings = Vec::<Filing>::new();
+
+        for trans in transactions {
+            match filings.iter_mut().find(|f| f.access_no == trans.access_no) {
+                Some(filing) => filing.transactions.push(trans),
+                None => filings.push(Filing {
+                    access_no: trans.access_no.clone(),
+                    form_type: trans.form_type.clone(),
+                    form_date: trans.form_date,
+                    company: trans.company.clone(),
+                    symbol: trans.symbol.clone(),
+                    company_cik: trans.company_cik.clone(),
+                    owners: vec![ReportingOwner {
+                        cik: trans.owner_cik.clone(),
+                        name: trans.owner.clone(),
+                        relationship: trans.relationship.clone(),
+                        ..Default::default()
+                    }],
+                    form_url: trans.form_url.clone(),
+                    web_url: trans.web_url.clone(),
+                    transactions: vec![trans],
+                    derivatives: Vec::new(),
+                    holdings: Vec::new(),
+                    footnotes: HashMap::new(),
+                    filed_date: None,
+                    original_submission: None,
+                    ..Default::default()
+                }),
+            }
+        }
+
+        filings
+    }
+
+    pub fn primary_owner(&self) -> Option<&ReportingOwner> {
+        self.owners.first()
+    }
+
+    pub fn is_amendment(&self) -> bool {
+        self.form_type.ends_with("/A") || self.original_submission.is_some()
+    }
+
+    /// The form type without its amendment suffix, `4` for a `4/A`.
+    pub fn base_form_type(&self) -> &str {
+        self.form_type.strip_suffix("/A").unwrap_or(&self.form_type)
+    }
+}
+
+/// Owners are written to json as an `owners` list. Daily files saved before joint filings were
+/// read hold a single `owner`, `owner_cik` and `relationship` instead, those become the only owner.
+mod owners {
+    use super::{Relationship, ReportingOwner};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct Owners<'a> {
+        owners: &'a [ReportingOwner],
+    }
+
+    #[derive(Deserialize)]
+    struct OwnersRepr {
+        owners: Option<Vec<ReportingOwner>>,
+        #[serde(default)]
+        owner: String,
+        #[serde(default)]
+        owner_cik: String,
+        #[serde(default)]
+        relationship: Vec<Relationship>,
+    }
+
+    pub fn serialize<S: Serializer>(owners: &[ReportingOwner], serializer: S) -> Result<S::Ok, S::Error> {
+        Owners { owners }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ReportingOwner>, D::Error> {
+        let repr = OwnersRepr::deserialize(deserializer)?;
+
+        Ok(match repr.owners {
+            Some(owners) => owners,
+            None if repr.owner_cik.is_empty() => Vec::new(),
+            None => vec![ReportingOwner {
+                cik: repr.owner_cik,
+                name: repr.owner,
+                relationship: repr.relationship,
+                ..Default::default()
+            }],
+        })
+    }
+}
+
+/// Decimals are written to json as strings so no precision is lost. Older daily files stored
+/// them as floats, those are still accepted and read back through their shortest representation.
+mod decimal {
+    use bigdecimal::BigDecimal;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum DecimalRepr {
+        Text(String),
+        Float(f64),
+    }
+
+    pub fn serialize<S: Serializer>(value: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigDecimal, D::Error> {
+        let text = match DecimalRepr::deserialize(deserializer)? {
+            DecimalRepr::Text(text) => text,
+            DecimalRepr::Float(float) => float.to_string(),
+        };
+
+        BigDecimal::from_str(&text).map_err(de::Error::custom)
+    }
+
+    pub mod option {
+        use bigdecimal::BigDecimal;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(value: &Option<BigDecimal>, serializer: S) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<BigDecimal>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] BigDecimal);
+
+            let value = Option::<Wrapper>::deserialize(deserializer)?;
+            Ok(value.map(|Wrapper(value)| value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_classified_from_titles() {
+        let cases: &[(&str, &[Role])] = &[
+            ("CEO", &[Role::CEO]),
+            ("CFO", &[Role::CFO]),
+            ("Chief Executive Officer", &[Role::CEO]),
+            ("President & CEO", &[Role::CEO, Role::PRESIDENT]),
+            ("Pres. & COO", &[Role::COO, Role::PRESIDENT]),
+            ("EVP, Chief Financial Officer", &[Role::CFO, Role::VP]),
+            ("Senior Vice President", &[Role::VP]),
+            ("Vice Pres., Sales", &[Role::VP]),
+            ("Vice Chairman", &[Role::OTHER]),
+            ("Chairman of the Board", &[Role::CHAIR]),
+            ("General Counsel and Secretary", &[Role::GENERAL_COUNSEL, Role::SECRETARY]),
+            ("", &[]),
+            ("   ", &[]),
+            ("See Remarks", &[Role::OTHER]),
+            ("10% owner group member", &[Role::OTHER]),
+        ];
+
+        for (title, roles) in cases {
+            assert_eq!(Role::classify(title), *roles, "{title}");
+        }
+    }
+}
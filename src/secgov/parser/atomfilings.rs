@@ -1,80 +1,138 @@
+use bigdecimal::BigDecimal;
 use chrono::NaiveDate;
 use minidom::{Element, NSChoice};
 use regex::Regex;
-use std::{error::Error, format, string::String};
+use std::{collections::HashMap, format, str::FromStr, string::String};
 
-use crate::secgov::models::{FilingTransaction, Relationship};
+use super::error::ParseError;
+use crate::secgov::models::{DerivativeTransaction, Filing, FilingTransaction, Holding, MissingReason, Relationship, ReportingOwner, Role};
+
+/// Ownership documents are only parsed for these `documentType` values.
+const SUPPORTED_TYPES: [&str; 6] = ["3", "3/A", "4", "4/A", "5", "5/A"];
 
 #[derive(Debug, Default)]
 struct XMLNode {
-    text: String
+    text: String,
+    footnotes: Vec<String>,
+    path: String
 }
 
 impl XMLNode {
-    pub fn new(el: &Element) -> XMLNode {
+    pub fn new(el: &Element, path: String) -> XMLNode {
         let mut text =  el.text().trim().to_uppercase();
         if el.has_child("value", NSChoice::Any) {
             text = el.get_child("value", NSChoice::Any).unwrap().text().trim().to_uppercase();
         }
 
-        XMLNode { text: text }
+        let footnotes = el.children()
+            .filter(|c| c.is("footnoteId", NSChoice::Any))
+            .filter_map(|c| c.attr("id"))
+            .map(|id| id.to_string())
+            .collect();
+
+        XMLNode { text: text, footnotes, path }
     }
 
-    pub fn parse_num(&self) -> f32 {
-        self.text.parse::<f32>().unwrap_or(0.0)
+    /// An empty text is either a value that is not reported or one replaced by a footnote.
+    fn missing_reason(&self) -> MissingReason {
+        if !self.text.is_empty() {
+            MissingReason::UNPARSABLE
+        } else if !self.footnotes.is_empty() {
+            MissingReason::FOOTNOTE_ONLY
+        } else {
+            MissingReason::ABSENT
+        }
     }
 
-    pub fn parse_date(&self) -> NaiveDate {
-        NaiveDate::parse_from_str(&self.text, "%Y-%m-%d").expect("Invalid date string")
+    pub fn parse_num(&self) -> Result<BigDecimal, MissingReason> {
+        BigDecimal::from_str(&self.text).map_err(|_| self.missing_reason())
+    }
+
+    pub fn parse_date(&self) -> Result<NaiveDate, ParseError> {
+        NaiveDate::parse_from_str(&self.text, "%Y-%m-%d").map_err(|_| ParseError::BadDate {
+            access_no: String::new(),
+            path: self.path.clone(),
+            value: self.text.clone(),
+        })
+    }
+
+    pub fn parse_opt_date(&self) -> Result<NaiveDate, MissingReason> {
+        NaiveDate::parse_from_str(&self.text, "%Y-%m-%d").map_err(|_| self.missing_reason())
     }
 }
 
 pub struct XMLFiling {
     pub transactions: Vec<FilingTransaction>,
-    pub url: String
+    pub url: String,
+    pub access_no: String
 }
 
 impl XMLFiling {
     pub fn new(url: &str) -> XMLFiling {
-        XMLFiling {url: url.to_string(), transactions: Vec::<FilingTransaction>::new() }
+        XMLFiling {
+            url: url.to_string(),
+            access_no: Self::parse_access_num(url).unwrap_or_default(),
+            transactions: Vec::<FilingTransaction>::new()
+        }
     }
 
     pub fn get_web_url(&self, owner_cik: &str) -> String {
-        let bare_num = self.parse_access_num().replace("-", "");
-        format!("https://www.sec.gov/Archives/edgar/data/{}/{}/{}-index.html", owner_cik, bare_num, self.parse_access_num())
+        let bare_num = self.access_no.replace("-", "");
+        format!("https://www.sec.gov/Archives/edgar/data/{}/{}/{}-index.html", owner_cik, bare_num, self.access_no)
     }
 
-    fn parse_access_num(&self) -> String {
+    pub fn parse_access_num(url: &str) -> Option<String> {
         let pattern: Regex = Regex::new(r#"[0-9]{10}-[0-9]{2}-[0-9]{6}"#).unwrap();
 
-        let result = pattern.find(&self.url)
-            .expect("Url should have valid accession number");
-
-        result.as_str().to_string()
+        pattern.find(url).map(|m| m.as_str().to_string())
     }
 
     fn get_relationship(node: &Element) -> Vec<Relationship> {
         let mut relationships = Vec::<Relationship>::new();
 
-        if Self::traverse(&node, &["reportingOwner", "reportingOwnerRelationship", "isDirector"]).unwrap_or_default().text == "1" {
+        if Self::traverse(node, &["reportingOwnerRelationship", "isDirector"]).unwrap_or_default().text == "1" {
             relationships.push(Relationship::DIRECTOR);
         }
 
-        if Self::traverse(&node, &["reportingOwner", "reportingOwnerRelationship", "isOfficer"]).unwrap_or_default().text == "1" {
+        if Self::traverse(node, &["reportingOwnerRelationship", "isOfficer"]).unwrap_or_default().text == "1" {
             relationships.push(Relationship::OFFICER);
         }
 
-        if Self::traverse(&node, &["reportingOwner", "reportingOwnerRelationship", "isTenPercentOwner"]).unwrap_or_default().text == "1" {
+        if Self::traverse(node, &["reportingOwnerRelationship", "isTenPercentOwner"]).unwrap_or_default().text == "1" {
             relationships.push(Relationship::TENPERC);
         }
 
-        if Self::traverse(&node, &["reportingOwner", "reportingOwnerRelationship", "isOther"]).unwrap_or_default().text == "1" {
+        if Self::traverse(node, &["reportingOwnerRelationship", "isOther"]).unwrap_or_default().text == "1" {
             relationships.push(Relationship::OTHER);
         }
 
         relationships
     }
 
+    fn get_owners(root: &Element) -> Vec<ReportingOwner> {
+        root.children()
+            .filter(|c| c.is("reportingOwner", NSChoice::Any))
+            .map(|node| {
+                let officer_title = Self::traverse(node, &["reportingOwnerRelationship", "officerTitle"]).unwrap_or_default().text;
+                let other_text = Self::traverse(node, &["reportingOwnerRelationship", "otherText"]).unwrap_or_default().text;
+
+                ReportingOwner {
+                    cik: Self::traverse(node, &["reportingOwnerId", "rptOwnerCik"]).unwrap_or_default().text,
+                    name: Self::traverse(node, &["reportingOwnerId", "rptOwnerName"]).unwrap_or_default().text,
+                    relationship: Self::get_relationship(node),
+                    roles: Role::classify(&format!("{officer_title} {other_text}")),
+                    officer_title,
+                    other_text,
+                    street1: Self::traverse(node, &["reportingOwnerAddress", "rptOwnerStreet1"]).unwrap_or_default().text,
+                    street2: Self::traverse(node, &["reportingOwnerAddress", "rptOwnerStreet2"]).unwrap_or_default().text,
+                    city: Self::traverse(node, &["reportingOwnerAddress", "rptOwnerCity"]).unwrap_or_default().text,
+                    state: Self::traverse(node, &["reportingOwnerAddress", "rptOwnerState"]).unwrap_or_default().text,
+                    zip_code: Self::traverse(node, &["reportingOwnerAddress", "rptOwnerZipCode"]).unwrap_or_default().text,
+                }
+            })
+            .collect()
+    }
+
     fn traverse(root: &Element, path: &[&str]) -> Option<XMLNode> {
         let mut pos = Option::None;
         let mut prev = root;
@@ -90,7 +148,7 @@ impl XMLFiling {
 
         match pos {
             Some(el) => {
-                return Some(XMLNode::new(el));
+                return Some(XMLNode::new(el, format!("{}/{}", root.name(), path.join("/"))));
             },
             None => {
                 return Option::None;
@@ -98,60 +156,521 @@ impl XMLFiling {
         }
     }
 
-    pub fn extract_transactions(&mut self, xml_input: &str) -> Result<Vec<FilingTransaction>, Box<dyn Error>>{
+    /// A checkbox of the document, written as `1`/`0` or `true`/`false`.
+    fn flag(root: &Element, path: &[&str]) -> bool {
+        Self::traverse(root, path).is_some_and(|n| n.text == "1" || n.text == "TRUE")
+    }
+
+    /// Like [`Self::traverse`] but a missing element is an error.
+    fn require(root: &Element, path: &[&str]) -> Result<XMLNode, ParseError> {
+        Self::traverse(root, path).ok_or_else(|| ParseError::MissingElement {
+            access_no: String::new(),
+            path: format!("{}/{}", root.name(), path.join("/")),
+        })
+    }
+
+    pub fn extract_filing(&mut self, xml_input: &str) -> Result<Filing, ParseError> {
+        self.parse_filing(xml_input)
+            .map_err(|err| err.with_access_no(&self.access_no))
+    }
+
+    fn parse_filing(&self, xml_input: &str) -> Result<Filing, ParseError> {
+        let root: Element = xml_input.parse().map_err(|err: minidom::Error| ParseError::MalformedXml {
+            access_no: String::new(),
+            reason: err.to_string(),
+        })?;
+
+        let form_type = Self::require(&root, &["documentType"])?.text;
+        if !SUPPORTED_TYPES.contains(&form_type.as_str()) {
+            return Err(ParseError::UnsupportedDocumentType { access_no: String::new(), document_type: form_type });
+        }
+
+        let owners = Self::get_owners(&root);
+        let primary_owner = owners.first()
+            .ok_or_else(|| ParseError::MissingElement {
+                access_no: String::new(),
+                path: "ownershipDocument/reportingOwner".to_string(),
+            })?;
+
+        let mut filing = Filing {
+            access_no: self.access_no.clone(),
+            form_type,
+            form_date: Self::require(&root, &["periodOfReport"])?.parse_date()?,
+            company: Self::require(&root, &["issuer", "issuerName"])?.text,
+            symbol: Self::traverse(&root, &["issuer", "issuerTradingSymbol"]).unwrap_or_default().text,
+            company_cik: Self::require(&root, &["issuer", "issuerCik"])?.text,
+            web_url: self.get_web_url(&primary_owner.cik),
+            owners: owners.clone(),
+            form_url: self.url.clone(),
+            transactions: Vec::new(),
+            derivatives: Vec::new(),
+            holdings: Vec::new(),
+            footnotes: Self::extract_footnotes(&root),
+            filed_date: None,
+            original_submission: Self::traverse(&root, &["dateOfOriginalSubmission"]).and_then(|n| n.parse_opt_date().ok()),
+            not_subject_to_section16: Self::flag(&root, &["notSubjectToSection16"]),
+            form3_holdings_reported: Self::flag(&root, &["form3HoldingsReported"]),
+            form4_transactions_reported: Self::flag(&root, &["form4TransactionsReported"]),
+            documents: Vec::new(),
+        };
+
+        // rows are attributed to the primary owner, the other owners of a joint filing are linked to its form
+        filing.transactions = Self::extract_transactions(&root, &filing, primary_owner)?;
+        filing.derivatives = Self::extract_derivatives(&root, &filing.form_type)?;
+        filing.holdings = Self::extract_holdings(&root, &filing.form_type)?;
+
+        // a Form 3 can state that the insider owns nothing, which is a baseline of zero
+        let no_securities = Self::flag(&root, &["noSecuritiesOwned"]);
+
+        if filing.transactions.is_empty() && filing.derivatives.is_empty() && filing.holdings.is_empty() && !no_securities {
+            return Err(ParseError::MissingElement {
+                access_no: String::new(),
+                path: "ownershipDocument/nonDerivativeTable|derivativeTable".to_string(),
+            });
+        }
+
+        Ok(filing)
+    }
+
+    fn extract_footnotes(root: &Element) -> HashMap<String, String> {
+        let mut footnotes = HashMap::<String, String>::new();
+
+        if let Some(table) = root.get_child("footnotes", NSChoice::Any) {
+            for child in table.children() {
+                if let Some(id) = child.attr("id").filter(|_| child.is("footnote", NSChoice::Any)) {
+                    footnotes.insert(id.to_string(), child.text().trim().to_string());
+                }
+            }
+        }
+
+        footnotes
+    }
+
+    /// Maps each field name to the footnote ids attached to the element it was read from.
+    fn field_footnotes(fields: &[(&str, &XMLNode)]) -> HashMap<String, Vec<String>> {
+        fields.iter()
+            .filter(|(_, node)| !node.footnotes.is_empty())
+            .map(|(field, node)| (field.to_string(), node.footnotes.clone()))
+            .collect()
+    }
+
+    /// Keeps the value of an optional field, or records why it is missing.
+    fn optional<T>(missing: &mut HashMap<String, MissingReason>, field: &str, value: Result<T, MissingReason>) -> Option<T> {
+        match value {
+            Ok(value) => Some(value),
+            Err(reason) => {
+                missing.insert(field.to_string(), reason);
+                None
+            }
+        }
+    }
+
+    /// The amount is only known when both of its factors are, otherwise it inherits their reason.
+    fn amount(missing: &mut HashMap<String, MissingReason>, shares: &Option<BigDecimal>, price: &Option<BigDecimal>) -> Option<BigDecimal> {
+        match (shares, price) {
+            (Some(shares), Some(price)) => Some(shares * price),
+            _ => {
+                let reason = missing.get("avg_price")
+                    .or_else(|| missing.get("shares_traded"))
+                    .copied()
+                    .unwrap_or(MissingReason::ABSENT);
+                missing.insert("amount".to_string(), reason);
+                None
+            }
+        }
+    }
+
+    fn extract_transactions(root: &Element, filing: &Filing, owner: &ReportingOwner) -> Result<Vec<FilingTransaction>, ParseError> {
         let mut transactions = Vec::<FilingTransaction>::new();
-        let root: Element = xml_input.parse().unwrap();
-
-        let access_no = self.parse_access_num();
-        let company_cik = Self::traverse(&root, &["issuer", "issuerCik"]).unwrap().text;
-        let rpt_owner_cik = Self::traverse(&root, &["reportingOwner", "reportingOwnerId", "rptOwnerCik"]).unwrap().text;
-        let form_type = Self::traverse(&root, &["documentType"]).unwrap().text;
-        let company = Self::traverse(&root, &["issuer", "issuerName"]).unwrap().text;
-        let symbol =  Self::traverse(&root, &["issuer", "issuerTradingSymbol"]).unwrap().text;
-        let owner = Self::traverse(&root, &["reportingOwner", "reportingOwnerId", "rptOwnerName"]).unwrap().text;
-        let relationships = Self::get_relationship(&root);
-        let form_date = Self::traverse(&root, &["periodOfReport"]).unwrap().parse_date();
-        let web_url = self.get_web_url(&rpt_owner_cik);
-
-        if let Some(non_derivation) = Self::traverse(&root, &["nonDerivativeTable", "nonDerivativeTransaction"]) {
-            let table = root
-                .get_child("nonDerivativeTable", NSChoice::Any)
-                .expect("Filing should have a non derivative table");
 
+        if let Some(table) = root.get_child("nonDerivativeTable", NSChoice::Any) {
             for child in table.children() {
                 if child.is("nonDerivativeTransaction", NSChoice::Any) {
-                    let shares_traded = Self::traverse(&child, &["transactionAmounts", "transactionShares"]).unwrap().parse_num();
-                    let avg_price = Self::traverse(&child, &["transactionAmounts", "transactionPricePerShare"]).unwrap().parse_num();
-
-                    let filing = FilingTransaction {
-                        web_url: web_url.clone(),
-                        form_url: self.url.clone(),
-                        access_no: access_no.clone(),
-                        form_date: form_date.clone(),
-                        company_cik:  company_cik.clone(),
-                        owner_cik: rpt_owner_cik.clone(),
-                        form_type: form_type.clone(),
-                        company: company.clone(),
-                        symbol: symbol.clone(),
-                        owner: owner.clone(),
-                        shares_traded: shares_traded,
-                        avg_price: avg_price,
-                        amount: shares_traded * avg_price,
-                        shares_owned: Self::traverse(&child, &["postTransactionAmounts", "sharesOwnedFollowingTransaction"]).unwrap().parse_num(),
-                        trans_date: Self::traverse(&child, &["transactionDate"]).unwrap().parse_date(),
-                        relationship: relationships.clone(),
-                        action_code: Self::traverse(&child, &["transactionAmounts", "transactionAcquiredDisposedCode"]).unwrap().text,
-                        ownership_code: Self::traverse(&child, &["ownershipNature", "directOrIndirectOwnership"]).unwrap().text,
-                        trans_code: Self::traverse(&child, &["transactionCoding", "transactionCode"]).unwrap().text
+                    let shares_node = Self::traverse(child, &["transactionAmounts", "transactionShares"]).unwrap_or_default();
+                    let price_node = Self::traverse(child, &["transactionAmounts", "transactionPricePerShare"]).unwrap_or_default();
+                    let owned_node = Self::traverse(child, &["postTransactionAmounts", "sharesOwnedFollowingTransaction"]).unwrap_or_default();
+                    let date_node = Self::traverse(child, &["transactionDate"]).unwrap_or_default();
+                    let ownership_node = Self::require(child, &["ownershipNature", "directOrIndirectOwnership"])?;
+
+                    let mut missing = HashMap::<String, MissingReason>::new();
+                    let shares_traded = Self::optional(&mut missing, "shares_traded", shares_node.parse_num());
+                    let avg_price = Self::optional(&mut missing, "avg_price", price_node.parse_num());
+                    let amount = Self::amount(&mut missing, &shares_traded, &avg_price);
+                    let shares_owned = Self::optional(&mut missing, "shares_owned", owned_node.parse_num());
+                    let trans_date = Self::optional(&mut missing, "trans_date", date_node.parse_opt_date());
+
+                    let footnotes = Self::field_footnotes(&[
+                        ("shares_traded", &shares_node),
+                        ("avg_price", &price_node),
+                        ("shares_owned", &owned_node),
+                        ("trans_date", &date_node),
+                        ("ownership_code", &ownership_node),
+                    ]);
+
+                    let trans = FilingTransaction {
+                        web_url: filing.web_url.clone(),
+                        form_url: filing.form_url.clone(),
+                        access_no: filing.access_no.clone(),
+                        form_date: filing.form_date,
+                        company_cik: filing.company_cik.clone(),
+                        owner_cik: owner.cik.clone(),
+                        form_type: filing.form_type.clone(),
+                        company: filing.company.clone(),
+                        symbol: filing.symbol.clone(),
+                        owner: owner.name.clone(),
+                        shares_traded,
+                        avg_price,
+                        amount,
+                        shares_owned,
+                        trans_date,
+                        relationship: owner.relationship.clone(),
+                        action_code: Self::require(child, &["transactionAmounts", "transactionAcquiredDisposedCode"])?.text,
+                        ownership_code: ownership_node.text,
+                        trans_code: Self::require(child, &["transactionCoding", "transactionCode"])?.text,
+                        footnotes,
+                        officer_title: owner.officer_title.clone(),
+                        other_text: owner.other_text.clone(),
+                        roles: owner.roles.clone(),
+                        missing,
                     };
 
-                    transactions.push(filing);
+                    transactions.push(trans);
+                }
+            }
+        }
+
+        Ok(transactions)
+    }
+
+    fn extract_derivatives(root: &Element, form_type: &str) -> Result<Vec<DerivativeTransaction>, ParseError> {
+        let mut derivatives = Vec::<DerivativeTransaction>::new();
+
+        if let Some(table) = root.get_child("derivativeTable", NSChoice::Any) {
+            for child in table.children() {
+                if child.is("derivativeTransaction", NSChoice::Any) {
+                    let node = |path: &[&str]| Self::traverse(child, path).unwrap_or_default();
+
+                    let shares_node = node(&["transactionAmounts", "transactionShares"]);
+                    let price_node = node(&["transactionAmounts", "transactionPricePerShare"]);
+                    let owned_node = node(&["postTransactionAmounts", "sharesOwnedFollowingTransaction"]);
+                    let date_node = node(&["transactionDate"]);
+                    let conversion_node = node(&["conversionOrExercisePrice"]);
+                    let exercise_node = node(&["exerciseDate"]);
+                    let expiration_node = node(&["expirationDate"]);
+                    let underlying_node = node(&["underlyingSecurity", "underlyingSecurityShares"]);
+                    let ownership_node = node(&["ownershipNature", "directOrIndirectOwnership"]);
+
+                    let mut missing = HashMap::<String, MissingReason>::new();
+                    let shares_traded = Self::optional(&mut missing, "shares_traded", shares_node.parse_num());
+                    let avg_price = Self::optional(&mut missing, "avg_price", price_node.parse_num());
+                    let amount = Self::amount(&mut missing, &shares_traded, &avg_price);
+
+                    let footnotes = Self::field_footnotes(&[
+                        ("shares_traded", &shares_node),
+                        ("avg_price", &price_node),
+                        ("shares_owned", &owned_node),
+                        ("trans_date", &date_node),
+                        ("conversion_price", &conversion_node),
+                        ("exercise_date", &exercise_node),
+                        ("expiration_date", &expiration_node),
+                        ("underlying_shares", &underlying_node),
+                        ("ownership_code", &ownership_node),
+                    ]);
+
+                    let trans = DerivativeTransaction {
+                        trans_date: Self::optional(&mut missing, "trans_date", date_node.parse_opt_date()),
+                        security_title: node(&["securityTitle"]).text,
+                        conversion_price: Self::optional(&mut missing, "conversion_price", conversion_node.parse_num()),
+                        exercise_date: Self::optional(&mut missing, "exercise_date", exercise_node.parse_opt_date()),
+                        expiration_date: Self::optional(&mut missing, "expiration_date", expiration_node.parse_opt_date()),
+                        underlying_title: node(&["underlyingSecurity", "underlyingSecurityTitle"]).text,
+                        underlying_shares: Self::optional(&mut missing, "underlying_shares", underlying_node.parse_num()),
+                        shares_traded,
+                        avg_price,
+                        amount,
+                        shares_owned: Self::optional(&mut missing, "shares_owned", owned_node.parse_num()),
+                        action_code: node(&["transactionAmounts", "transactionAcquiredDisposedCode"]).text,
+                        ownership_code: ownership_node.text,
+                        trans_code: node(&["transactionCoding", "transactionCode"]).text,
+                        form_type: form_type.to_string(),
+                        footnotes,
+                        missing,
+                    };
+
+                    derivatives.push(trans);
+                }
+            }
+        }
+
+        Ok(derivatives)
+    }
+
+    fn extract_holdings(root: &Element, form_type: &str) -> Result<Vec<Holding>, ParseError> {
+        let mut holdings = Vec::<Holding>::new();
+
+        let tables = [
+            ("nonDerivativeTable", "nonDerivativeHolding", false),
+            ("derivativeTable", "derivativeHolding", true),
+        ];
+
+        for (table_name, row_name, derivative) in tables {
+            if let Some(table) = root.get_child(table_name, NSChoice::Any) {
+                for child in table.children() {
+                    if child.is(row_name, NSChoice::Any) {
+                        let node = |path: &[&str]| Self::traverse(child, path).unwrap_or_default();
+
+                        let mut missing = HashMap::<String, MissingReason>::new();
+                        // Form 3 derivative holdings give their size as the underlying shares
+                        let owned_node = match Self::traverse(child, &["postTransactionAmounts", "sharesOwnedFollowingTransaction"]) {
+                            Some(owned) => owned,
+                            None if derivative => node(&["underlyingSecurity", "underlyingSecurityShares"]),
+                            None => XMLNode::default(),
+                        };
+                        let shares_owned = Self::optional(&mut missing, "shares_owned", owned_node.parse_num());
+                        let ownership_node = node(&["ownershipNature", "directOrIndirectOwnership"]);
+                        let nature_node = node(&["ownershipNature", "natureOfOwnership"]);
+                        // the terms of the security only exist on derivative rows
+                        let term = |path: &[&str]| if derivative { node(path) } else { XMLNode::default() };
+                        let conversion_node = term(&["conversionOrExercisePrice"]);
+                        let exercise_node = term(&["exerciseDate"]);
+                        let expiration_node = term(&["expirationDate"]);
+                        let underlying_node = term(&["underlyingSecurity", "underlyingSecurityShares"]);
+
+                        let footnotes = Self::field_footnotes(&[
+                            ("shares_owned", &owned_node),
+                            ("ownership_code", &ownership_node),
+                            ("ownership_nature", &nature_node),
+                            ("conversion_price", &conversion_node),
+                            ("exercise_date", &exercise_node),
+                            ("expiration_date", &expiration_node),
+                            ("underlying_shares", &underlying_node),
+                        ]);
+
+                        let mut holding = Holding {
+                            security_title: node(&["securityTitle"]).text,
+                            shares_owned,
+                            ownership_code: ownership_node.text,
+                            ownership_nature: nature_node.text,
+                            derivative,
+                            form_type: form_type.to_string(),
+                            footnotes,
+                            ..Default::default()
+                        };
+
+                        if derivative {
+                            holding.conversion_price = Self::optional(&mut missing, "conversion_price", conversion_node.parse_num());
+                            holding.exercise_date = Self::optional(&mut missing, "exercise_date", exercise_node.parse_opt_date());
+                            holding.expiration_date = Self::optional(&mut missing, "expiration_date", expiration_node.parse_opt_date());
+                            holding.underlying_title = term(&["underlyingSecurity", "underlyingSecurityTitle"]).text;
+                            holding.underlying_shares = Self::optional(&mut missing, "underlying_shares", underlying_node.parse_num());
+                        }
+
+                        holding.missing = missing;
+                        holdings.push(holding);
+                    }
                 }
             }
-            Ok(transactions)
-        } else {
-            Err("Filing does not have a non derivative table".into())
         }
 
+        Ok(holdings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORM5: &str = r#"<ownershipDocument xmlns="">
+        <schemaVersion>X0508</schemaVersion>
+        <documentType>5</documentType>
+        <periodOfReport>2024-12-31</periodOfReport>
+        <notSubjectToSection16>0</notSubjectToSection16>
+        <form3HoldingsReported>0</form3HoldingsReported>
+        <form4TransactionsReported>1</form4TransactionsReported>
+        <issuer>
+            <issuerCik>0000320193</issuerCik>
+            <issuerName>Apple Inc.</issuerName>
+            <issuerTradingSymbol>AAPL</issuerTradingSymbol>
+        </issuer>
+        <reportingOwner>
+            <reportingOwnerId>
+                <rptOwnerCik>0001214156</rptOwnerCik>
+                <rptOwnerName>COOK TIMOTHY D</rptOwnerName>
+            </reportingOwnerId>
+            <reportingOwnerRelationship>
+                <isOfficer>1</isOfficer>
+                <officerTitle>Chief Executive Officer</officerTitle>
+            </reportingOwnerRelationship>
+        </reportingOwner>
+        <nonDerivativeTable>
+            <nonDerivativeTransaction>
+                <securityTitle><value>Common Stock</value></securityTitle>
+                <transactionDate><value>2024-12-20</value></transactionDate>
+                <transactionCoding>
+                    <transactionFormType>5</transactionFormType>
+                    <transactionCode>G</transactionCode>
+                    <equitySwapInvolved>0</equitySwapInvolved>
+                </transactionCoding>
+                <transactionAmounts>
+                    <transactionShares><value>1000</value></transactionShares>
+                    <transactionPricePerShare><value>0</value></transactionPricePerShare>
+                    <transactionAcquiredDisposedCode><value>D</value></transactionAcquiredDisposedCode>
+                </transactionAmounts>
+                <postTransactionAmounts>
+                    <sharesOwnedFollowingTransaction><value>3280000</value></sharesOwnedFollowingTransaction>
+                </postTransactionAmounts>
+                <ownershipNature>
+                    <directOrIndirectOwnership><value>D</value></directOrIndirectOwnership>
+                </ownershipNature>
+            </nonDerivativeTransaction>
+            <nonDerivativeHolding>
+                <securityTitle><value>Common Stock</value></securityTitle>
+                <postTransactionAmounts>
+                    <sharesOwnedFollowingTransaction><value>12000</value></sharesOwnedFollowingTransaction>
+                </postTransactionAmounts>
+                <ownershipNature>
+                    <directOrIndirectOwnership><value>I</value></directOrIndirectOwnership>
+                    <natureOfOwnership><value>By Trust</value></natureOfOwnership>
+                </ownershipNature>
+            </nonDerivativeHolding>
+        </nonDerivativeTable>
+    </ownershipDocument>"#;
+
+    #[test]
+    fn form5_rows_and_flags() {
+        let mut parser = XMLFiling::new("https://www.sec.gov/Archives/edgar/data/320193/000032019325000001/0000320193-25-000001.xml");
+        let filing = parser.extract_filing(FORM5).unwrap();
+
+        assert_eq!(filing.form_type, "5");
+        assert!(!filing.not_subject_to_section16);
+        assert!(!filing.form3_holdings_reported);
+        assert!(filing.form4_transactions_reported);
+
+        assert_eq!(filing.transactions.len(), 1);
+        assert_eq!(filing.transactions[0].trans_code, "G");
+        assert_eq!(filing.transactions[0].form_type, "5");
+
+        assert_eq!(filing.holdings.len(), 1);
+        assert_eq!(filing.holdings[0].form_type, "5");
+        assert_eq!(filing.holdings[0].shares_owned, BigDecimal::from_str("12000").ok());
+    }
+
+    const FORM3: &str = r#"<ownershipDocument xmlns="">
+        <schemaVersion>X0206</schemaVersion>
+        <documentType>3</documentType>
+        <periodOfReport>2025-01-02</periodOfReport>
+        <noSecuritiesOwned>0</noSecuritiesOwned>
+        <issuer>
+            <issuerCik>0001045810</issuerCik>
+            <issuerName>NVIDIA CORP</issuerName>
+            <issuerTradingSymbol>NVDA</issuerTradingSymbol>
+        </issuer>
+        <reportingOwner>
+            <reportingOwnerId>
+                <rptOwnerCik>0001999999</rptOwnerCik>
+                <rptOwnerName>DOE JANE</rptOwnerName>
+            </reportingOwnerId>
+            <reportingOwnerRelationship>
+                <isDirector>1</isDirector>
+            </reportingOwnerRelationship>
+        </reportingOwner>
+        <derivativeTable>
+            <derivativeHolding>
+                <securityTitle><value>Stock Option (right to buy)</value></securityTitle>
+                <conversionOrExercisePrice><value>120.50</value></conversionOrExercisePrice>
+                <exerciseDate><footnoteId id="F1"/></exerciseDate>
+                <expirationDate><value>2034-03-01</value></expirationDate>
+                <underlyingSecurity>
+                    <underlyingSecurityTitle><value>Common Stock</value></underlyingSecurityTitle>
+                    <underlyingSecurityShares><value>8000</value></underlyingSecurityShares>
+                </underlyingSecurity>
+                <ownershipNature>
+                    <directOrIndirectOwnership><value>D</value></directOrIndirectOwnership>
+                </ownershipNature>
+            </derivativeHolding>
+        </derivativeTable>
+        <footnotes>
+            <footnote id="F1">The option vests in four equal annual installments.</footnote>
+        </footnotes>
+    </ownershipDocument>"#;
+
+    #[test]
+    fn form3_derivative_holding_terms() {
+        let mut parser = XMLFiling::new("https://www.sec.gov/Archives/edgar/data/1045810/000104581025000002/0001045810-25-000002.xml");
+        let filing = parser.extract_filing(FORM3).unwrap();
+
+        assert_eq!(filing.holdings.len(), 1);
+        let option = &filing.holdings[0];
+        assert!(option.derivative);
+        assert_eq!(option.conversion_price, BigDecimal::from_str("120.50").ok());
+        assert_eq!(option.exercise_date, None);
+        assert_eq!(option.missing.get("exercise_date"), Some(&MissingReason::FOOTNOTE_ONLY));
+        assert_eq!(option.expiration_date, NaiveDate::from_ymd_opt(2034, 3, 1));
+        assert_eq!(option.underlying_title, "COMMON STOCK");
+        assert_eq!(option.underlying_shares, BigDecimal::from_str("8000").ok());
+        assert_eq!(option.shares_owned, option.underlying_shares);
+        assert_eq!(option.footnotes.get("exercise_date"), Some(&vec!["F1".to_string()]));
+    }
+
+    const FORM4_DERIVATIVE: &str = r#"<ownershipDocument xmlns="">
+        <schemaVersion>X0508</schemaVersion>
+        <documentType>4</documentType>
+        <periodOfReport>2025-02-14</periodOfReport>
+        <issuer>
+            <issuerCik>0001045810</issuerCik>
+            <issuerName>NVIDIA CORP</issuerName>
+            <issuerTradingSymbol>NVDA</issuerTradingSymbol>
+        </issuer>
+        <reportingOwner>
+            <reportingOwnerId>
+                <rptOwnerCik>0001999999</rptOwnerCik>
+                <rptOwnerName>DOE JANE</rptOwnerName>
+            </reportingOwnerId>
+            <reportingOwnerRelationship>
+                <isDirector>1</isDirector>
+            </reportingOwnerRelationship>
+        </reportingOwner>
+        <derivativeTable>
+            <derivativeTransaction>
+                <securityTitle><value>Restricted Stock Units</value></securityTitle>
+                <conversionOrExercisePrice><footnoteId id="F1"/></conversionOrExercisePrice>
+                <transactionDate><value>2025-02-14</value></transactionDate>
+                <transactionCoding><transactionCode>M</transactionCode></transactionCoding>
+                <transactionAmounts>
+                    <transactionShares><value>500</value></transactionShares>
+                    <transactionPricePerShare><value>0</value><footnoteId id="F1"/></transactionPricePerShare>
+                    <transactionAcquiredDisposedCode><value>D</value></transactionAcquiredDisposedCode>
+                </transactionAmounts>
+                <underlyingSecurity>
+                    <underlyingSecurityTitle><value>Common Stock</value></underlyingSecurityTitle>
+                    <underlyingSecurityShares><value>500</value></underlyingSecurityShares>
+                </underlyingSecurity>
+                <postTransactionAmounts>
+                    <sharesOwnedFollowingTransaction><value>1500</value><footnoteId id="F2"/></sharesOwnedFollowingTransaction>
+                </postTransactionAmounts>
+                <ownershipNature>
+                    <directOrIndirectOwnership><value>D</value></directOrIndirectOwnership>
+                </ownershipNature>
+            </derivativeTransaction>
+        </derivativeTable>
+        <footnotes>
+            <footnote id="F1">Each restricted stock unit converts into one share of common stock.</footnote>
+            <footnote id="F2">The remaining units vest quarterly.</footnote>
+        </footnotes>
+    </ownershipDocument>"#;
+
+    #[test]
+    fn derivative_transaction_footnotes() {
+        let mut parser = XMLFiling::new("https://www.sec.gov/Archives/edgar/data/1045810/000104581025000003/0001045810-25-000003.xml");
+        let filing = parser.extract_filing(FORM4_DERIVATIVE).unwrap();
+
+        assert_eq!(filing.derivatives.len(), 1);
+        let units = &filing.derivatives[0];
+        assert_eq!(units.conversion_price, None);
+        assert_eq!(units.footnotes.get("conversion_price"), Some(&vec!["F1".to_string()]));
+        assert_eq!(units.footnotes.get("avg_price"), Some(&vec!["F1".to_string()]));
+        assert_eq!(units.footnotes.get("shares_owned"), Some(&vec!["F2".to_string()]));
+        assert!(!units.footnotes.contains_key("shares_traded"));
+        assert_eq!(filing.footnotes.len(), 2);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reason an ownership document could not be turned into a [`Filing`](crate::secgov::models::Filing).
+///
+/// Every variant carries the accession number of the submission so failures can be
+/// classified and retried without re-reading the raw `.txt` file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    MissingElement { access_no: String, path: String },
+    BadDate { access_no: String, path: String, value: String },
+    BadNumber { access_no: String, path: String, value: String },
+    MalformedXml { access_no: String, reason: String },
+    NoXmlPayload { access_no: String },
+    UnsupportedDocumentType { access_no: String, document_type: String },
+}
+
+impl ParseError {
+    pub fn access_no(&self) -> &str {
+        match self {
+            ParseError::MissingElement { access_no, .. }
+            | ParseError::BadDate { access_no, .. }
+            | ParseError::BadNumber { access_no, .. }
+            | ParseError::MalformedXml { access_no, .. }
+            | ParseError::NoXmlPayload { access_no }
+            | ParseError::UnsupportedDocumentType { access_no, .. } => access_no,
+        }
+    }
+
+    /// Short name of the variant, used when logging failed entries.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ParseError::MissingElement { .. } => "missing_element",
+            ParseError::BadDate { .. } => "bad_date",
+            ParseError::BadNumber { .. } => "bad_number",
+            ParseError::MalformedXml { .. } => "malformed_xml",
+            ParseError::NoXmlPayload { .. } => "no_xml_payload",
+            ParseError::UnsupportedDocumentType { .. } => "unsupported_document_type",
+        }
+    }
+
+    /// Errors raised while walking the XML tree do not know which submission they belong to,
+    /// the caller fills the accession number in once it bubbles up.
+    pub(crate) fn with_access_no(mut self, value: &str) -> ParseError {
+        match &mut self {
+            ParseError::MissingElement { access_no, .. }
+            | ParseError::BadDate { access_no, .. }
+            | ParseError::BadNumber { access_no, .. }
+            | ParseError::MalformedXml { access_no, .. }
+            | ParseError::NoXmlPayload { access_no }
+            | ParseError::UnsupportedDocumentType { access_no, .. } => *access_no = value.to_string(),
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingElement { access_no, path } =>
+                write!(f, "{access_no}: missing element {path}"),
+            ParseError::BadDate { access_no, path, value } =>
+                write!(f, "{access_no}: invalid date '{value}' at {path}"),
+            ParseError::BadNumber { access_no, path, value } =>
+                write!(f, "{access_no}: invalid number '{value}' at {path}"),
+            ParseError::MalformedXml { access_no, reason } =>
+                write!(f, "{access_no}: malformed XML: {reason}"),
+            ParseError::NoXmlPayload { access_no } =>
+                write!(f, "{access_no}: submission does not contain an XML document"),
+            ParseError::UnsupportedDocumentType { access_no, document_type } =>
+                write!(f, "{access_no}: unsupported document type {document_type}"),
+        }
+    }
+}
+
+impl Error for ParseError {}
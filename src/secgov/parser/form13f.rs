@@ -0,0 +1,240 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use minidom::{Element, NSChoice};
+use std::str::FromStr;
+
+use super::atomfilings::XMLFiling;
+use super::error::ParseError;
+use super::xml;
+use crate::secgov::models::InstitutionalHolding;
+
+/// 13F-HR holdings report, an `edgarSubmission` cover page followed by the information table
+/// as a separate XML document of the same submission.
+pub struct XMLForm13F {
+    pub url: String,
+    pub access_no: String,
+}
+
+impl XMLForm13F {
+    pub fn new(url: &str) -> XMLForm13F {
+        XMLForm13F {
+            url: url.to_string(),
+            access_no: XMLFiling::parse_access_num(url).unwrap_or_default(),
+        }
+    }
+
+    /// One holding per `infoTable` row, carrying the manager and quarter of the cover page.
+    /// `table_input` is `None` when the submission has no information table, which is only
+    /// accepted if the cover page reports no entries.
+    pub fn extract_holdings(&self, cover_input: &str, table_input: Option<&str>) -> Result<Vec<InstitutionalHolding>, ParseError> {
+        self.parse_report(cover_input, table_input)
+            .map_err(|err| err.with_access_no(&self.access_no))
+    }
+
+    fn parse_report(&self, cover_input: &str, table_input: Option<&str>) -> Result<Vec<InstitutionalHolding>, ParseError> {
+        let cover = Self::parse(cover_input)?;
+
+        let form_type = xml::find_text(&cover, &["submissionType"]).unwrap_or_default();
+        if !InstitutionalHolding::is_report(&form_type) {
+            return Err(ParseError::UnsupportedDocumentType { access_no: String::new(), document_type: form_type });
+        }
+
+        let period_of_report = xml::find_text(&cover, &["reportCalendarOrQuarter", "periodOfReport"])
+            .and_then(|t| Self::parse_date(&t))
+            .ok_or_else(|| ParseError::MissingElement {
+                access_no: String::new(),
+                path: "edgarSubmission/formData/coverPage/reportCalendarOrQuarter".to_string(),
+            })?;
+
+        let manager = cover.get_child("formData", NSChoice::Any)
+            .and_then(|form| form.get_child("coverPage", NSChoice::Any))
+            .and_then(|page| page.get_child("filingManager", NSChoice::Any));
+
+        let report = InstitutionalHolding {
+            access_no: self.access_no.clone(),
+            amendment_type: xml::find_text(&cover, &["amendmentType"]).map(|t| t.to_uppercase()),
+            form_type,
+            form_url: self.url.clone(),
+            period_of_report,
+            manager_cik: cover.get_child("headerData", NSChoice::Any)
+                .and_then(|header| xml::find_text(header, &["cik"]))
+                .map(|cik| format!("{cik:0>10}"))
+                .unwrap_or_default(),
+            manager_name: manager.and_then(|m| xml::find_text(m, &["name"])).unwrap_or_default(),
+            ..Default::default()
+        };
+
+        let table = match table_input {
+            Some(input) => Self::parse(input)?,
+            None if xml::find_text(&cover, &["tableEntryTotal"]).as_deref() == Some("0") => return Ok(Vec::new()),
+            None => {
+                return Err(ParseError::MissingElement {
+                    access_no: String::new(),
+                    path: "informationTable".to_string(),
+                })
+            }
+        };
+
+        let mut rows = Vec::<&Element>::new();
+        xml::descendants(&table, &["infoTable"], &mut rows);
+
+        let holdings = rows.into_iter()
+            .map(|row| Ok(InstitutionalHolding {
+                issuer_name: xml::find_text(row, &["nameOfIssuer"]).unwrap_or_default(),
+                title_of_class: xml::find_text(row, &["titleOfClass"]).unwrap_or_default(),
+                cusip: xml::find_text(row, &["cusip"]).map(|c| c.to_uppercase()).unwrap_or_default(),
+                market_value: Self::required_number(row, "value")?,
+                shares: Self::required_number(row, "sshPrnamt")?,
+                share_type: xml::find_text(row, &["sshPrnamtType"]).unwrap_or_default(),
+                put_call: xml::find_text(row, &["putCall"]),
+                investment_discretion: xml::find_text(row, &["investmentDiscretion"]).unwrap_or_default(),
+                voting_sole: Self::number(row, &["Sole"]),
+                voting_shared: Self::number(row, &["Shared"]),
+                voting_none: Self::number(row, &["None"]),
+                ..report.clone()
+            }))
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(holdings)
+    }
+
+    fn parse(input: &str) -> Result<Element, ParseError> {
+        input.parse().map_err(|err: minidom::Error| ParseError::MalformedXml {
+            access_no: String::new(),
+            reason: err.to_string(),
+        })
+    }
+
+    fn number(el: &Element, names: &[&str]) -> Option<BigDecimal> {
+        xml::find_text(el, names).and_then(|t| BigDecimal::from_str(&t.replace(',', "")).ok())
+    }
+
+    /// Value and amount are required on every row, a text that is not a number is an error
+    /// rather than a missing value.
+    fn required_number(el: &Element, name: &str) -> Result<Option<BigDecimal>, ParseError> {
+        match xml::find_text(el, &[name]) {
+            Some(text) => BigDecimal::from_str(&text.replace(',', ""))
+                .map(Some)
+                .map_err(|_| ParseError::BadNumber {
+                    access_no: String::new(),
+                    path: format!("informationTable/infoTable/{name}"),
+                    value: text,
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Cover pages write the quarter as `MM-DD-YYYY`.
+    fn parse_date(text: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(text, "%m-%d-%Y")
+            .or_else(|_| NaiveDate::parse_from_str(text, "%m/%d/%Y"))
+            .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d"))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COVER: &str = r#"<edgarSubmission xmlns="http://www.sec.gov/edgar/thirteenffiler">
+        <headerData>
+            <submissionType>13F-HR/A</submissionType>
+            <filerInfo>
+                <filer>
+                    <credentials>
+                        <cik>1067983</cik>
+                        <ccc>XXXXXXXX</ccc>
+                    </credentials>
+                </filer>
+                <periodOfReport>12-31-2024</periodOfReport>
+            </filerInfo>
+        </headerData>
+        <formData>
+            <coverPage>
+                <reportCalendarOrQuarter>12-31-2024</reportCalendarOrQuarter>
+                <isAmendment>true</isAmendment>
+                <amendmentInfo>
+                    <amendmentType>NEW HOLDINGS</amendmentType>
+                </amendmentInfo>
+                <filingManager>
+                    <name>Berkshire Hathaway Inc</name>
+                    <address><city>Omaha</city></address>
+                </filingManager>
+            </coverPage>
+            <summaryPage>
+                <tableEntryTotal>2</tableEntryTotal>
+            </summaryPage>
+        </formData>
+    </edgarSubmission>"#;
+
+    const TABLE: &str = r#"<ns1:informationTable xmlns:ns1="http://www.sec.gov/edgar/document/thirteenf/informationtable">
+        <ns1:infoTable>
+            <ns1:nameOfIssuer>APPLE INC</ns1:nameOfIssuer>
+            <ns1:titleOfClass>COM</ns1:titleOfClass>
+            <ns1:cusip>037833100</ns1:cusip>
+            <ns1:value>75112050000</ns1:value>
+            <ns1:shrsOrPrnAmt>
+                <ns1:sshPrnamt>300000000</ns1:sshPrnamt>
+                <ns1:sshPrnamtType>SH</ns1:sshPrnamtType>
+            </ns1:shrsOrPrnAmt>
+            <ns1:investmentDiscretion>DFND</ns1:investmentDiscretion>
+            <ns1:otherManager>4,8,11</ns1:otherManager>
+            <ns1:votingAuthority>
+                <ns1:Sole>300000000</ns1:Sole>
+                <ns1:Shared>0</ns1:Shared>
+                <ns1:None>0</ns1:None>
+            </ns1:votingAuthority>
+        </ns1:infoTable>
+        <ns1:infoTable>
+            <ns1:nameOfIssuer>SPDR S&amp;P 500 ETF TR</ns1:nameOfIssuer>
+            <ns1:titleOfClass>TR UNIT</ns1:titleOfClass>
+            <ns1:cusip>78462F103</ns1:cusip>
+            <ns1:value>11720000</ns1:value>
+            <ns1:shrsOrPrnAmt>
+                <ns1:sshPrnamt>20000</ns1:sshPrnamt>
+                <ns1:sshPrnamtType>SH</ns1:sshPrnamtType>
+            </ns1:shrsOrPrnAmt>
+            <ns1:putCall>Put</ns1:putCall>
+            <ns1:investmentDiscretion>SOLE</ns1:investmentDiscretion>
+            <ns1:votingAuthority>
+                <ns1:Sole>0</ns1:Sole>
+                <ns1:Shared>0</ns1:Shared>
+                <ns1:None>20000</ns1:None>
+            </ns1:votingAuthority>
+        </ns1:infoTable>
+    </ns1:informationTable>"#;
+
+    #[test]
+    fn information_table_rows() {
+        let parser = XMLForm13F::new("https://www.sec.gov/Archives/edgar/data/1067983/0000950123-25-002701.txt");
+        let holdings = parser.extract_holdings(COVER, Some(TABLE)).unwrap();
+
+        assert_eq!(holdings.len(), 2);
+        let apple = &holdings[0];
+        assert_eq!(apple.access_no, "0000950123-25-002701");
+        assert_eq!(apple.amendment_type.as_deref(), Some("NEW HOLDINGS"));
+        assert_eq!(apple.period_of_report, NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        assert_eq!(apple.manager_cik, "0001067983");
+        assert_eq!(apple.manager_name, "Berkshire Hathaway Inc");
+        assert_eq!(apple.cusip, "037833100");
+        assert_eq!(apple.market_value, BigDecimal::from_str("75112050000").ok());
+        assert_eq!(apple.shares, BigDecimal::from_str("300000000").ok());
+        assert_eq!(apple.investment_discretion, "DFND");
+        assert_eq!(apple.put_call, None);
+        assert_eq!(apple.voting_sole, BigDecimal::from_str("300000000").ok());
+
+        let put = &holdings[1];
+        assert_eq!(put.issuer_name, "SPDR S&P 500 ETF TR");
+        assert_eq!(put.put_call.as_deref(), Some("Put"));
+        assert_eq!(put.voting_none, BigDecimal::from_str("20000").ok());
+
+        let garbled = TABLE.replace("<ns1:value>11720000</ns1:value>", "<ns1:value>11.720.000</ns1:value>");
+        let err = parser.extract_holdings(COVER, Some(&garbled)).unwrap_err();
+        assert_eq!(err, ParseError::BadNumber {
+            access_no: "0000950123-25-002701".to_string(),
+            path: "informationTable/infoTable/value".to_string(),
+            value: "11.720.000".to_string(),
+        });
+    }
+}
@@ -0,0 +1,183 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use minidom::{Element, NSChoice};
+use std::str::FromStr;
+
+use super::atomfilings::XMLFiling;
+use super::error::ParseError;
+use crate::secgov::models::ProposedSale;
+
+/// Notice types parsed from an `edgarSubmission`.
+const SUPPORTED_TYPES: [&str; 2] = ["144", "144/A"];
+
+/// Form 144 notice of proposed sale, filed as XML since 2023.
+pub struct XMLForm144 {
+    pub url: String,
+    pub access_no: String,
+}
+
+impl XMLForm144 {
+    pub fn new(url: &str) -> XMLForm144 {
+        XMLForm144 {
+            url: url.to_string(),
+            access_no: XMLFiling::parse_access_num(url).unwrap_or_default(),
+        }
+    }
+
+    pub fn extract_sales(&self, xml_input: &str) -> Result<Vec<ProposedSale>, ParseError> {
+        self.parse_notice(xml_input)
+            .map_err(|err| err.with_access_no(&self.access_no))
+    }
+
+    fn parse_notice(&self, xml_input: &str) -> Result<Vec<ProposedSale>, ParseError> {
+        let root: Element = xml_input.parse().map_err(|err: minidom::Error| ParseError::MalformedXml {
+            access_no: String::new(),
+            reason: err.to_string(),
+        })?;
+
+        let form_type = Self::require(&root, &["headerData", "submissionType"])?;
+        if !SUPPORTED_TYPES.contains(&form_type.as_str()) {
+            return Err(ParseError::UnsupportedDocumentType { access_no: String::new(), document_type: form_type });
+        }
+
+        let form = Self::child(&root, &["formData"]).ok_or_else(|| ParseError::MissingElement {
+            access_no: String::new(),
+            path: "edgarSubmission/formData".to_string(),
+        })?;
+
+        let relationship = Self::child(form, &["issuerInfo", "relationshipsToIssuer"])
+            .map(|rels| rels.children().map(|r| r.text().trim().to_string()).collect::<Vec<_>>().join(", "))
+            .unwrap_or_default();
+
+        // the first plan listed, a notice covering several plans lists them oldest first
+        let plan_adoption_date = Self::child(form, &["noticeSignature", "planAdoptionDates"])
+            .and_then(|dates| dates.children().next())
+            .and_then(|date| Self::parse_date(date.text().trim()));
+
+        let notice = ProposedSale {
+            access_no: self.access_no.clone(),
+            form_type,
+            form_url: self.url.clone(),
+            filed_date: None,
+            issuer_cik: Self::cik(&Self::require(form, &["issuerInfo", "issuerCik"])?),
+            issuer_name: Self::require(form, &["issuerInfo", "issuerName"])?,
+            seller_cik: Self::cik(&Self::require(&root, &["headerData", "filerInfo", "filer", "filerCredentials", "cik"])?),
+            seller_name: Self::text(form, &["issuerInfo", "nameOfPersonForWhoseAccountTheSecuritiesAreToBeSold"]).unwrap_or_default(),
+            relationship,
+            plan_adoption_date,
+            ..Default::default()
+        };
+
+        let sales: Vec<ProposedSale> = form.children()
+            .filter(|c| c.is("securitiesInformation", NSChoice::Any))
+            .map(|info| ProposedSale {
+                security_class: Self::text(info, &["securitiesClassTitle"]).unwrap_or_default(),
+                broker: Self::text(info, &["brokerOrMarketmakerDetails", "name"]).unwrap_or_default(),
+                shares: Self::text(info, &["noOfUnitsSold"]).and_then(|t| BigDecimal::from_str(&t).ok()),
+                market_value: Self::text(info, &["aggregateMarketValue"]).and_then(|t| BigDecimal::from_str(&t).ok()),
+                approx_sale_date: Self::text(info, &["approxSaleDate"]).and_then(|t| Self::parse_date(&t)),
+                ..notice.clone()
+            })
+            .collect();
+
+        if sales.is_empty() {
+            return Err(ParseError::MissingElement {
+                access_no: String::new(),
+                path: "edgarSubmission/formData/securitiesInformation".to_string(),
+            });
+        }
+
+        Ok(sales)
+    }
+
+    fn child<'a>(root: &'a Element, path: &[&str]) -> Option<&'a Element> {
+        path.iter().try_fold(root, |el, tag| el.get_child(tag, NSChoice::Any))
+    }
+
+    fn text(root: &Element, path: &[&str]) -> Option<String> {
+        Self::child(root, path)
+            .map(|el| el.text().trim().to_string())
+            .filter(|text| !text.is_empty())
+    }
+
+    fn require(root: &Element, path: &[&str]) -> Result<String, ParseError> {
+        Self::text(root, path).ok_or_else(|| ParseError::MissingElement {
+            access_no: String::new(),
+            path: format!("{}/{}", root.name(), path.join("/")),
+        })
+    }
+
+    /// Ciks are zero padded to ten digits like in ownership documents, so both can be joined.
+    fn cik(cik: &str) -> String {
+        format!("{cik:0>10}")
+    }
+
+    /// Notices write dates as `MM/DD/YYYY`.
+    fn parse_date(text: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(text, "%m/%d/%Y")
+            .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d"))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTICE: &str = r#"<edgarSubmission xmlns="http://www.sec.gov/edgar/ownership" xmlns:com="http://www.sec.gov/edgar/common">
+        <headerData>
+            <submissionType>144</submissionType>
+            <filerInfo>
+                <filer>
+                    <filerCredentials>
+                        <cik>1214156</cik>
+                        <ccc>XXXXXXXX</ccc>
+                    </filerCredentials>
+                </filer>
+            </filerInfo>
+        </headerData>
+        <formData>
+            <issuerInfo>
+                <issuerCik>0000320193</issuerCik>
+                <issuerName>Apple Inc.</issuerName>
+                <nameOfPersonForWhoseAccountTheSecuritiesAreToBeSold>Timothy D. Cook</nameOfPersonForWhoseAccountTheSecuritiesAreToBeSold>
+                <relationshipsToIssuer>
+                    <relationshipToIssuer>Officer</relationshipToIssuer>
+                    <relationshipToIssuer>Director</relationshipToIssuer>
+                </relationshipsToIssuer>
+            </issuerInfo>
+            <securitiesInformation>
+                <securitiesClassTitle>Common</securitiesClassTitle>
+                <brokerOrMarketmakerDetails>
+                    <name>Morgan Stanley Smith Barney LLC</name>
+                </brokerOrMarketmakerDetails>
+                <noOfUnitsSold>108136</noOfUnitsSold>
+                <aggregateMarketValue>24567890.12</aggregateMarketValue>
+                <approxSaleDate>04/02/2025</approxSaleDate>
+            </securitiesInformation>
+            <noticeSignature>
+                <planAdoptionDates>
+                    <planAdoptionDate>11/20/2024</planAdoptionDate>
+                </planAdoptionDates>
+            </noticeSignature>
+        </formData>
+    </edgarSubmission>"#;
+
+    #[test]
+    fn notice_fields() {
+        let parser = XMLForm144::new("https://www.sec.gov/Archives/edgar/data/320193/0001959173-25-002211.txt");
+        let sales = parser.extract_sales(NOTICE).unwrap();
+
+        assert_eq!(sales.len(), 1);
+        let sale = &sales[0];
+        assert_eq!(sale.access_no, "0001959173-25-002211");
+        assert_eq!(sale.seller_cik, "0001214156");
+        assert_eq!(sale.issuer_cik, "0000320193");
+        assert_eq!(sale.relationship, "Officer, Director");
+        assert_eq!(sale.broker, "Morgan Stanley Smith Barney LLC");
+        assert_eq!(sale.shares, BigDecimal::from_str("108136").ok());
+        assert_eq!(sale.market_value, BigDecimal::from_str("24567890.12").ok());
+        assert_eq!(sale.approx_sale_date, NaiveDate::from_ymd_opt(2025, 4, 2));
+        assert_eq!(sale.plan_adoption_date, NaiveDate::from_ymd_opt(2024, 11, 20));
+    }
+}
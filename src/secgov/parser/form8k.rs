@@ -0,0 +1,256 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::atomfilings::XMLFiling;
+use super::error::ParseError;
+use crate::secgov::models::MaterialEvent;
+
+/// Items of Form 8-K with the titles the SEC header lists them by.
+const ITEMS: [(&str, &str); 32] = [
+    ("1.01", "Entry into a Material Definitive Agreement"),
+    ("1.02", "Termination of a Material Definitive Agreement"),
+    ("1.03", "Bankruptcy or Receivership"),
+    ("1.04", "Mine Safety - Reporting of Shutdowns and Patterns of Violations"),
+    ("1.05", "Material Cybersecurity Incidents"),
+    ("2.01", "Completion of Acquisition or Disposition of Assets"),
+    ("2.02", "Results of Operations and Financial Condition"),
+    ("2.03", "Creation of a Direct Financial Obligation or an Obligation under an Off-Balance Sheet Arrangement of a Registrant"),
+    ("2.04", "Triggering Events That Accelerate or Increase a Direct Financial Obligation or an Obligation under an Off-Balance Sheet Arrangement"),
+    ("2.05", "Costs Associated with Exit or Disposal Activities"),
+    ("2.06", "Material Impairments"),
+    ("3.01", "Notice of Delisting or Failure to Satisfy a Continued Listing Rule or Standard; Transfer of Listing"),
+    ("3.02", "Unregistered Sales of Equity Securities"),
+    ("3.03", "Material Modification to Rights of Security Holders"),
+    ("4.01", "Changes in Registrant's Certifying Accountant"),
+    ("4.02", "Non-Reliance on Previously Issued Financial Statements or a Related Audit Report or Completed Interim Review"),
+    ("5.01", "Changes in Control of Registrant"),
+    ("5.02", "Departure of Directors or Certain Officers; Election of Directors; Appointment of Certain Officers; Compensatory Arrangements of Certain Officers"),
+    ("5.03", "Amendments to Articles of Incorporation or Bylaws; Change in Fiscal Year"),
+    ("5.04", "Temporary Suspension of Trading Under Registrant's Employee Benefit Plans"),
+    ("5.05", "Amendments to the Registrant's Code of Ethics, or Waiver of a Provision of the Code of Ethics"),
+    ("5.06", "Change in Shell Company Status"),
+    ("5.07", "Submission of Matters to a Vote of Security Holders"),
+    ("5.08", "Shareholder Director Nominations"),
+    ("6.01", "ABS Informational and Computational Material"),
+    ("6.02", "Change of Servicer or Trustee"),
+    ("6.03", "Change in Credit Enhancement or Other External Support"),
+    ("6.04", "Failure to Make a Required Distribution"),
+    ("6.05", "Securities Act Updating Disclosure"),
+    ("7.01", "Regulation FD Disclosure"),
+    ("8.01", "Other Events"),
+    ("9.01", "Financial Statements and Exhibits"),
+];
+
+static BLOCKS: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<br\s*/?>|</(?:p|div|tr|li|h[1-6]|table)\s*>").unwrap());
+
+static TAGS: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+static HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?im)^item[ \t]*([0-9]\.[0-9]{2})\b[.:]?[ \t]*(.*)$").unwrap());
+
+static ITEM_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bitem[ \t]*([0-9]\.[0-9]{2})\b").unwrap());
+
+static SIGNATURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?im)^(?:signatures?\b|pursuant to the requirements of the securities exchange act)").unwrap()
+});
+
+/// Current report on Form 8-K, read from the item list of the SEC header and the item
+/// headings of the primary document.
+pub struct Form8K {
+    pub url: String,
+    pub access_no: String,
+}
+
+impl Form8K {
+    pub fn new(url: &str) -> Form8K {
+        Form8K {
+            url: url.to_string(),
+            access_no: XMLFiling::parse_access_num(url).unwrap_or_default(),
+        }
+    }
+
+    /// One event per item of the report. The items are the ones the header lists by title,
+    /// or the ones the document heads when the header lists none. Each event carries the text
+    /// between its heading and the next one, or the signatures.
+    pub fn extract_events(&self, form_type: &str, header_items: &[String], document: &str) -> Result<Vec<MaterialEvent>, ParseError> {
+        let text = Self::plain_text(document);
+        let body = match SIGNATURE.find(&text) {
+            Some(signature) => &text[..signature.start()],
+            None => text.as_str(),
+        };
+
+        // item, title of the heading and section, the longest section wins when an item is headed twice
+        let headings: Vec<_> = HEADING.captures_iter(body).collect();
+        let mut sections = Vec::<(String, String, String)>::new();
+        for (i, heading) in headings.iter().enumerate() {
+            let end = headings.get(i + 1).map_or(body.len(), |next| next.get(0).unwrap().start());
+            let item = heading[1].to_string();
+            let section = Self::section(&item, &body[heading.get(0).unwrap().end()..end]);
+
+            match sections.iter_mut().find(|(known, _, _)| *known == item) {
+                Some(known) if known.2.len() < section.len() => known.2 = section,
+                Some(_) => {}
+                None => sections.push((item, heading[2].trim().to_string(), section)),
+            }
+        }
+
+        let mut items: Vec<String> = header_items.iter().filter_map(|title| Self::item_number(title)).map(str::to_string).collect();
+        if items.is_empty() {
+            items = sections.iter().map(|(item, _, _)| item.clone()).collect();
+        }
+        items.sort();
+        items.dedup();
+
+        if items.is_empty() {
+            return Err(ParseError::MissingElement {
+                access_no: self.access_no.clone(),
+                path: "SEC-HEADER/ITEM INFORMATION".to_string(),
+            });
+        }
+
+        let events = items.into_iter()
+            .map(|item| {
+                let headed = sections.iter().find(|(known, _, _)| *known == item);
+
+                MaterialEvent {
+                    access_no: self.access_no.clone(),
+                    form_type: form_type.to_string(),
+                    form_url: self.url.clone(),
+                    item_title: Self::item_title(&item)
+                        .map(str::to_string)
+                        .or_else(|| headed.map(|(_, title, _)| title.clone()))
+                        .unwrap_or_default(),
+                    section: headed.map(|(_, _, section)| section.clone()).unwrap_or_default(),
+                    item,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Ok(events)
+    }
+
+    /// Item number of a title of the SEC header, the `Item N.NN` it names or else the item
+    /// whose title it is. Titles are compared on letters and digits only as punctuation varies
+    /// between filings.
+    pub fn item_number(title: &str) -> Option<&'static str> {
+        if let Some(number) = ITEM_NUMBER.captures(title) {
+            return ITEMS.iter().find(|(item, _)| *item == &number[1]).map(|(item, _)| *item);
+        }
+
+        let title = Self::normalize(title);
+        if title.is_empty() {
+            return None;
+        }
+
+        ITEMS.iter()
+            .find(|(_, known)| Self::normalize(known) == title)
+            .map(|(item, _)| *item)
+    }
+
+    fn item_title(item: &str) -> Option<&'static str> {
+        ITEMS.iter()
+            .find(|(known, _)| *known == item)
+            .map(|(_, title)| *title)
+    }
+
+    /// Section text of an item, without the title when the document puts it on its own lines.
+    fn section(item: &str, text: &str) -> String {
+        let title = Self::item_title(item).map(Self::normalize).unwrap_or_default();
+
+        let mut heading = String::new();
+
+        text.lines()
+            .skip_while(|line| {
+                heading.push_str(&Self::normalize(line));
+                !title.is_empty() && title.starts_with(&heading)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    /// Text of an HTML or plain text document, a line per block with its whitespace collapsed.
+    fn plain_text(document: &str) -> String {
+        let text = BLOCKS.replace_all(document, "\n");
+        let text = TAGS.replace_all(&text, " ")
+            .replace("&nbsp;", " ")
+            .replace("&#160;", " ")
+            .replace("&#xa0;", " ")
+            .replace("&#8217;", "'")
+            .replace("&rsquo;", "'")
+            .replace("&#8220;", "\"")
+            .replace("&#8221;", "\"")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&");
+
+        text.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn normalize(text: &str) -> String {
+        text.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"<html><body>
+        <p style="text-align:center"><b>UNITED STATES SECURITIES AND EXCHANGE COMMISSION</b></p>
+        <p><b>FORM 8-K</b></p>
+        <p><b>Item&#160;5.02</b></p>
+        <p><b>Departure of Directors or Certain Officers; Election of Directors; Appointment of Certain Officers;
+            Compensatory Arrangements of Certain Officers.</b></p>
+        <p>On March&#160;3, 2025, Jane Doe notified the Company of her decision to resign as Chief Financial Officer.</p>
+        <p>The information set forth under Item 7.01 is incorporated herein by reference.</p>
+        <div><b>Item 7.01 Regulation FD Disclosure.</b></div>
+        <div>A copy of the press release is furnished as Exhibit 99.1.</div>
+        <p><b>Item 9.01 Financial Statements and Exhibits.</b></p>
+        <table><tr><td>99.1</td><td>Press release dated March&#160;4, 2025</td></tr></table>
+        <p style="text-align:center"><b>SIGNATURES</b></p>
+        <p>Pursuant to the requirements of the Securities Exchange Act of 1934, the registrant has duly caused this report to be signed.</p>
+    </body></html>"#;
+
+    #[test]
+    fn items_and_their_sections() {
+        let parser = Form8K::new("https://www.sec.gov/Archives/edgar/data/1234567/0001234567-25-000010.txt");
+        let header_items = vec![
+            "Departure of Directors or Certain Officers; Election of Directors; Appointment of Certain Officers: Compensatory Arrangements of Certain Officers".to_string(),
+            "Regulation FD Disclosure".to_string(),
+            "Financial Statements and Exhibits".to_string(),
+        ];
+
+        let events = parser.extract_events("8-K", &header_items, DOCUMENT).unwrap();
+        let items: Vec<&str> = events.iter().map(|e| e.item.as_str()).collect();
+        assert_eq!(items, vec!["5.02", "7.01", "9.01"]);
+
+        let departure = &events[0];
+        assert_eq!(departure.access_no, "0001234567-25-000010");
+        assert!(departure.item_title.starts_with("Departure of Directors"));
+        assert_eq!(
+            departure.section,
+            "On March 3, 2025, Jane Doe notified the Company of her decision to resign as Chief Financial Officer.\n\
+             The information set forth under Item 7.01 is incorporated herein by reference."
+        );
+        assert_eq!(events[1].section, "A copy of the press release is furnished as Exhibit 99.1.");
+        assert_eq!(events[2].section, "99.1 Press release dated March 4, 2025");
+
+        assert_eq!(Form8K::item_number("Item 2.02 Results of Operations"), Some("2.02"));
+        assert_eq!(Form8K::item_number("ITEM 8.01: Other Events"), Some("8.01"));
+        assert_eq!(Form8K::item_number("Item 9.99"), None);
+        // a title that only starts a known one is not enough
+        assert_eq!(Form8K::item_number("Material"), None);
+        assert_eq!(Form8K::item_number("Results of Operations"), None);
+
+        // without the header list the headings of the document are the items
+        let headed = parser.extract_events("8-K", &[], DOCUMENT).unwrap();
+        assert_eq!(headed.len(), 3);
+        assert_eq!(headed[1].item_title, "Regulation FD Disclosure");
+    }
+}
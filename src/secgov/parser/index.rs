@@ -1,7 +1,8 @@
 use chrono::{Datelike, NaiveDate};
-use regex::Regex;
 use std::string::String;
 
+use super::atomfilings::XMLFiling;
+
 #[derive(Debug, Clone)]
 pub struct IndexEntry {
     pub company_cik: String,
@@ -11,30 +12,30 @@ pub struct IndexEntry {
     pub filepath: String
 }
 
-pub fn extract_index_entries(input: &str) -> Vec<IndexEntry> {
-    let mut entries = Vec::<IndexEntry>::new();
-
-    let re = Regex::new(r"\d+\|.*\|4\|.*").unwrap();
-    for entry in re.find_iter(input) {
-        let entry = parse_entry(entry.as_str());
-        if entry.is_some() {
-            entries.push(entry.unwrap());
-        }
+impl IndexEntry {
+    /// Accession number of the submission, the same under every cik the index lists it for.
+    pub fn access_no(&self) -> Option<String> {
+        XMLFiling::parse_access_num(&self.filepath)
     }
+}
 
-    entries
+/// Every entry of a master index, daily or quarterly. Headers and separator lines are skipped.
+pub fn read_index(input: &str) -> impl Iterator<Item = IndexEntry> + '_ {
+    input.lines().filter_map(parse_entry)
 }
 
 fn parse_entry(entry: &str) -> Option<IndexEntry> {
-    let values: Vec<&str> = entry.split('|').collect();
+    let values: Vec<&str> = entry.trim_end().split('|').collect();
 
     if values.len() == 5 {
         Some(IndexEntry {
             company_cik: values[0].to_uppercase(),
             company_name: values[1].to_uppercase(),
             form_type: values[2].to_uppercase(),
+            // daily indexes write the date as 20250213, quarterly ones as 2025-02-13
             file_date: NaiveDate::parse_from_str(values[3], "%Y%m%d")
-                .expect("Invalid date string"),
+                .or_else(|_| NaiveDate::parse_from_str(values[3], "%Y-%m-%d"))
+                .ok()?,
             filepath: values[4].to_string()
         })
     } else {
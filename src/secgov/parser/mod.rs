@@ -1,44 +1,299 @@
 pub mod index;
 pub mod atomfilings;
+pub mod error;
+pub mod form13f;
+pub mod form144;
+pub mod form8k;
+pub mod schedule13;
+pub(crate) mod xml;
 
-use std::error::Error;
-
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
 use regex::Regex;
 
 use self::atomfilings::XMLFiling;
-use super::models::FilingTransaction;
+use self::error::ParseError;
+use self::form13f::XMLForm13F;
+use self::form144::XMLForm144;
+use self::form8k::Form8K;
+use self::schedule13::Schedule13;
+use super::models::{BeneficialOwnership, Filing, FilingDocument, InstitutionalHolding, MaterialEvent, ProposedSale};
+
+/// What the SEC header of a submission says about it, read from the header of the `.txt`
+/// submission or from the `-index.html` page of its accession directory.
+#[derive(Debug, Default, Clone)]
+pub struct SubmissionHeader {
+    pub form_type: String,
+    pub filed_date: Option<NaiveDate>,
+    pub period_of_report: Option<NaiveDate>,
+    /// Titles of the 8-K items, `ITEM INFORMATION` lines.
+    pub items: Vec<String>,
+    /// Cik and conformed name of the `FILER`, `SUBJECT COMPANY` and `FILED BY` parties.
+    pub filer: Option<(String, String)>,
+    pub subject: Option<(String, String)>,
+    pub filed_by: Option<(String, String)>,
+}
+
+impl SubmissionHeader {
+    /// The header that opens a `.txt` submission.
+    pub fn from_text(content: &str) -> SubmissionHeader {
+        let header = content.split("</SEC-HEADER>").next().unwrap_or_default();
+        let items_pattern = Regex::new(r"ITEM INFORMATION:[ \t]*([^\r\n]+)").unwrap();
+
+        SubmissionHeader {
+            form_type: FilingDoc::header_value(header, "CONFORMED SUBMISSION TYPE").unwrap_or_default(),
+            filed_date: FilingDoc::filed_date(header),
+            period_of_report: FilingDoc::header_value(header, "CONFORMED PERIOD OF REPORT")
+                .and_then(|date| NaiveDate::parse_from_str(&date, "%Y%m%d").ok()),
+            items: items_pattern.captures_iter(header).map(|c| c[1].trim().to_string()).collect(),
+            filer: FilingDoc::header_party(header, "FILER"),
+            subject: FilingDoc::header_party(header, "SUBJECT COMPANY"),
+            filed_by: FilingDoc::header_party(header, "FILED BY"),
+        }
+    }
+}
 
 pub struct FilingDoc;
 
 impl FilingDoc {
-    pub fn new(url: &str, content: &str) -> Result<Vec<FilingTransaction>, Box<dyn Error>>{
+    pub fn new(url: &str, content: &str) -> Result<Filing, ParseError> {
         let mut filing = XMLFiling::new(url);
-        let content = Self::extract_xml(content);
 
-        filing
-            .extract_transactions(
-                Self::extract_xml(&content)
-                    .as_str()
-            )
+        let xml = Self::extract_xml(content)
+            .ok_or_else(|| ParseError::NoXmlPayload { access_no: filing.access_no.clone() })?;
+
+        let mut parsed = filing.extract_filing(&xml)?;
+        parsed.filed_date = Self::filed_date(content);
+        parsed.documents = Self::documents(content);
+
+        Ok(parsed)
+    }
+
+    /// The documents a `.txt` submission embeds, from the `<TYPE>`, `<SEQUENCE>`, `<FILENAME>`
+    /// and `<DESCRIPTION>` lines that open each `<DOCUMENT>`. The text file gives no sizes.
+    pub fn documents(content: &str) -> Vec<FilingDocument> {
+        let pattern = Regex::new(r"<DOCUMENT>([\W\S]*?)<TEXT>").unwrap();
+
+        pattern.captures_iter(content)
+            .map(|doc| FilingDocument {
+                seq: Self::tag_value(&doc[1], "SEQUENCE").and_then(|seq| seq.parse().ok()),
+                name: Self::tag_value(&doc[1], "FILENAME").unwrap_or_default(),
+                doc_type: Self::tag_value(&doc[1], "TYPE").unwrap_or_default(),
+                description: Self::tag_value(&doc[1], "DESCRIPTION").unwrap_or_default(),
+                size: None,
+            })
+            .collect()
+    }
+
+    /// The sales announced by a Form 144 submission, `content` holding its XML document.
+    pub fn proposed_sales(url: &str, header: &SubmissionHeader, content: &str) -> Result<Vec<ProposedSale>, ParseError> {
+        let notice = XMLForm144::new(url);
+
+        let pattern = Regex::new(r"<edgarSubmission[\W\S]*</edgarSubmission>").unwrap();
+        let xml = pattern.find(content)
+            .ok_or_else(|| ParseError::NoXmlPayload { access_no: notice.access_no.clone() })?;
+
+        let mut sales = notice.extract_sales(xml.as_str())?;
+        sales.iter_mut().for_each(|sale| sale.filed_date = header.filed_date);
+
+        Ok(sales)
     }
 
-    fn extract_xml(input: &str) -> String {
+    /// The beneficial ownership events of a Schedule 13D or 13G submission, `content` holding
+    /// its XML or text statement. Ciks and names the statement leaves out are taken from the
+    /// subject company and filer of the SEC header.
+    pub fn beneficial_ownership(url: &str, header: &SubmissionHeader, content: &str) -> Result<Vec<BeneficialOwnership>, ParseError> {
+        let schedule = Schedule13::new(url);
+
+        let pattern = Regex::new(r"<edgarSubmission[\W\S]*</edgarSubmission>").unwrap();
+        let mut events = match pattern.find(content) {
+            Some(xml) => schedule.extract_xml(xml.as_str())?,
+            None => schedule.extract_text(&header.form_type, content)?,
+        };
+
+        for event in &mut events {
+            event.filed_date = header.filed_date;
+
+            if let Some((cik, name)) = &header.subject {
+                if event.issuer_cik.is_empty() { event.issuer_cik = cik.clone(); }
+                if event.issuer_name.is_empty() { event.issuer_name = name.clone(); }
+            }
+
+            // the header names a single filer, other persons of a joint statement keep their own
+            if let Some((cik, name)) = &header.filed_by {
+                let same_person = event.filer_name.is_empty() || event.filer_name.eq_ignore_ascii_case(name);
+                if same_person && event.filer_cik.is_empty() { event.filer_cik = cik.clone(); }
+                if event.filer_name.is_empty() { event.filer_name = name.clone(); }
+            }
+
+            if event.issuer_cik.is_empty() {
+                return Err(ParseError::MissingElement {
+                    access_no: schedule.access_no.clone(),
+                    path: "SEC-HEADER/SUBJECT COMPANY".to_string(),
+                });
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// The positions of a 13F-HR submission, `content` holding its cover page and information
+    /// table, which may be missing for a report without holdings. The manager cik comes from
+    /// the filer of the SEC header when the cover page leaves it out.
+    pub fn institutional_holdings(url: &str, header: &SubmissionHeader, content: &str) -> Result<Vec<InstitutionalHolding>, ParseError> {
+        let report = XMLForm13F::new(url);
+
+        let cover = Self::find_document(content, "edgarSubmission")
+            .ok_or_else(|| ParseError::NoXmlPayload { access_no: report.access_no.clone() })?;
+        let table = Self::find_document(content, "informationTable");
+
+        let mut holdings = report.extract_holdings(&cover, table.as_deref())?;
+
+        // values were reported in thousands of dollars until January 3, 2023
+        let in_thousands = header.filed_date.is_some_and(|d| d < NaiveDate::from_ymd_opt(2023, 1, 3).unwrap());
+
+        for holding in &mut holdings {
+            holding.filed_date = header.filed_date;
+
+            if holding.manager_cik.is_empty() {
+                if let Some((cik, _)) = &header.filer { holding.manager_cik = cik.clone(); }
+            }
+
+            if in_thousands {
+                holding.market_value = holding.market_value.take().map(|v| v * BigDecimal::from(1000));
+            }
+        }
+
+        Ok(holdings)
+    }
+
+    /// The items of an 8-K submission, read from the item list of the SEC header and the
+    /// primary document `document`. The filer of the header is the issuer.
+    pub fn material_events(url: &str, header: &SubmissionHeader, document: &str) -> Result<Vec<MaterialEvent>, ParseError> {
+        let report = Form8K::new(url);
+
+        let (issuer_cik, issuer_name) = header.filer.clone()
+            .ok_or_else(|| ParseError::MissingElement {
+                access_no: report.access_no.clone(),
+                path: "SEC-HEADER/FILER".to_string(),
+            })?;
+
+        let mut events = report.extract_events(&header.form_type, &header.items, document)?;
+        for event in &mut events {
+            event.filed_date = header.filed_date;
+            event.event_date = header.period_of_report;
+            event.issuer_cik = issuer_cik.clone();
+            event.issuer_name = issuer_name.clone();
+        }
+
+        Ok(events)
+    }
+
+    /// Text of the `<DOCUMENT>` of a `.txt` submission whose type is `form_type`, the first
+    /// one when none is.
+    pub fn primary_document<'a>(content: &'a str, form_type: &str) -> Option<&'a str> {
+        let pattern = Regex::new(r"<DOCUMENT>\s*<TYPE>([^\r\n<]+)[\W\S]*?<TEXT>([\W\S]*?)</TEXT>").unwrap();
+        let documents: Vec<_> = pattern.captures_iter(content).collect();
+
+        documents.iter()
+            .find(|doc| doc[1].trim().eq_ignore_ascii_case(form_type))
+            .or_else(|| documents.first())
+            .and_then(|doc| doc.get(2))
+            .map(|text| text.as_str())
+    }
+
+    /// An XML document of the submission by its root element, which may carry a namespace
+    /// prefix. Roots without a namespace get an empty one, as the parser requires it.
+    fn find_document(content: &str, root: &str) -> Option<String> {
+        let pattern = Regex::new(&format!(r"<(?:\w+:)?{root}[\s>][\W\S]*?</(?:\w+:)?{root}>")).unwrap();
+        let xml = pattern.find(content)?.as_str();
+
+        let open_tag = &xml[..xml.find('>')?];
+        if open_tag.contains("xmlns") {
+            Some(xml.to_string())
+        } else {
+            Some(xml.replacen(&format!("<{root}"), &format!("<{root} xmlns=\"\""), 1))
+        }
+    }
+
+    /// The value of a `<TAG>value` line that opens a `<DOCUMENT>`.
+    fn tag_value(content: &str, tag: &str) -> Option<String> {
+        let pattern = Regex::new(&format!(r"<{tag}>([^\r\n<]+)")).unwrap();
+        let value = pattern.captures(content)?.get(1)?.as_str().trim();
+
+        Some(value.to_string())
+    }
+
+    /// A `KEY: value` line of the SEC header.
+    fn header_value(content: &str, key: &str) -> Option<String> {
+        let pattern = Regex::new(&format!(r"{key}:[ \t]*([^\r\n]+)")).unwrap();
+        let value = pattern.captures(content)?.get(1)?.as_str().trim();
+
+        Some(value.to_string())
+    }
+
+    /// Cik and conformed name of a party of the SEC header, `SUBJECT COMPANY` or `FILED BY`.
+    fn header_party(content: &str, section: &str) -> Option<(String, String)> {
+        let header = content.split("</SEC-HEADER>").next()?;
+        let party = &header[header.find(&format!("{section}:"))?..];
+
+        let name = Self::header_value(party, "COMPANY CONFORMED NAME")?;
+        let cik = Self::header_value(party, "CENTRAL INDEX KEY")?;
+
+        Some((cik, name))
+    }
+
+    /// The `FILED AS OF DATE` line of the SEC header that precedes the documents.
+    fn filed_date(content: &str) -> Option<NaiveDate> {
+        let pattern = Regex::new(r"FILED AS OF DATE:\s*(\d{8})").unwrap();
+        let date = pattern.captures(content)?.get(1)?.as_str();
+
+        NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+    }
+
+    fn extract_xml(input: &str) -> Option<String> {
         let pattern: Regex =
             Regex::new(r#"<\?xml version="1\.0"\?>[\W\S]*</ownershipDocument>"#).unwrap();
 
         let result = pattern
-            .captures(input)
-            .and_then(|cap| {
-                cap.iter()
-                    .next()
-                    .expect("Failed to parse XML")
-                    .map(|m| m.as_str())
-            })
-            .expect("XML regex match failed");
-
-        let result = result
+            .find(input)?
+            .as_str()
             .replace("<ownershipDocument>", "<ownershipDocument xmlns=\"\">");
 
-        result
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_of_a_text_submission() {
+        let content = "<SEC-DOCUMENT>0000320193-25-000030.txt : 20250214\n\
+            <SEC-HEADER>0000320193-25-000030.hdr.sgml : 20250214\n\
+            CONFORMED SUBMISSION TYPE:\t4\n\
+            </SEC-HEADER>\n\
+            <DOCUMENT>\n<TYPE>4\n<SEQUENCE>1\n<FILENAME>wk-form4_1739574321.xml\n<DESCRIPTION>FORM 4\n<TEXT>\n<XML>\n</XML>\n</TEXT>\n</DOCUMENT>\n\
+            <DOCUMENT>\n<TYPE>EX-24\n<SEQUENCE>2\n<FILENAME>poa.txt\n<TEXT>\nPower of attorney\n</TEXT>\n</DOCUMENT>\n\
+            </SEC-DOCUMENT>";
+
+        let documents = FilingDoc::documents(content);
+        assert_eq!(documents, vec![
+            FilingDocument {
+                seq: Some(1),
+                name: "wk-form4_1739574321.xml".to_string(),
+                doc_type: "4".to_string(),
+                description: "FORM 4".to_string(),
+                size: None,
+            },
+            FilingDocument {
+                seq: Some(2),
+                name: "poa.txt".to_string(),
+                doc_type: "EX-24".to_string(),
+                description: String::new(),
+                size: None,
+            },
+        ]);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,235 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use minidom::Element;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::str::FromStr;
+
+use super::atomfilings::XMLFiling;
+use super::error::ParseError;
+use super::xml;
+use crate::secgov::models::BeneficialOwnership;
+
+/// Cover page rows of the HTML and text schedules, the value follows its label.
+static COVER_ROWS: Lazy<Vec<(&str, Regex)>> = Lazy::new(|| {
+    [
+        ("sole_voting", r"(?i)SOLE\s+VOTING\s+POWER[^0-9]{0,40}?([0-9][0-9,]*(?:\.[0-9]+)?)"),
+        ("shared_voting", r"(?i)SHARED\s+VOTING\s+POWER[^0-9]{0,40}?([0-9][0-9,]*(?:\.[0-9]+)?)"),
+        ("sole_dispositive", r"(?i)SOLE\s+DISPOSITIVE\s+POWER[^0-9]{0,40}?([0-9][0-9,]*(?:\.[0-9]+)?)"),
+        ("shared_dispositive", r"(?i)SHARED\s+DISPOSITIVE\s+POWER[^0-9]{0,40}?([0-9][0-9,]*(?:\.[0-9]+)?)"),
+        ("aggregate_shares", r"(?i)AGGREGATE\s+AMOUNT\s+BENEFICIALLY\s+OWNED\s+BY\s+EACH\s+REPORTING\s+PERSON[^0-9]{0,40}?([0-9][0-9,]*(?:\.[0-9]+)?)"),
+        ("percent_of_class", r"(?i)PERCENT\s+OF\s+CLASS\s+REPRESENTED\s+BY\s+AMOUNT\s+IN\s+ROW\s*\(?\s*[0-9]+\s*\)?[^0-9]{0,40}?([0-9]+(?:\.[0-9]+)?)\s*%"),
+    ]
+    .into_iter()
+    .map(|(field, pattern)| (field, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+static CUSIP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)CUSIP\s*(?:No\.?|Number|#)?\s*:?\s*([0-9A-Z]{6}\s?[0-9A-Z]{2}\s?[0-9A-Z]?)\b").unwrap()
+});
+
+static AMENDMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)Amendment\s+No\.?\s*:?\s*([0-9]+)").unwrap());
+
+/// Schedule 13D and 13G statements, XML since December 2024 and HTML or plain text before.
+pub struct Schedule13 {
+    pub url: String,
+    pub access_no: String,
+}
+
+impl Schedule13 {
+    pub fn new(url: &str) -> Schedule13 {
+        Schedule13 {
+            url: url.to_string(),
+            access_no: XMLFiling::parse_access_num(url).unwrap_or_default(),
+        }
+    }
+
+    /// One event per reporting person of an `edgarSubmission`. The 13D and 13G schemas name
+    /// the same cover page rows differently, so elements are looked up by any of their names.
+    pub fn extract_xml(&self, xml_input: &str) -> Result<Vec<BeneficialOwnership>, ParseError> {
+        let root: Element = xml_input.parse().map_err(|err: minidom::Error| ParseError::MalformedXml {
+            access_no: self.access_no.clone(),
+            reason: err.to_string(),
+        })?;
+
+        let form_type = xml::find_text(&root, &["submissionType"]).unwrap_or_default();
+        if !BeneficialOwnership::is_schedule(&form_type) {
+            return Err(ParseError::UnsupportedDocumentType { access_no: self.access_no.clone(), document_type: form_type });
+        }
+
+        let statement = BeneficialOwnership {
+            access_no: self.access_no.clone(),
+            form_type,
+            form_url: self.url.clone(),
+            event_date: xml::find_text(&root, &["dateOfEvent", "eventDateRequiresFilingThisStatement"]).and_then(|t| Self::parse_date(&t)),
+            issuer_cik: xml::find_text(&root, &["issuerCik"]).map(|cik| format!("{cik:0>10}")).unwrap_or_default(),
+            issuer_name: xml::find_text(&root, &["issuerName"]).unwrap_or_default(),
+            issuer_cusip: xml::find_text(&root, &["issuerCusip", "cusipNumber"]).unwrap_or_default(),
+            amendment_no: xml::find_text(&root, &["amendmentNo"]).and_then(|t| t.parse().ok()),
+            ..Default::default()
+        };
+
+        let mut persons = Vec::<&Element>::new();
+        xml::descendants(&root, &["reportingPersonInfo", "coverPageHeaderReportingPersonDetails"], &mut persons);
+
+        let events: Vec<BeneficialOwnership> = persons.into_iter()
+            .map(|person| BeneficialOwnership {
+                filer_cik: xml::find_text(person, &["reportingPersonCik"]).map(|cik| format!("{cik:0>10}")).unwrap_or_default(),
+                filer_name: xml::find_text(person, &["reportingPersonName"]).unwrap_or_default(),
+                sole_voting: Self::number(person, &["soleVotingPower"]),
+                shared_voting: Self::number(person, &["sharedVotingPower"]),
+                sole_dispositive: Self::number(person, &["soleDispositivePower"]),
+                shared_dispositive: Self::number(person, &["sharedDispositivePower"]),
+                aggregate_shares: Self::number(person, &["aggregateAmountOwned", "reportingPersonBeneficiallyOwnedAggregateNumberOfShares"]),
+                percent_of_class: Self::number(person, &["percentOfClass", "classPercent"]),
+                ..statement.clone()
+            })
+            .collect();
+
+        if events.is_empty() {
+            return Err(ParseError::MissingElement {
+                access_no: self.access_no.clone(),
+                path: "edgarSubmission/formData/reportingPersons".to_string(),
+            });
+        }
+
+        Ok(events)
+    }
+
+    /// The first cover page of an HTML or text statement. Only one event is read, the
+    /// reporting person is the filer of the SEC header.
+    pub fn extract_text(&self, form_type: &str, content: &str) -> Result<Vec<BeneficialOwnership>, ParseError> {
+        let tags = Regex::new(r"<[^>]*>").unwrap();
+        let text = tags.replace_all(content, " ")
+            .replace("&nbsp;", " ")
+            .replace("&#160;", " ")
+            .replace("&amp;", "&");
+
+        let mut event = BeneficialOwnership {
+            access_no: self.access_no.clone(),
+            form_type: form_type.to_string(),
+            form_url: self.url.clone(),
+            issuer_cusip: CUSIP.captures(&text)
+                .map(|c| c[1].split_whitespace().collect::<String>())
+                .unwrap_or_default(),
+            amendment_no: AMENDMENT.captures(&text).and_then(|c| c[1].parse().ok()),
+            ..Default::default()
+        };
+
+        for (field, pattern) in COVER_ROWS.iter() {
+            let value = pattern.captures(&text).and_then(|c| Self::parse_number(&c[1]));
+
+            match *field {
+                "sole_voting" => event.sole_voting = value,
+                "shared_voting" => event.shared_voting = value,
+                "sole_dispositive" => event.sole_dispositive = value,
+                "shared_dispositive" => event.shared_dispositive = value,
+                "aggregate_shares" => event.aggregate_shares = value,
+                _ => event.percent_of_class = value,
+            }
+        }
+
+        if event.aggregate_shares.is_none() && event.percent_of_class.is_none() {
+            return Err(ParseError::MissingElement {
+                access_no: self.access_no.clone(),
+                path: "cover page/aggregate amount beneficially owned".to_string(),
+            });
+        }
+
+        Ok(vec![event])
+    }
+
+    fn number(el: &Element, names: &[&str]) -> Option<BigDecimal> {
+        xml::find_text(el, names).and_then(|t| Self::parse_number(&t))
+    }
+
+    fn parse_number(text: &str) -> Option<BigDecimal> {
+        BigDecimal::from_str(&text.replace([',', '%'], "")).ok()
+    }
+
+    fn parse_date(text: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(text, "%m/%d/%Y")
+            .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d"))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://www.sec.gov/Archives/edgar/data/320193/0000950170-25-012345.txt";
+
+    #[test]
+    fn xml_statement_has_one_event_per_person() {
+        let xml = r#"<edgarSubmission xmlns="http://www.sec.gov/edgar/schedule13D">
+            <headerData><submissionType>SCHEDULE 13D/A</submissionType></headerData>
+            <formData>
+                <coverPageHeader>
+                    <amendmentNo>2</amendmentNo>
+                    <securitiesClassTitle>Common Stock</securitiesClassTitle>
+                    <dateOfEvent>03/14/2025</dateOfEvent>
+                    <issuerInfo>
+                        <issuerCIK>320193</issuerCIK>
+                        <issuerCUSIP>037833100</issuerCUSIP>
+                        <issuerName>Apple Inc.</issuerName>
+                    </issuerInfo>
+                </coverPageHeader>
+                <reportingPersons>
+                    <reportingPersonInfo>
+                        <reportingPersonCIK>0001067983</reportingPersonCIK>
+                        <reportingPersonName>Berkshire Hathaway Inc.</reportingPersonName>
+                        <soleVotingPower>0</soleVotingPower>
+                        <sharedVotingPower>300000000</sharedVotingPower>
+                        <soleDispositivePower>0</soleDispositivePower>
+                        <sharedDispositivePower>300000000</sharedDispositivePower>
+                        <aggregateAmountOwned>300000000</aggregateAmountOwned>
+                        <percentOfClass>2.0</percentOfClass>
+                    </reportingPersonInfo>
+                    <reportingPersonInfo>
+                        <reportingPersonName>Warren E. Buffett</reportingPersonName>
+                        <aggregateAmountOwned>300000000</aggregateAmountOwned>
+                        <percentOfClass>2.0</percentOfClass>
+                    </reportingPersonInfo>
+                </reportingPersons>
+            </formData>
+        </edgarSubmission>"#;
+
+        let events = Schedule13::new(URL).extract_xml(xml).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].form_type, "SCHEDULE 13D/A");
+        assert_eq!(events[0].amendment_no, Some(2));
+        assert_eq!(events[0].issuer_cik, "0000320193");
+        assert_eq!(events[0].issuer_cusip, "037833100");
+        assert_eq!(events[0].event_date, NaiveDate::from_ymd_opt(2025, 3, 14));
+        assert_eq!(events[0].filer_cik, "0001067983");
+        assert_eq!(events[0].shared_voting, BigDecimal::from_str("300000000").ok());
+        assert_eq!(events[1].filer_name, "Warren E. Buffett");
+        assert_eq!(events[1].percent_of_class, BigDecimal::from_str("2.0").ok());
+    }
+
+    #[test]
+    fn text_cover_page() {
+        let html = "<p>SCHEDULE 13G (Amendment No. 4)</p><p>CUSIP No. 037833 10 0</p>\
+            <table><tr><td>5</td><td>SOLE VOTING POWER</td><td>1,234,567</td></tr>\
+            <tr><td>6</td><td>SHARED VOTING POWER</td><td>0</td></tr>\
+            <tr><td>7</td><td>SOLE DISPOSITIVE POWER</td><td>1,300,000</td></tr>\
+            <tr><td>8</td><td>SHARED DISPOSITIVE POWER</td><td>0</td></tr>\
+            <tr><td>9</td><td>AGGREGATE AMOUNT BENEFICIALLY OWNED BY EACH REPORTING PERSON</td><td>1,300,000</td></tr>\
+            <tr><td>11</td><td>PERCENT OF CLASS REPRESENTED BY AMOUNT IN ROW (9)</td><td>6.1%</td></tr></table>";
+
+        let events = Schedule13::new(URL).extract_text("SC 13G/A", html).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.issuer_cusip, "037833100");
+        assert_eq!(event.amendment_no, Some(4));
+        assert_eq!(event.sole_voting, BigDecimal::from_str("1234567").ok());
+        assert_eq!(event.shared_voting, BigDecimal::from_str("0").ok());
+        assert_eq!(event.sole_dispositive, BigDecimal::from_str("1300000").ok());
+        assert_eq!(event.aggregate_shares, BigDecimal::from_str("1300000").ok());
+        assert_eq!(event.percent_of_class, BigDecimal::from_str("6.1").ok());
+    }
+}
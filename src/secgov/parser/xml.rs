@@ -0,0 +1,25 @@
+use minidom::Element;
+
+/// Elements below `el` named any of `names`, ignoring case, without looking inside a match.
+///
+/// EDGAR schemas name the same field differently from one form to another (`issuerCIK`,
+/// `issuerCik`), so the newer XML forms are read by name instead of by fixed path.
+pub(crate) fn descendants<'a>(el: &'a Element, names: &[&str], found: &mut Vec<&'a Element>) {
+    for child in el.children() {
+        if names.iter().any(|n| n.eq_ignore_ascii_case(child.name())) {
+            found.push(child);
+        } else {
+            descendants(child, names, found);
+        }
+    }
+}
+
+/// Trimmed text of the first non empty element below `el` named any of `names`.
+pub(crate) fn find_text(el: &Element, names: &[&str]) -> Option<String> {
+    let mut found = Vec::new();
+    descendants(el, names, &mut found);
+
+    found.into_iter()
+        .map(|e| e.text().trim().to_string())
+        .find(|t| !t.is_empty())
+}
@@ -0,0 +1,379 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use minidom::{Element, Node};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use super::metalinks::MetaLinks;
+use crate::secgov::models::XbrlFact;
+use crate::secgov::parser::atomfilings::XMLFiling;
+use crate::secgov::parser::error::ParseError;
+use crate::secgov::parser::xml;
+
+/// XML declaration, comments and doctype before the root element, which the parser rejects.
+static PROLOG: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:\s*<\?[\s\S]*?\?>|\s*<!--[\s\S]*?-->|\s*<!DOCTYPE[^>]*>)*").unwrap());
+
+/// Prefixes of the standard taxonomies, for instances without `MetaLinks.json`.
+const TAXONOMIES: [(&str, &str); 4] = [
+    ("fasb.org/us-gaap/", "us-gaap"),
+    ("fasb.org/srt/", "srt"),
+    ("xbrl.sec.gov/dei/", "dei"),
+    ("xbrl.ifrs.org/taxonomy/", "ifrs-full"),
+];
+
+struct Context {
+    start: Option<NaiveDate>,
+    end: NaiveDate,
+    dimensions: String,
+}
+
+/// A numeric fact as written in the document, before its context and unit are resolved.
+struct RawFact {
+    taxonomy: String,
+    concept: String,
+    context: String,
+    unit: String,
+    decimals: Option<i32>,
+    value: BigDecimal,
+}
+
+/// XBRL instance of a 10-K or 10-Q, either a plain instance (`xbrl` root) or an inline XBRL
+/// document (`html` root) whose facts are tagged in the report itself.
+pub struct XbrlInstance {
+    pub url: String,
+    pub access_no: String,
+}
+
+impl XbrlInstance {
+    pub fn new(url: &str) -> XbrlInstance {
+        XbrlInstance {
+            url: url.to_string(),
+            access_no: XMLFiling::parse_access_num(url).unwrap_or_default(),
+        }
+    }
+
+    /// Numeric facts of the instance, labelled from `links` when given. A fact repeated with
+    /// the same concept, context and unit is kept once, nil facts and facts whose context has
+    /// no period are left out.
+    pub fn extract_facts(&self, input: &str, links: Option<&MetaLinks>) -> Result<Vec<XbrlFact>, ParseError> {
+        self.parse_instance(input, links)
+            .map_err(|err| err.with_access_no(&self.access_no))
+    }
+
+    fn parse_instance(&self, input: &str, links: Option<&MetaLinks>) -> Result<Vec<XbrlFact>, ParseError> {
+        let body = PROLOG.replace(input, "");
+        let root: Element = body.trim_start().parse().map_err(|err: minidom::Error| ParseError::MalformedXml {
+            access_no: String::new(),
+            reason: err.to_string(),
+        })?;
+
+        let inline = root.name() == "html";
+        if !inline && root.name() != "xbrl" {
+            return Err(ParseError::UnsupportedDocumentType { access_no: String::new(), document_type: root.name().to_string() });
+        }
+
+        let contexts = Self::contexts(&root);
+        let units = Self::units(&root);
+
+        let dei = |name: &str| if inline { Self::inline_text(&root, name) } else { Self::instance_text(&root, name) };
+        let document = XbrlFact {
+            access_no: self.access_no.clone(),
+            form_url: self.url.clone(),
+            form_type: dei("DocumentType").unwrap_or_default(),
+            issuer_cik: dei("EntityCentralIndexKey").map(|cik| format!("{cik:0>10}")).unwrap_or_default(),
+            issuer_name: dei("EntityRegistrantName").unwrap_or_default(),
+            fiscal_year: dei("DocumentFiscalYearFocus").and_then(|y| y.parse().ok()),
+            fiscal_period: dei("DocumentFiscalPeriodFocus").unwrap_or_default(),
+            ..Default::default()
+        };
+
+        let raw = if inline { Self::inline_facts(&root) } else { Self::instance_facts(&root, links) };
+
+        let mut seen = HashSet::new();
+        let facts = raw.into_iter()
+            .filter(|f| seen.insert((f.taxonomy.clone(), f.concept.clone(), f.context.clone(), f.unit.clone())))
+            .filter_map(|f| {
+                let context = contexts.get(&f.context)?;
+                let (report, report_group) = links.and_then(|l| l.report(&f.taxonomy, &f.concept)).unwrap_or_default();
+
+                Some(XbrlFact {
+                    label: links.and_then(|l| l.label(&f.taxonomy, &f.concept)).unwrap_or_default().to_string(),
+                    report: report.to_string(),
+                    report_group: report_group.to_string(),
+                    unit: units.get(&f.unit).cloned().unwrap_or(f.unit),
+                    value: f.value,
+                    decimals: f.decimals,
+                    period_start: context.start,
+                    period_end: context.end,
+                    dimensions: context.dimensions.clone(),
+                    taxonomy: f.taxonomy,
+                    concept: f.concept,
+                    ..document.clone()
+                })
+            })
+            .collect();
+
+        Ok(facts)
+    }
+
+    fn contexts(root: &Element) -> HashMap<String, Context> {
+        let mut found = Vec::new();
+        xml::descendants(root, &["context"], &mut found);
+
+        found.into_iter()
+            .filter_map(|ctx| {
+                let date = |names: &[&str]| xml::find_text(ctx, names)
+                    .and_then(|t| NaiveDate::parse_from_str(t.get(..10)?, "%Y-%m-%d").ok());
+
+                let mut members = Vec::new();
+                xml::descendants(ctx, &["explicitMember", "typedMember"], &mut members);
+                let mut dimensions: Vec<String> = members.into_iter()
+                    .map(|m| format!("{}={}", m.attr("dimension").unwrap_or_default(), Self::all_text(m).trim()))
+                    .collect();
+                dimensions.sort();
+
+                let context = Context {
+                    start: date(&["startDate"]),
+                    end: date(&["endDate", "instant"])?,
+                    dimensions: dimensions.join(";"),
+                };
+
+                Some((ctx.attr("id")?.to_string(), context))
+            })
+            .collect()
+    }
+
+    /// Units by id, `USD/shares` for a divide unit and the measures without their prefix.
+    fn units(root: &Element) -> HashMap<String, String> {
+        let mut found = Vec::new();
+        xml::descendants(root, &["unit"], &mut found);
+
+        let measures = |el: &Element| {
+            let mut found = Vec::new();
+            xml::descendants(el, &["measure"], &mut found);
+            found.iter()
+                .map(|m| { let text = m.text(); text.trim().rsplit(':').next().unwrap_or_default().to_string() })
+                .collect::<Vec<_>>()
+                .join("*")
+        };
+
+        found.into_iter()
+            .filter_map(|unit| {
+                let value = match unit.children().find(|c| c.name() == "divide") {
+                    Some(divide) => {
+                        let part = |name| divide.children().find(|c| c.name() == name).map(measures).unwrap_or_default();
+                        format!("{}/{}", part("unitNumerator"), part("unitDenominator"))
+                    }
+                    None => measures(unit),
+                };
+
+                Some((unit.attr("id")?.to_string(), value))
+            })
+            .collect()
+    }
+
+    /// Facts of a plain instance are the children of the root, numeric ones carry a unit.
+    fn instance_facts(root: &Element, links: Option<&MetaLinks>) -> Vec<RawFact> {
+        root.children()
+            .filter(|el| el.attr("xsi:nil") != Some("true"))
+            .filter_map(|el| {
+                let ns = el.ns();
+                let taxonomy = links.and_then(|l| l.prefix(&ns)).map(str::to_string)
+                    .or_else(|| TAXONOMIES.iter().find(|(uri, _)| ns.contains(uri)).map(|(_, prefix)| prefix.to_string()))
+                    .unwrap_or(ns);
+
+                Some(RawFact {
+                    taxonomy,
+                    concept: el.name().to_string(),
+                    context: el.attr("contextRef")?.to_string(),
+                    unit: el.attr("unitRef")?.to_string(),
+                    decimals: el.attr("decimals").and_then(|d| d.parse().ok()),
+                    value: BigDecimal::from_str(el.text().trim()).ok()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Numeric facts of an inline document are `ix:nonFraction` elements, whose displayed
+    /// value is scaled and signed by their attributes.
+    fn inline_facts(root: &Element) -> Vec<RawFact> {
+        let mut found = Vec::new();
+        xml::descendants(root, &["nonFraction"], &mut found);
+
+        found.into_iter()
+            .filter(|el| el.attr("xsi:nil") != Some("true"))
+            .filter_map(|el| {
+                let (taxonomy, concept) = el.attr("name")?.split_once(':')?;
+                let mut value = Self::inline_number(&Self::all_text(el), el.attr("format").unwrap_or_default())?;
+
+                if let Some(scale) = el.attr("scale").filter(|s| *s != "0") {
+                    value *= BigDecimal::from_str(&format!("1e{scale}")).ok()?;
+                }
+                if el.attr("sign") == Some("-") {
+                    value = -value;
+                }
+
+                Some(RawFact {
+                    taxonomy: taxonomy.to_string(),
+                    concept: concept.to_string(),
+                    context: el.attr("contextRef")?.to_string(),
+                    unit: el.attr("unitRef")?.to_string(),
+                    decimals: el.attr("decimals").and_then(|d| d.parse().ok()),
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Displayed number of an inline fact under its `ixt` format. Dashes and words like `none`
+    /// stand for zero.
+    fn inline_number(text: &str, format: &str) -> Option<BigDecimal> {
+        let text = text.trim();
+        let format = format.rsplit(':').next().unwrap_or_default();
+
+        if format == "fixed-zero" || format == "fixedzero" || matches!(text, "-" | "—" | "–") {
+            return Some(BigDecimal::from(0));
+        }
+        if format == "numwordsen" && matches!(text.to_lowercase().as_str(), "no" | "none" | "zero") {
+            return Some(BigDecimal::from(0));
+        }
+
+        let digits: String = if format == "num-comma-decimal" || format == "numcommadecimal" {
+            text.chars().filter(|c| !matches!(c, '.' | ' ' | '\u{a0}')).map(|c| if c == ',' { '.' } else { c }).collect()
+        } else {
+            text.chars().filter(|c| !matches!(c, ',' | ' ' | '\u{a0}')).collect()
+        };
+
+        BigDecimal::from_str(&digits).ok()
+    }
+
+    fn instance_text(root: &Element, name: &str) -> Option<String> {
+        root.children()
+            .find(|el| el.name() == name && el.ns().contains("xbrl.sec.gov/dei/"))
+            .map(|el| el.text().trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    fn inline_text(root: &Element, name: &str) -> Option<String> {
+        let mut found = Vec::new();
+        xml::descendants(root, &["nonNumeric"], &mut found);
+
+        found.into_iter()
+            .find(|el| el.attr("name") == Some(&format!("dei:{name}")))
+            .map(|el| Self::all_text(el).trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    /// Text of an element and everything below it, inline facts are often wrapped in spans.
+    fn all_text(el: &Element) -> String {
+        el.nodes()
+            .map(|node| match node {
+                Node::Element(child) => Self::all_text(child),
+                Node::Text(text) => text.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://www.sec.gov/Archives/edgar/data/320193/0000320193-24-000123.txt";
+
+    #[test]
+    fn instance_facts_with_contexts_and_units() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <xbrli:xbrl xmlns:xbrli="http://www.xbrl.org/2003/instance" xmlns:dei="http://xbrl.sec.gov/dei/2024"
+                xmlns:us-gaap="http://fasb.org/us-gaap/2024" xmlns:xbrldi="http://xbrl.org/2006/xbrldi"
+                xmlns:iso4217="http://www.xbrl.org/2003/iso4217" xmlns:srt="http://fasb.org/srt/2024"
+                xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+                <xbrli:context id="c-1">
+                    <xbrli:entity><xbrli:identifier scheme="http://www.sec.gov/CIK">0000320193</xbrli:identifier></xbrli:entity>
+                    <xbrli:period><xbrli:startDate>2023-10-01</xbrli:startDate><xbrli:endDate>2024-09-28</xbrli:endDate></xbrli:period>
+                </xbrli:context>
+                <xbrli:context id="c-2">
+                    <xbrli:entity>
+                        <xbrli:identifier scheme="http://www.sec.gov/CIK">0000320193</xbrli:identifier>
+                        <xbrli:segment><xbrldi:explicitMember dimension="srt:ProductOrServiceAxis">us-gaap:ProductMember</xbrldi:explicitMember></xbrli:segment>
+                    </xbrli:entity>
+                    <xbrli:period><xbrli:instant>2024-09-28</xbrli:instant></xbrli:period>
+                </xbrli:context>
+                <xbrli:unit id="usd"><xbrli:measure>iso4217:USD</xbrli:measure></xbrli:unit>
+                <xbrli:unit id="usdPerShare">
+                    <xbrli:divide>
+                        <xbrli:unitNumerator><xbrli:measure>iso4217:USD</xbrli:measure></xbrli:unitNumerator>
+                        <xbrli:unitDenominator><xbrli:measure>xbrli:shares</xbrli:measure></xbrli:unitDenominator>
+                    </xbrli:divide>
+                </xbrli:unit>
+                <dei:DocumentType contextRef="c-1">10-K</dei:DocumentType>
+                <dei:EntityCentralIndexKey contextRef="c-1">0000320193</dei:EntityCentralIndexKey>
+                <dei:EntityRegistrantName contextRef="c-1">Apple Inc.</dei:EntityRegistrantName>
+                <dei:DocumentFiscalYearFocus contextRef="c-1">2024</dei:DocumentFiscalYearFocus>
+                <dei:DocumentFiscalPeriodFocus contextRef="c-1">FY</dei:DocumentFiscalPeriodFocus>
+                <us-gaap:RevenueFromContractWithCustomerExcludingAssessedTax contextRef="c-1" unitRef="usd" decimals="-6">391035000000</us-gaap:RevenueFromContractWithCustomerExcludingAssessedTax>
+                <us-gaap:RevenueFromContractWithCustomerExcludingAssessedTax contextRef="c-1" unitRef="usd" decimals="-6">391035000000</us-gaap:RevenueFromContractWithCustomerExcludingAssessedTax>
+                <us-gaap:EarningsPerShareDiluted contextRef="c-1" unitRef="usdPerShare" decimals="2">6.08</us-gaap:EarningsPerShareDiluted>
+                <us-gaap:InventoryNet contextRef="c-2" unitRef="usd" decimals="INF">7286000000</us-gaap:InventoryNet>
+                <us-gaap:Goodwill contextRef="c-2" unitRef="usd" xsi:nil="true"/>
+            </xbrli:xbrl>"#;
+
+        let facts = XbrlInstance::new(URL).extract_facts(xml, None).unwrap();
+
+        assert_eq!(facts.len(), 3);
+        let revenue = &facts[0];
+        assert_eq!(revenue.access_no, "0000320193-24-000123");
+        assert_eq!(revenue.form_type, "10-K");
+        assert_eq!(revenue.issuer_cik, "0000320193");
+        assert_eq!(revenue.fiscal_year, Some(2024));
+        assert_eq!(revenue.taxonomy, "us-gaap");
+        assert_eq!(revenue.value, BigDecimal::from_str("391035000000").unwrap());
+        assert_eq!(revenue.unit, "USD");
+        assert_eq!(revenue.decimals, Some(-6));
+        assert_eq!(revenue.period_start, NaiveDate::from_ymd_opt(2023, 10, 1));
+        assert_eq!(revenue.dimensions, "");
+
+        assert_eq!(facts[1].unit, "USD/shares");
+
+        let inventory = &facts[2];
+        assert_eq!(inventory.decimals, None);
+        assert_eq!(inventory.period_start, None);
+        assert_eq!(inventory.period_end, NaiveDate::from_ymd_opt(2024, 9, 28).unwrap());
+        assert_eq!(inventory.dimensions, "srt:ProductOrServiceAxis=us-gaap:ProductMember");
+    }
+
+    #[test]
+    fn inline_facts_are_scaled_and_signed() {
+        let html = r#"<?xml version="1.0" encoding="utf-8"?>
+            <!DOCTYPE html>
+            <html xmlns="http://www.w3.org/1999/xhtml" xmlns:ix="http://www.xbrl.org/2013/inlineXBRL"
+                xmlns:xbrli="http://www.xbrl.org/2003/instance" xmlns:ixt="http://www.xbrl.org/inlineXBRL/transformation/2020-02-12">
+                <body>
+                    <div style="display:none"><ix:header><ix:resources>
+                        <xbrli:context id="c-1">
+                            <xbrli:entity><xbrli:identifier scheme="http://www.sec.gov/CIK">0000320193</xbrli:identifier></xbrli:entity>
+                            <xbrli:period><xbrli:startDate>2024-06-30</xbrli:startDate><xbrli:endDate>2024-09-28</xbrli:endDate></xbrli:period>
+                        </xbrli:context>
+                        <xbrli:unit id="usd"><xbrli:measure>iso4217:USD</xbrli:measure></xbrli:unit>
+                    </ix:resources></ix:header></div>
+                    <p>Form <ix:nonNumeric name="dei:DocumentType" contextRef="c-1">10-Q</ix:nonNumeric>
+                        of <ix:nonNumeric name="dei:EntityRegistrantName" contextRef="c-1"><span>Apple</span> Inc.</ix:nonNumeric></p>
+                    <td>$ <ix:nonFraction name="us-gaap:NetIncomeLoss" contextRef="c-1" unitRef="usd" decimals="-6" scale="6" format="ixt:num-dot-decimal">14,736</ix:nonFraction></td>
+                    <td>(<ix:nonFraction name="us-gaap:OtherNonoperatingIncomeExpense" contextRef="c-1" unitRef="usd" decimals="-6" scale="6" sign="-" format="ixt:num-dot-decimal">1.5</ix:nonFraction>)</td>
+                    <td><ix:nonFraction name="us-gaap:Goodwill" contextRef="c-1" unitRef="usd" decimals="INF" format="ixt:fixed-zero">—</ix:nonFraction></td>
+                </body>
+            </html>"#;
+
+        let facts = XbrlInstance::new(URL).extract_facts(html, None).unwrap();
+
+        assert_eq!(facts.len(), 3);
+        assert_eq!(facts[0].form_type, "10-Q");
+        assert_eq!(facts[0].issuer_name, "Apple Inc.");
+        assert_eq!(facts[0].concept, "NetIncomeLoss");
+        assert_eq!(facts[0].value, BigDecimal::from_str("14736000000").unwrap());
+        assert_eq!(facts[1].value, BigDecimal::from_str("-1500000").unwrap());
+        assert_eq!(facts[2].value, BigDecimal::from(0));
+    }
+}
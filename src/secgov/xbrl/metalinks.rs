@@ -0,0 +1,157 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct MetaLinksFile {
+    instance: HashMap<String, InstanceLinks>,
+}
+
+#[derive(Deserialize)]
+struct InstanceLinks {
+    #[serde(default)]
+    dts: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    report: HashMap<String, Report>,
+    #[serde(default)]
+    tag: HashMap<String, Tag>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Report {
+    role: String,
+    #[serde(default)]
+    short_name: String,
+    #[serde(default)]
+    menu_cat: String,
+}
+
+#[derive(Deserialize)]
+struct Tag {
+    #[serde(default)]
+    nsuri: String,
+    #[serde(default)]
+    presentation: Vec<String>,
+    #[serde(default)]
+    lang: HashMap<String, Lang>,
+}
+
+#[derive(Deserialize)]
+struct Lang {
+    role: Roles,
+}
+
+#[derive(Deserialize)]
+struct Roles {
+    label: Option<String>,
+}
+
+/// `MetaLinks.json` of a filing, written by the SEC renderer next to the XBRL documents. It
+/// names the instance and gives the labels of every concept and the reports (R files) that
+/// present them.
+pub struct MetaLinks {
+    /// File name of the instance, the inline document for inline filings.
+    pub instance: String,
+    pub inline: bool,
+    /// Reports by role, with their number so the first one presenting a concept wins.
+    reports: HashMap<String, (u32, Report)>,
+    /// Prefix of each namespace, taken from the `prefix_concept` keys of the tags.
+    prefixes: HashMap<String, String>,
+    tags: HashMap<String, Tag>,
+}
+
+impl MetaLinks {
+    pub fn parse(json: &str) -> Result<MetaLinks, serde_json::Error> {
+        let file: MetaLinksFile = serde_json::from_str(json)?;
+
+        // a submission has a single instance
+        let (instance, links) = file.instance.into_iter().next()
+            .ok_or_else(|| serde::de::Error::custom("MetaLinks.json lists no instance"))?;
+
+        let reports = links.report.into_iter()
+            .map(|(key, report)| {
+                let number = key.trim_start_matches('R').parse().unwrap_or(u32::MAX);
+                (report.role.clone(), (number, report))
+            })
+            .collect();
+
+        let prefixes = links.tag.iter()
+            .filter_map(|(key, tag)| Some((tag.nsuri.clone(), key.split_once('_')?.0.to_string())))
+            .collect();
+
+        Ok(MetaLinks {
+            instance,
+            inline: links.dts.contains_key("inline"),
+            reports,
+            prefixes,
+            tags: links.tag,
+        })
+    }
+
+    /// Prefix the filing uses for a namespace, e.g. `us-gaap` for `http://fasb.org/us-gaap/2024`.
+    pub fn prefix(&self, nsuri: &str) -> Option<&str> {
+        self.prefixes.get(nsuri).map(String::as_str)
+    }
+
+    /// Standard label of a concept, in US English.
+    pub fn label(&self, prefix: &str, concept: &str) -> Option<&str> {
+        self.tag(prefix, concept)?
+            .lang.get("en-us")?
+            .role.label.as_deref()
+    }
+
+    /// Short name and menu category of the first report presenting a concept.
+    pub fn report(&self, prefix: &str, concept: &str) -> Option<(&str, &str)> {
+        self.tag(prefix, concept)?
+            .presentation.iter()
+            .filter_map(|role| self.reports.get(role))
+            .min_by_key(|(number, _)| *number)
+            .map(|(_, report)| (report.short_name.as_str(), report.menu_cat.as_str()))
+    }
+
+    fn tag(&self, prefix: &str, concept: &str) -> Option<&Tag> {
+        self.tags.get(&format!("{prefix}_{concept}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_and_first_report() {
+        let json = r#"{
+            "version": "2.2",
+            "instance": {
+                "aapl-20240928.htm": {
+                    "nsprefix": "aapl",
+                    "dts": { "inline": { "local": ["aapl-20240928.htm"] } },
+                    "report": {
+                        "R4": { "role": "http://www.apple.com/role/CONSOLIDATEDSTATEMENTSOFOPERATIONS", "longName": "0000004 - Statement",
+                                "shortName": "CONSOLIDATED STATEMENTS OF OPERATIONS", "menuCat": "Statements", "order": "4" },
+                        "R40": { "role": "http://www.apple.com/role/RevenueDetails", "shortName": "Revenue (Details)", "menuCat": "Details", "order": "40" }
+                    },
+                    "tag": {
+                        "us-gaap_RevenueFromContractWithCustomerExcludingAssessedTax": {
+                            "xbrltype": "monetaryItemType",
+                            "nsuri": "http://fasb.org/us-gaap/2024",
+                            "localname": "RevenueFromContractWithCustomerExcludingAssessedTax",
+                            "presentation": ["http://www.apple.com/role/RevenueDetails", "http://www.apple.com/role/CONSOLIDATEDSTATEMENTSOFOPERATIONS"],
+                            "lang": { "en-us": { "role": { "label": "Revenue from Contract with Customer, Excluding Assessed Tax", "terseLabel": "Net sales" } } }
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        let links = MetaLinks::parse(json).unwrap();
+        let concept = "RevenueFromContractWithCustomerExcludingAssessedTax";
+
+        assert_eq!(links.instance, "aapl-20240928.htm");
+        assert!(links.inline);
+        assert_eq!(links.prefix("http://fasb.org/us-gaap/2024"), Some("us-gaap"));
+        assert_eq!(links.label("us-gaap", concept), Some("Revenue from Contract with Customer, Excluding Assessed Tax"));
+        assert_eq!(links.report("us-gaap", concept), Some(("CONSOLIDATED STATEMENTS OF OPERATIONS", "Statements")));
+        assert_eq!(links.report("us-gaap", "Assets"), None);
+    }
+}
@@ -0,0 +1,83 @@
+mod instance;
+mod metalinks;
+
+pub use self::instance::XbrlInstance;
+pub use self::metalinks::MetaLinks;
+
+use std::error::Error;
+
+use super::models::XbrlFact;
+use super::parser::error::ParseError;
+use super::{FilingIndex, IndexEntry, BASEURL};
+
+/// The document holding the facts among the files of an accession directory. The plain
+/// instance the SEC extracts from an inline document (`*_htm.xml`) is preferred over the
+/// inline document itself. Without `MetaLinks.json` any xml that is not a linkbase is taken.
+pub fn instance_document(items: &[String], links: Option<&MetaLinks>) -> Option<String> {
+    if let Some(links) = links {
+        let extracted = format!("{}_htm.xml", links.instance.trim_end_matches(".htm"));
+        if links.inline && items.contains(&extracted) {
+            return Some(extracted);
+        }
+        return Some(links.instance.clone());
+    }
+
+    let linkbase = ["_cal.xml", "_def.xml", "_lab.xml", "_pre.xml"];
+    items.iter()
+        .find(|name| name.ends_with("_htm.xml"))
+        .or_else(|| items.iter().find(|name| {
+            name.ends_with(".xml") && name.as_str() != "FilingSummary.xml" && !linkbase.iter().any(|l| name.ends_with(l))
+        }))
+        .cloned()
+}
+
+/// The numeric facts of the 10-K or 10-Q of an index entry, read from its XBRL instance and
+/// labelled with its `MetaLinks.json` when the filing has one.
+pub async fn get_facts(entry: &IndexEntry) -> Result<Vec<XbrlFact>, Box<dyn Error>> {
+    let directory = FilingIndex::fetch_directory(entry).await?;
+    let items = directory.names();
+
+    let links = match directory.contains("MetaLinks.json") {
+        true => Some(MetaLinks::parse(&directory.get("MetaLinks.json").await?)?),
+        false => None,
+    };
+
+    let document = instance_document(&items, links.as_ref())
+        .ok_or_else(|| ParseError::NoXmlPayload { access_no: directory.access_no.clone() })?;
+
+    let mut facts = XbrlInstance::new(&format!("{BASEURL}{}", entry.filepath)).extract_facts(&directory.get(&document).await?, links.as_ref())?;
+
+    // the dei cover facts are optional on old instances, the index knows the filer
+    for fact in &mut facts {
+        if fact.issuer_cik.is_empty() { fact.issuer_cik = format!("{:0>10}", entry.company_cik); }
+        if fact.issuer_name.is_empty() { fact.issuer_name = entry.company_name.clone(); }
+        if fact.form_type.is_empty() { fact.form_type = entry.form_type.clone(); }
+    }
+
+    Ok(facts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn instance_document_prefers_the_extracted_instance() {
+        let entry = IndexEntry {
+            company_cik: "320193".to_string(),
+            company_name: "APPLE INC.".to_string(),
+            form_type: "10-K".to_string(),
+            file_date: NaiveDate::from_ymd_opt(2024, 11, 1).unwrap(),
+            filepath: "edgar/data/320193/0000320193-24-000123.txt".to_string(),
+        };
+        assert_eq!(FilingIndex::dir_url(&entry).unwrap(), "https://www.sec.gov/Archives/edgar/data/320193/000032019324000123/");
+
+        let items: Vec<String> = ["aapl-20240928.htm", "aapl-20240928.xsd", "aapl-20240928_cal.xml", "aapl-20240928_htm.xml", "FilingSummary.xml", "MetaLinks.json"]
+            .iter().map(|s| s.to_string()).collect();
+        assert_eq!(instance_document(&items, None).unwrap(), "aapl-20240928_htm.xml");
+
+        let old: Vec<String> = ["FilingSummary.xml", "abc-20101231_lab.xml", "abc-20101231.xml"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(instance_document(&old, None).unwrap(), "abc-20101231.xml");
+    }
+}